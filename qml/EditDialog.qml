@@ -18,9 +18,11 @@ Window {
     property var mediaModel
     property string activePage: "Movie"
     property string activeStatus: "On Drive"
+    property var statusOptions: ["On Drive", "To Download", "To Work On"]
 
     property bool isEditing: false
     property int editingId: -1
+    property string externalUrl: "" // IMDb/TMDB/AniList page of the item being edited
     property bool searching: false
 
     palette {
@@ -44,9 +46,11 @@ Window {
     // ---- QML-side selection state (avoids model resets & scroll jumps) ----
     property var selectedIndices: ({})   // { rowIndex: true, ... }
     property int selectedCount: 0
+    property string resultSort: "relevance" // persists across searches while the dialog lives
     signal aboutToSave()
     property int lastClickedIndex: -1
     property bool hasSearched: false     // true after first search in this session
+    property var searchHistory: []       // recent queries for this page, newest first
     property var qualityOptions: []
 
     function clearSelection() {
@@ -63,6 +67,23 @@ Window {
         lastClickedIndex = idx
     }
 
+    // Results already in the library can't be picked when adding, unless
+    // duplicates are allowed (SearchModel role 266 = alreadyOwned)
+    function isSelectable(idx) {
+        return isEditing || allowDuplicatesCheck.checked || !searchModel.data(searchModel.index(idx, 0), 266)
+    }
+
+    // Owned rows stay selected only while duplicates are allowed
+    function dropUnselectableResults() {
+        var sel = {}
+        var keys = Object.keys(selectedIndices)
+        for (var i = 0; i < keys.length; i++) {
+            if (isSelectable(parseInt(keys[i]))) sel[keys[i]] = true
+        }
+        selectedIndices = sel
+        selectedCount = Object.keys(sel).length
+    }
+
     function toggleSelect(idx) {
         var sel = {}
         var keys = Object.keys(selectedIndices)
@@ -88,15 +109,56 @@ Window {
         var end = Math.max(lastClickedIndex, idx)
         var sel = {}
         for (var i = start; i <= end; i++) {
-            sel[i] = true
+            if (isSelectable(i)) sel[i] = true
         }
         selectedIndices = sel
         selectedCount = Object.keys(sel).length
         // keep lastClickedIndex as the anchor
     }
 
+    // Bulk selection for adding many results at once. Rows are in result
+    // order, so row numbers double as result indices.
+    function selectAllResults() {
+        var sel = {}
+        for (var i = 0; i < searchResultsList.count; i++) {
+            if (isSelectable(i)) sel[i] = true
+        }
+        selectedIndices = sel
+        selectedCount = Object.keys(sel).length
+        lastClickedIndex = -1
+        searchModel.selectAll()
+    }
+
+    function invertResultSelection() {
+        var sel = {}
+        for (var i = 0; i < searchResultsList.count; i++) {
+            if (!selectedIndices[i] && isSelectable(i)) sel[i] = true
+        }
+        selectedIndices = sel
+        selectedCount = Object.keys(sel).length
+        lastClickedIndex = -1
+        searchModel.invertSelection()
+    }
+
+    // Selection is keyed by row; rows differ from result indices while the
+    // results are filtered (SearchModel role 264 = resultIndex)
     function getSelectedResultIndices() {
-        return Object.keys(selectedIndices).join(",")
+        return Object.keys(selectedIndices).map(function(row) {
+            return searchModel.data(searchModel.index(parseInt(row), 0), 264)
+        }).join(",")
+    }
+
+    // Re-filter the loaded results; rows shift, so the row-keyed selection is dropped
+    function applyResultFilter() {
+        clearSelection()
+        searchModel.setResultFilter(resultFilterField.text)
+        searchModel.setYearFilter(parseInt(resultMinYear.text) || 0, parseInt(resultMaxYear.text) || 0)
+    }
+
+    function resetResultFilter() {
+        resultFilterField.text = ""
+        resultMinYear.text = ""
+        resultMaxYear.text = ""
     }
 
     function refreshQualityOptions() {
@@ -110,7 +172,9 @@ Window {
 
     function openAdd() {
         refreshQualityOptions()
+        searchHistory = JSON.parse(controller.getSearchHistory(activePage))
         isEditing = false
+        externalUrl = ""
         editingId = -1
         titleField.text = ""
         nativeTitleField.text = ""
@@ -121,11 +185,17 @@ Window {
         qualityCombo.currentIndex = 0
         sourceField.text = ""
         notesField.text = ""
+        progressField.text = ""
+        totalEpisodesField.text = ""
+        acquiredDateField.text = ""
+        watchedDateField.text = ""
         posterUrlField.text = ""
         searchQuery.text = ""
         searchYear.text = ""
         searchModel.clear()
+        resetResultFilter()
         clearSelection()
+        allowDuplicatesCheck.checked = false
         hasSearched = false
         show()
     }
@@ -136,43 +206,45 @@ Window {
         hasSearched = false
         clearSelection()
         searchModel.clear()
+        resetResultFilter()
 
-        // Load item data from MediaModel using role numbers
-        // MediaModel roles: ID=256, Title=257, NativeTitle=258, RomajiTitle=259,
-        //   Year=260, MediaType=261, Status=262, QualityType=263,
-        //   Source=264, Notes=265, PosterPath=266, HasPoster=267
-        var mi = mediaModel.index(row, 0)
-        editingId = mediaModel.data(mi, 256) || -1  // ID
-        titleField.text = mediaModel.data(mi, 257) || ""
-        nativeTitleField.text = mediaModel.data(mi, 258) || ""
-        romajiTitleField.text = mediaModel.data(mi, 259) || ""
-        var yr = mediaModel.data(mi, 260) || 0
-        yearField.text = yr > 0 ? String(yr) : ""
+        // The full item as stored (null for unset fields)
+        var item = JSON.parse(mediaModel.getItemJson(row))
+        editingId = item.id !== undefined ? item.id : -1
+        titleField.text = item.title || ""
+        nativeTitleField.text = item.native_title || ""
+        romajiTitleField.text = item.romaji_title || ""
+        yearField.text = item.year > 0 ? String(item.year) : ""
 
         // Status combo
-        var status = mediaModel.data(mi, 262) || "On Drive"
-        var statusIdx = statusCombo.find(status)
+        var statusIdx = statusCombo.find(item.status || activeStatus)
         statusCombo.currentIndex = statusIdx >= 0 ? statusIdx : 0
 
         // Quality combo
-        var quality = mediaModel.data(mi, 263) || ""
-        var qualIdx = qualityCombo.find(quality)
+        var qualIdx = qualityCombo.find(item.quality_type || "")
         qualityCombo.currentIndex = qualIdx >= 0 ? qualIdx : 0
 
         // Source, Notes
-        sourceField.text = mediaModel.data(mi, 264) || ""
-        notesField.text = mediaModel.data(mi, 265) || ""
+        sourceField.text = item.source || ""
+        notesField.text = item.notes || ""
 
-        // Poster path (model already returns file:// prefixed)
-        var pp = mediaModel.data(mi, 266) || ""
-        if (pp.toString().startsWith("file://")) pp = pp.toString().substring(7)
-        posterUrlField.text = pp
+        // Progress (null = not tracked), total episodes/chapters (0 = unknown)
+        progressField.text = item.progress !== null && item.progress >= 0 ? String(item.progress) : ""
+        totalEpisodesField.text = item.total_episodes > 0 ? String(item.total_episodes) : ""
+        acquiredDateField.text = item.acquired_date || ""
+        watchedDateField.text = item.watched_date || ""
 
+        // Poster path (model role 266 returns a file:// URL for cached posters)
+        posterUrlField.text = controller.posterPathFromUrl(String(mediaModel.data(mediaModel.index(row, 0), 266) || ""))
+
+        externalUrl = editingId >= 0 ? controller.getExternalUrl(editingId) : ""
+        if (editingId >= 0) controller.recordView(editingId)
         show()
     }
 
     function onSearchDone() {
         clearSelection()
+        resetResultFilter()  // the model starts a new result set unfiltered
     }
 
     Theme { id: _t }
@@ -268,11 +340,16 @@ Window {
                             Layout.preferredWidth: 70
                             Layout.preferredHeight: 36
                             radius: 8
-                            color: searchBtnMouse.containsMouse ? _t.accentHover : _t.accent
+                            // Greyed out while offline; still clickable so a search re-checks
+                            color: !controller.online ? _t.surfaceElevated
+                                   : (searchBtnMouse.containsMouse ? _t.accentHover : _t.accent)
+
+                            ToolTip.visible: !controller.online && searchBtnMouse.containsMouse
+                            ToolTip.text: "Offline. Search again to retry."
 
                             Text {
                                 anchors.centerIn: parent
-                                text: editWin.searching ? "..." : "Search"
+                                text: editWin.searching ? "Cancel" : "Search"
                                 color: _t.textWhite
                                 font.pixelSize: 13
                                 font.bold: true
@@ -282,7 +359,56 @@ Window {
                                 anchors.fill: parent
                                 hoverEnabled: true
                                 cursorShape: Qt.PointingHandCursor
-                                onClicked: doSearch()
+                                onClicked: editWin.searching ? controller.cancelSearch() : doSearch()
+                            }
+                        }
+                    }
+
+                    // Recent queries, until the first search of this session
+                    Flow {
+                        Layout.fillWidth: true
+                        spacing: 6
+                        visible: !editWin.hasSearched && editWin.searchHistory.length > 0
+
+                        Repeater {
+                            model: editWin.searchHistory
+
+                            Rectangle {
+                                width: historyText.implicitWidth + 16
+                                height: 24
+                                radius: 12
+                                color: historyMouse.containsMouse ? _t.surfaceCardHover : _t.surfaceDark
+                                border.color: _t.borderSubtle
+
+                                Text {
+                                    id: historyText
+                                    anchors.centerIn: parent
+                                    text: modelData
+                                    color: _t.textSecondary
+                                    font.pixelSize: 11
+                                }
+                                MouseArea {
+                                    id: historyMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                                    onClicked: {
+                                        searchQuery.text = modelData
+                                        doSearch()
+                                    }
+                                }
+                            }
+                        }
+
+                        Text {
+                            height: 24
+                            verticalAlignment: Text.AlignVCenter
+                            text: "Clear"
+                            color: clearHistoryMouse.containsMouse ? _t.textPrimary : _t.textMuted
+                            font.pixelSize: 11
+                            MouseArea {
+                                id: clearHistoryMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                                onClicked: {
+                                    controller.clearSearchHistory()
+                                    editWin.searchHistory = []
+                                }
                             }
                         }
                     }
@@ -317,6 +443,110 @@ Window {
                                     color: _t.accent
                                     font.pixelSize: 11
                                 }
+                                Repeater {
+                                    model: editWin.isEditing || searchResultsList.count === 0 ? [] : ["All", "None", "Invert"]
+                                    delegate: Text {
+                                        text: modelData
+                                        color: selMouse.containsMouse ? _t.accentLight : _t.textSecondary
+                                        font.pixelSize: 11
+                                        MouseArea {
+                                            id: selMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                                            onClicked: {
+                                                if (modelData === "All") editWin.selectAllResults()
+                                                else if (modelData === "None") { editWin.clearSelection(); searchModel.selectNone() }
+                                                else editWin.invertResultSelection()
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
+                            // Narrow the loaded results without searching again
+                            RowLayout {
+                                Layout.fillWidth: true
+                                spacing: 6
+                                visible: searchResultsList.count > 0 || resultFilterField.text !== ""
+                                         || resultMinYear.text !== "" || resultMaxYear.text !== ""
+
+                                TextField {
+                                    id: resultFilterField
+                                    Layout.fillWidth: true
+                                    Layout.preferredHeight: 28
+                                    placeholderText: "Filter results..."
+                                    placeholderTextColor: _t.textMuted
+                                    color: _t.textPrimary
+                                    font.pixelSize: 12
+                                    background: Rectangle {
+                                        color: _t.surface
+                                        border.color: resultFilterField.activeFocus ? _t.accent : _t.borderSubtle
+                                        radius: 6
+                                    }
+                                    onTextChanged: editWin.applyResultFilter()
+                                }
+                                TextField {
+                                    id: resultMinYear
+                                    Layout.preferredWidth: 56
+                                    Layout.preferredHeight: 28
+                                    placeholderText: "From"
+                                    placeholderTextColor: _t.textMuted
+                                    color: _t.textPrimary
+                                    font.pixelSize: 12
+                                    validator: IntValidator { bottom: 0; top: 2099 }
+                                    background: Rectangle {
+                                        color: _t.surface
+                                        border.color: resultMinYear.activeFocus ? _t.accent : _t.borderSubtle
+                                        radius: 6
+                                    }
+                                    onTextChanged: editWin.applyResultFilter()
+                                }
+                                TextField {
+                                    id: resultMaxYear
+                                    Layout.preferredWidth: 56
+                                    Layout.preferredHeight: 28
+                                    placeholderText: "To"
+                                    placeholderTextColor: _t.textMuted
+                                    color: _t.textPrimary
+                                    font.pixelSize: 12
+                                    validator: IntValidator { bottom: 0; top: 2099 }
+                                    background: Rectangle {
+                                        color: _t.surface
+                                        border.color: resultMaxYear.activeFocus ? _t.accent : _t.borderSubtle
+                                        radius: 6
+                                    }
+                                    onTextChanged: editWin.applyResultFilter()
+                                }
+                                ComboBox {
+                                    id: resultSortCombo
+                                    Layout.preferredWidth: 130
+                                    Layout.preferredHeight: 28
+                                    textRole: "text"
+                                    valueRole: "value"
+                                    model: [
+                                        { text: "Relevance", value: "relevance" },
+                                        { text: "★ Top rated", value: "rating" },
+                                        { text: "Most popular", value: "popularity" },
+                                        { text: "Newest", value: "year" },
+                                        { text: "Title", value: "title" }
+                                    ]
+                                    Component.onCompleted: currentIndex = indexOfValue(editWin.resultSort)
+                                    background: Rectangle { color: _t.surface; border.color: resultSortCombo.activeFocus ? _t.accent : _t.borderSubtle; radius: 6 }
+                                    contentItem: Text { leftPadding: 8; text: resultSortCombo.displayText; color: _t.textSecondary; font.pixelSize: 12; verticalAlignment: Text.AlignVCenter }
+                                    indicator: Text { x: resultSortCombo.width - width - 8; anchors.verticalCenter: parent.verticalCenter; text: "▾"; color: _t.textMuted; font.pixelSize: 12 }
+                                    delegate: ItemDelegate {
+                                        required property int index
+                                        required property var modelData
+                                        width: resultSortCombo.width
+                                        contentItem: Text { text: modelData.text; color: highlighted ? _t.textWhite : _t.textPrimary; font.pixelSize: 12; leftPadding: 8 }
+                                        background: Rectangle { color: highlighted ? _t.accent : "transparent" }
+                                        highlighted: resultSortCombo.highlightedIndex === index
+                                    }
+                                    onActivated: {
+                                        // Rows reorder, so the row-keyed selection is dropped
+                                        editWin.clearSelection()
+                                        editWin.resultSort = currentValue
+                                        searchModel.setResultSort(currentValue)
+                                    }
+                                }
                             }
 
                             ListView {
@@ -334,8 +564,10 @@ Window {
                                     radius: 8
 
                                     property bool isSelected: editWin.selectedIndices[index] === true
+                                    property bool blocked: model.alreadyOwned && !editWin.isEditing && !allowDuplicatesCheck.checked
 
-                                    color: isSelected ? _t.accentBg : (srMouse.containsMouse ? "#0dffffff" : "transparent")
+                                    color: isSelected ? _t.accentBg : (srMouse.containsMouse && !blocked ? "#0dffffff" : "transparent")
+                                    opacity: blocked ? 0.5 : 1.0
                                     border.width: isSelected ? 1 : 0
                                     border.color: isSelected ? _t.accent : "transparent"
 
@@ -374,7 +606,10 @@ Window {
                                                 Layout.fillWidth: true
                                             }
                                             Text {
-                                                text: model.year > 0 ? String(model.year) : "Unknown year"
+                                                text: ((model.season || "") !== "" && model.year > 0 ? model.season + " " : "")
+                                                      + (model.year > 0 ? String(model.year) : "Unknown year")
+                                                      + ((model.animeFormat || "") !== "" ? "  ·  " + model.animeFormat : "")
+                                                      + (model.voteAverage > 0 ? "  ·  ★ " + model.voteAverage.toFixed(1) : "")
                                                 color: _t.textMuted
                                                 font.pixelSize: 11
                                             }
@@ -388,6 +623,13 @@ Window {
                                             }
                                         }
 
+                                        Text {
+                                            text: (resultDelegate.isSelected ? "Adds another copy · " : "In library · ") + model.ownedStatus
+                                            color: resultDelegate.isSelected ? _t.warning : _t.textMuted
+                                            font.pixelSize: 11
+                                            visible: model.alreadyOwned || false
+                                        }
+
                                         Text {
                                             text: resultDelegate.isSelected ? "✓" : ""
                                             color: _t.accent
@@ -400,8 +642,9 @@ Window {
                                         id: srMouse
                                         anchors.fill: parent
                                         hoverEnabled: true
-                                        cursorShape: Qt.PointingHandCursor
+                                        cursorShape: resultDelegate.blocked ? Qt.ArrowCursor : Qt.PointingHandCursor
                                         onClicked: (mouse) => {
+                                            if (resultDelegate.blocked) return
                                             if (mouse.modifiers & Qt.ShiftModifier) {
                                                 editWin.rangeSelect(index)
                                             } else if (mouse.modifiers & Qt.ControlModifier) {
@@ -433,15 +676,30 @@ Window {
                     // Batch add button
                     RowLayout {
                         Layout.fillWidth: true
-                        visible: editWin.selectedCount > 1
+                        // Also shown with nothing selected, so duplicates can be
+                        // allowed before picking results already in the library
+                        visible: searchResultsList.count > 0 && !editWin.isEditing
 
                         Text {
                             text: editWin.selectedCount + " items selected"
                             color: _t.accent
                             font.pixelSize: 12
+                            visible: editWin.selectedCount > 1
+                        }
+                        // Off by default; for a second copy such as another cut of a film
+                        CheckBox {
+                            id: allowDuplicatesCheck
+                            text: "Add even if already in library"
+                            palette.text: _t.textPrimary
+                            font.pixelSize: 12
+                            onCheckedChanged: {
+                                searchModel.setAllowOwned(checked)
+                                if (!checked) editWin.dropUnselectableResults()
+                            }
                         }
                         Item { Layout.fillWidth: true }
                         Rectangle {
+                            visible: editWin.selectedCount > 1
                             Layout.preferredWidth: addAllText.implicitWidth + 24
                             Layout.preferredHeight: 32
                             radius: 8
@@ -458,7 +716,7 @@ Window {
                             MouseArea {
                                 id: addAllMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
                                 onClicked: {
-                                    controller.addSearchResults(editWin.getSelectedResultIndices())
+                                    controller.addSearchResults(editWin.getSelectedResultIndices(), statusCombo.currentText, allowDuplicatesCheck.checked)
                                     editWin.close()
                                 }
                             }
@@ -484,11 +742,7 @@ Window {
 
                         Image {
                             anchors.fill: parent
-                            source: {
-                                if (posterUrlField.text === "") return ""
-                                if (posterUrlField.text.startsWith("http")) return posterUrlField.text
-                                return "file://" + posterUrlField.text
-                            }
+                            source: controller.posterPreviewUrl(posterUrlField.text)
                             fillMode: Image.PreserveAspectCrop
                             asynchronous: true
                         }
@@ -510,10 +764,10 @@ Window {
                             }
                         }
 
-                        // Romaji + Native (anime)
+                        // Romaji + Native (anime/manga)
                         RowLayout {
                             Layout.fillWidth: true; spacing: 12
-                            visible: activePage === "Anime"
+                            visible: activePage === "Anime" || activePage === "Manga"
 
                             ColumnLayout {
                                 Layout.fillWidth: true; spacing: 4
@@ -554,7 +808,7 @@ Window {
                                 Text { text: "Status"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
                                 ComboBox {
                                     id: statusCombo; Layout.fillWidth: true
-                                    model: ["On Drive", "To Download", "To Work On"]
+                                    model: editWin.statusOptions
                                     background: Rectangle { color: _t.surfaceDark; border.color: statusCombo.activeFocus ? _t.accent : _t.borderSubtle; radius: 8; implicitHeight: 36 }
                                     contentItem: Text { leftPadding: 12; text: statusCombo.displayText; color: _t.textPrimary; font.pixelSize: 13; verticalAlignment: Text.AlignVCenter }
                                     indicator: Text { x: statusCombo.width - width - 8; anchors.verticalCenter: parent.verticalCenter; text: "▾"; color: _t.textMuted; font.pixelSize: 14 }
@@ -604,6 +858,59 @@ Window {
                             }
                         }
 
+                        // Progress + Total (episodic types only)
+                        RowLayout {
+                            Layout.fillWidth: true; spacing: 12
+                            visible: activePage !== "Movie"
+
+                            ColumnLayout {
+                                Layout.fillWidth: true; spacing: 4
+                                Text { text: activePage === "Manga" ? "Chapters Read" : "Episodes Watched"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
+                                TextField {
+                                    id: progressField; Layout.fillWidth: true
+                                    color: _t.textPrimary; font.pixelSize: 13
+                                    validator: IntValidator { bottom: 0; top: 100000 }
+                                    background: Rectangle { color: _t.surfaceDark; border.color: progressField.activeFocus ? _t.accent : _t.borderSubtle; radius: 8 }
+                                }
+                            }
+                            ColumnLayout {
+                                Layout.fillWidth: true; spacing: 4
+                                Text { text: activePage === "Manga" ? "Total Chapters" : "Total Episodes"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
+                                TextField {
+                                    id: totalEpisodesField; Layout.fillWidth: true
+                                    color: _t.textPrimary; font.pixelSize: 13
+                                    validator: IntValidator { bottom: 0; top: 100000 }
+                                    background: Rectangle { color: _t.surfaceDark; border.color: totalEpisodesField.activeFocus ? _t.accent : _t.borderSubtle; radius: 8 }
+                                }
+                            }
+                        }
+
+                        // Acquired + Watched dates
+                        RowLayout {
+                            Layout.fillWidth: true; spacing: 12
+
+                            ColumnLayout {
+                                Layout.fillWidth: true; spacing: 4
+                                Text { text: "Acquired"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
+                                TextField {
+                                    id: acquiredDateField; Layout.fillWidth: true
+                                    color: _t.textPrimary; font.pixelSize: 13
+                                    placeholderText: "YYYY-MM-DD"
+                                    background: Rectangle { color: _t.surfaceDark; border.color: acquiredDateField.activeFocus ? _t.accent : _t.borderSubtle; radius: 8 }
+                                }
+                            }
+                            ColumnLayout {
+                                Layout.fillWidth: true; spacing: 4
+                                Text { text: "Watched"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
+                                TextField {
+                                    id: watchedDateField; Layout.fillWidth: true
+                                    color: _t.textPrimary; font.pixelSize: 13
+                                    placeholderText: "YYYY-MM-DD"
+                                    background: Rectangle { color: _t.surfaceDark; border.color: watchedDateField.activeFocus ? _t.accent : _t.borderSubtle; radius: 8 }
+                                }
+                            }
+                        }
+
                         // Notes
                         ColumnLayout {
                             Layout.fillWidth: true; spacing: 4
@@ -639,6 +946,19 @@ Window {
                 anchors.leftMargin: 20
                 anchors.rightMargin: 20
 
+                Text {
+                    text: editWin.externalUrl.indexOf("imdb.com") >= 0 ? "Open on IMDb ↗"
+                        : editWin.externalUrl.indexOf("themoviedb.org") >= 0 ? "Open on TMDB ↗"
+                        : "Open on AniList ↗"
+                    color: externalMouse.containsMouse ? _t.accentLight : _t.textSecondary
+                    font.pixelSize: 13
+                    visible: editWin.isEditing && editWin.externalUrl !== ""
+                    MouseArea {
+                        id: externalMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                        onClicked: Qt.openUrlExternally(editWin.externalUrl)
+                    }
+                }
+
                 Item { Layout.fillWidth: true }
 
                 RowLayout {
@@ -697,10 +1017,8 @@ Window {
         romajiTitleField.text = searchModel.data(mi, 258) || ""
         var yr = searchModel.data(mi, 259) || 0
         yearField.text = yr > 0 ? String(yr) : ""
-        // poster path
-        var pp = searchModel.data(mi, 261) || ""
-        if (pp.startsWith("file://")) pp = pp.substring(7)
-        posterUrlField.text = pp
+        // poster path — the provider URL, not the prefetched search_cache copy
+        posterUrlField.text = controller.posterPathFromUrl(searchModel.data(mi, 265) || "")
     }
 
     function saveItem() {
@@ -708,8 +1026,21 @@ Window {
 
         // When adding from search results, route through addSearchResults
         // to properly cache the poster and preserve API IDs (tmdb_id/anilist_id)
-        if (!editWin.isEditing && editWin.selectedCount >= 1) {
-            controller.addSearchResults(editWin.getSelectedResultIndices())
+        if (!editWin.isEditing && editWin.selectedCount > 1) {
+            controller.addSearchResults(editWin.getSelectedResultIndices(), statusCombo.currentText, allowDuplicatesCheck.checked)
+            editWin.close()
+            return
+        }
+        // A single result keeps whatever was edited in the form after auto-fill
+        if (!editWin.isEditing && editWin.selectedCount === 1) {
+            controller.addSearchResultWithDetails(parseInt(editWin.getSelectedResultIndices()), JSON.stringify({
+                title: titleField.text,
+                year: parseInt(yearField.text) || null,
+                status: statusCombo.currentText,
+                quality_type: qualityCombo.currentText,
+                source: sourceField.text,
+                notes: notesField.text
+            }), allowDuplicatesCheck.checked)
             editWin.close()
             return
         }
@@ -724,7 +1055,11 @@ Window {
             qualityCombo.currentText,
             sourceField.text,
             notesField.text,
-            posterUrlField.text
+            posterUrlField.text,
+            progressField.text === "" ? -1 : parseInt(progressField.text),
+            parseInt(totalEpisodesField.text) || 0,
+            acquiredDateField.text.trim(),
+            watchedDateField.text.trim()
         )
         editWin.close()
     }
@@ -1,4 +1,5 @@
 import QtQuick
+import QtQuick.Controls
 import QtQuick.Layouts
 
 Rectangle {
@@ -16,10 +17,26 @@ Rectangle {
     property string title: ""
     property string nativeTitle: ""
     property int year: 0
+    property string animeFormat: "" // "TV", "Movie", "OVA"... for anime
     property string qualityType: ""
+    property int numberOfSeasons: 0
+    property int numberOfEpisodes: 0
+    property int progress: -1
+    property int totalEpisodes: 0
+    property bool favorite: false
+    property bool watched: false
     property string posterPath: ""
+    property int posterWidth: 0    // 0 when unknown
+    property int posterHeight: 0
+    // width / height of the poster; assume a standard 2:3 poster until known
+    readonly property real posterAspect: posterWidth > 0 && posterHeight > 0 ? posterWidth / posterHeight : 2 / 3
     property bool hasPoster: false
+    property string posterSource: "" // "manual", "provider" or "" without a poster
     property bool selected: false
+    // Search hit outside the title: "romaji", "native" or "notes", plus context
+    property string matchedField: ""
+    property string matchSnippet: ""
+    property string mediaType: "" // shown as a badge when set
 
     Theme { id: _t }
 
@@ -29,6 +46,7 @@ Rectangle {
 
         // Poster
         Rectangle {
+            id: posterArea
             Layout.fillWidth: true
             Layout.fillHeight: true
             color: _t.surfaceDark
@@ -37,9 +55,17 @@ Rectangle {
             Image {
                 anchors.fill: parent
                 source: card.hasPoster ? card.posterPath : ""
-                fillMode: Image.PreserveAspectCrop
+                // Crop posters close to the slot's shape; letterbox anything much
+                // wider (banners, screenshots) instead of cutting most of it off
+                fillMode: card.posterAspect > (posterArea.width / Math.max(1, posterArea.height)) * 1.5
+                          ? Image.PreserveAspectFit : Image.PreserveAspectCrop
                 visible: card.hasPoster
                 asynchronous: true
+
+                HoverHandler { id: posterHover }
+                ToolTip.visible: posterHover.hovered && card.posterSource !== ""
+                ToolTip.delay: 600
+                ToolTip.text: card.posterSource === "manual" ? "Poster set from a URL" : "Poster from the provider"
             }
 
             // No-poster placeholder
@@ -49,6 +75,79 @@ Rectangle {
                 font.pixelSize: 32
                 visible: !card.hasPoster
             }
+
+            // Favorite badge
+            Text {
+                anchors.top: parent.top
+                anchors.right: parent.right
+                anchors.margins: 6
+                text: "★"
+                color: _t.warning
+                font.pixelSize: 18
+                style: Text.Outline
+                styleColor: _t.surfaceDark
+                visible: card.favorite
+            }
+
+            // Watched badge
+            Text {
+                anchors.bottom: parent.bottom
+                anchors.right: parent.right
+                anchors.margins: 6
+                text: "✓"
+                color: _t.success
+                font.pixelSize: 16
+                font.bold: true
+                style: Text.Outline
+                styleColor: _t.surfaceDark
+                visible: card.watched
+            }
+
+            // Why a search hit is here when its title doesn't show the term
+            Rectangle {
+                anchors.top: parent.top
+                anchors.left: parent.left
+                anchors.margins: 6
+                width: matchLabel.implicitWidth + 10
+                height: matchLabel.implicitHeight + 4
+                radius: 4
+                color: _t.accent
+                visible: card.matchedField !== "" && card.matchedField !== "title"
+
+                Text {
+                    id: matchLabel
+                    anchors.centerIn: parent
+                    text: "in " + card.matchedField
+                    color: _t.textWhite
+                    font.pixelSize: 10
+                    font.bold: true
+                }
+
+                HoverHandler { id: matchHover }
+                ToolTip.visible: matchHover.hovered && card.matchSnippet !== ""
+                ToolTip.text: card.matchSnippet
+            }
+
+            // Media type, for views that mix them
+            Rectangle {
+                anchors.bottom: parent.bottom
+                anchors.left: parent.left
+                anchors.margins: 6
+                width: typeLabel.implicitWidth + 10
+                height: typeLabel.implicitHeight + 4
+                radius: 4
+                color: _t.surfaceDark
+                visible: card.mediaType !== ""
+
+                Text {
+                    id: typeLabel
+                    anchors.centerIn: parent
+                    text: card.mediaType
+                    color: _t.textSecondary
+                    font.pixelSize: 10
+                    font.bold: true
+                }
+            }
         }
 
         // Info
@@ -81,12 +180,37 @@ Rectangle {
                         font.pixelSize: 11
                         visible: card.year > 0
                     }
+                    Text {
+                        text: card.animeFormat
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                        visible: card.animeFormat !== ""
+                    }
                     Text {
                         text: card.qualityType
                         color: _t.accentLight
                         font.pixelSize: 11
                         visible: card.qualityType !== ""
                     }
+                    Text {
+                        text: {
+                            var parts = []
+                            if (card.numberOfSeasons > 0)
+                                parts.push(card.numberOfSeasons + (card.numberOfSeasons === 1 ? " season" : " seasons"))
+                            if (card.numberOfEpisodes > 0)
+                                parts.push(card.numberOfEpisodes + " eps")
+                            return parts.join(" · ")
+                        }
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                        visible: card.numberOfSeasons > 0 || card.numberOfEpisodes > 0
+                    }
+                    Text {
+                        text: card.progress + (card.totalEpisodes > 0 ? "/" + card.totalEpisodes : "")
+                        color: _t.accentLight
+                        font.pixelSize: 11
+                        visible: card.progress >= 0
+                    }
                 }
             }
         }
@@ -6,7 +6,7 @@ Item {
     id: gridRoot
 
     property alias model: gridView.model
-    property var selectedIds: []
+    property bool showMediaType: false // badge each card with its type (mixed-type views)
 
     signal itemClicked(int row, int modifiers)
     signal itemDoubleClicked(int row)
@@ -21,13 +21,6 @@ Item {
         var maxY = Math.max(0, gridView.contentHeight - gridView.height)
         gridView.contentY = Math.max(0, Math.min(y, maxY))
     }
-    function isSelected(row) {
-        var id = model.getItemId(row)
-        for (var i = 0; i < selectedIds.length; i++) {
-            if (selectedIds[i] === id) return true
-        }
-        return false
-    }
 
     GridView {
         id: gridView
@@ -53,10 +46,23 @@ Item {
                 title: model.title
                 nativeTitle: model.nativeTitle || ""
                 year: model.year
+                animeFormat: model.animeFormat || ""
                 qualityType: model.qualityType || ""
-                posterPath: model.posterPath || ""
+                numberOfSeasons: model.numberOfSeasons || 0
+                numberOfEpisodes: model.numberOfEpisodes || 0
+                progress: model.progress !== undefined ? model.progress : -1
+                totalEpisodes: model.totalEpisodes || 0
+                favorite: model.favorite || false
+                watched: model.watched || false
+                posterPath: model.thumbnailPath || ""
+                posterWidth: model.posterWidth || 0
+                posterHeight: model.posterHeight || 0
                 hasPoster: model.hasPoster || false
-                selected: gridRoot.isSelected(index)
+                posterSource: model.posterSource || ""
+                selected: model.selected || false
+                matchedField: model.matchedField || ""
+                matchSnippet: model.matchSnippet || ""
+                mediaType: gridRoot.showMediaType ? (model.mediaType || "") : ""
                 onClicked: (modifiers) => gridRoot.itemClicked(index, modifiers)
                 onDoubleClicked: gridRoot.itemDoubleClicked(index)
                 onRightClicked: (mx, my) => gridRoot.itemRightClicked(index, mx, my)
@@ -7,15 +7,19 @@ Item {
     Theme { id: _t }
 
     property alias model: listView.model
-    property var selectedIds: []
     property int rowHeight: 44
     property string sortField: "title"
     property string sortDir: "ASC"
+    property string sortField2: "title" // tie-breaker, set with shift-click
+    property string sortDir2: "ASC"
+    property bool highlightMissing: false // mark empty year/quality/source cells
+    property bool showMediaType: false // label each row with its type (mixed-type views)
 
     signal itemClicked(int row, int modifiers)
     signal itemDoubleClicked(int row)
     signal itemRightClicked(int row, real mx, real my)
     signal sortRequested(string field, string dir)
+    signal sortRequested2(string field, string dir)
 
 
     function scrollY() {
@@ -26,13 +30,6 @@ Item {
         var maxY = Math.max(0, listView.contentHeight - listView.height)
         listView.contentY = Math.max(0, Math.min(y, maxY))
     }
-    function isSelected(row) {
-        var id = model.getItemId(row)
-        for (var i = 0; i < selectedIds.length; i++) {
-            if (selectedIds[i] === id) return true
-        }
-        return false
-    }
 
     ColumnLayout {
         anchors.fill: parent
@@ -63,14 +60,14 @@ Item {
                         spacing: 4
                         Text { text: "Title"; color: _t.textMuted; font.pixelSize: 12; font.bold: true }
                         Text {
-                            text: tableRoot.sortField === "title" ? (tableRoot.sortDir === "ASC" ? "↑" : "↓") : ""
-                            color: _t.accent; font.pixelSize: 12
+                            text: tableRoot.sortIndicator("title")
+                            color: tableRoot.sortField === "title" ? _t.accent : _t.textMuted; font.pixelSize: 12
                         }
                         Item { Layout.fillWidth: true }
                     }
                     MouseArea {
                         id: titleHeaderMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
-                        onClicked: toggleSort("title")
+                        onClicked: (mouse) => toggleSort("title", mouse.modifiers)
                     }
                 }
 
@@ -87,13 +84,13 @@ Item {
                         spacing: 4
                         Text { text: "Year"; color: _t.textMuted; font.pixelSize: 12; font.bold: true }
                         Text {
-                            text: tableRoot.sortField === "year" ? (tableRoot.sortDir === "ASC" ? "↑" : "↓") : ""
-                            color: _t.accent; font.pixelSize: 12
+                            text: tableRoot.sortIndicator("year")
+                            color: tableRoot.sortField === "year" ? _t.accent : _t.textMuted; font.pixelSize: 12
                         }
                     }
                     MouseArea {
                         id: yearHeaderMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
-                        onClicked: toggleSort("year")
+                        onClicked: (mouse) => toggleSort("year", mouse.modifiers)
                     }
                 }
 
@@ -110,13 +107,13 @@ Item {
                         spacing: 4
                         Text { text: "Quality"; color: _t.textMuted; font.pixelSize: 12; font.bold: true }
                         Text {
-                            text: tableRoot.sortField === "quality_type" ? (tableRoot.sortDir === "ASC" ? "↑" : "↓") : ""
-                            color: _t.accent; font.pixelSize: 12
+                            text: tableRoot.sortIndicator("quality_type")
+                            color: tableRoot.sortField === "quality_type" ? _t.accent : _t.textMuted; font.pixelSize: 12
                         }
                     }
                     MouseArea {
                         id: qualityHeaderMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
-                        onClicked: toggleSort("quality_type")
+                        onClicked: (mouse) => toggleSort("quality_type", mouse.modifiers)
                     }
                 }
 
@@ -133,13 +130,36 @@ Item {
                         spacing: 4
                         Text { text: "Source"; color: _t.textMuted; font.pixelSize: 12; font.bold: true }
                         Text {
-                            text: tableRoot.sortField === "source" ? (tableRoot.sortDir === "ASC" ? "↑" : "↓") : ""
-                            color: _t.accent; font.pixelSize: 12
+                            text: tableRoot.sortIndicator("source")
+                            color: tableRoot.sortField === "source" ? _t.accent : _t.textMuted; font.pixelSize: 12
                         }
                     }
                     MouseArea {
                         id: sourceHeaderMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
-                        onClicked: toggleSort("source")
+                        onClicked: (mouse) => toggleSort("source", mouse.modifiers)
+                    }
+                }
+
+                // Acquired header (sortable)
+                Rectangle {
+                    Layout.preferredWidth: 90
+                    Layout.fillHeight: true
+                    color: acquiredHeaderMouse.containsMouse ? _t.surfaceCardHover : "transparent"
+                    radius: 4
+
+                    RowLayout {
+                        anchors.fill: parent
+                        anchors.leftMargin: 4
+                        spacing: 4
+                        Text { text: "Acquired"; color: _t.textMuted; font.pixelSize: 12; font.bold: true }
+                        Text {
+                            text: tableRoot.sortIndicator("acquired_date")
+                            color: tableRoot.sortField === "acquired_date" ? _t.accent : _t.textMuted; font.pixelSize: 12
+                        }
+                    }
+                    MouseArea {
+                        id: acquiredHeaderMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                        onClicked: (mouse) => toggleSort("acquired_date", mouse.modifiers)
                     }
                 }
             }
@@ -161,7 +181,9 @@ Item {
                 width: listView.width
                 height: tableRoot.rowHeight
 
-                property bool isSelected: tableRoot.isSelected(index)
+                property bool isSelected: model.selected || false
+                property var missingFields: (model.missingFields || "").split(",")
+                function isMissing(field) { return tableRoot.highlightMissing && missingFields.indexOf(field) >= 0 }
 
                 color: isSelected ? _t.accentBg : (rowMouse.containsMouse ? _t.surfaceCardHover : (index % 2 === 0 ? _t.surfaceDark : _t.surface))
                 border.width: isSelected ? 1 : 0
@@ -183,10 +205,15 @@ Item {
 
                         Image {
                             anchors.fill: parent
-                            source: model.hasPoster ? model.posterPath : ""
+                            source: model.hasPoster ? model.thumbnailPath : ""
                             fillMode: Image.PreserveAspectCrop
                             visible: model.hasPoster || false
                             asynchronous: true
+
+                            HoverHandler { id: posterHover }
+                            ToolTip.visible: posterHover.hovered && (model.posterSource || "") !== ""
+                            ToolTip.delay: 600
+                            ToolTip.text: model.posterSource === "manual" ? "Poster set from a URL" : "Poster from the provider"
                         }
                     }
 
@@ -203,18 +230,30 @@ Item {
                             Layout.fillWidth: true
                         }
                         Text {
-                            text: model.nativeTitle || ""
+                            text: tableRoot.showMediaType
+                                ? [model.mediaType, model.nativeTitle].filter(function(s) { return s }).join(" · ")
+                                : model.nativeTitle || ""
                             color: _t.textMuted
                             font.pixelSize: 11
                             elide: Text.ElideRight
                             Layout.fillWidth: true
-                            visible: (model.nativeTitle || "") !== ""
+                            visible: text !== ""
+                        }
+                        // Search hit outside the title
+                        Text {
+                            text: "in " + (model.matchedField || "") + ": " + (model.matchSnippet || "")
+                            color: _t.accentLight
+                            font.pixelSize: 11
+                            elide: Text.ElideRight
+                            Layout.fillWidth: true
+                            visible: (model.matchedField || "") !== "" && model.matchedField !== "title"
                         }
                     }
 
-                    Text { text: model.year > 0 ? String(model.year) : ""; color: _t.textSecondary; font.pixelSize: 13; Layout.preferredWidth: 60 }
-                    Text { text: model.qualityType || ""; color: _t.accentLight; font.pixelSize: 13; Layout.preferredWidth: 120 }
-                    Text { text: model.source || ""; color: _t.textSecondary; font.pixelSize: 13; Layout.preferredWidth: 100 }
+                    Text { text: model.year > 0 ? String(model.year) : (isMissing("year") ? "—" : ""); color: isMissing("year") ? _t.warning : _t.textSecondary; font.pixelSize: 13; Layout.preferredWidth: 60 }
+                    Text { text: model.qualityType || (isMissing("quality_type") ? "—" : ""); color: isMissing("quality_type") ? _t.warning : _t.accentLight; font.pixelSize: 13; Layout.preferredWidth: 120 }
+                    Text { text: model.source || (isMissing("source") ? "—" : ""); color: isMissing("source") ? _t.warning : _t.textSecondary; font.pixelSize: 13; Layout.preferredWidth: 100 }
+                    Text { text: model.acquiredDate || ""; color: _t.textSecondary; font.pixelSize: 13; Layout.preferredWidth: 90 }
                 }
 
                 MouseArea {
@@ -235,8 +274,22 @@ Item {
         }
     }
 
-    function toggleSort(field) {
-        if (sortField === field) {
+    // Arrow for the primary sort column; the secondary one gets a small "2"
+    function sortIndicator(field) {
+        if (sortField === field)
+            return sortDir === "ASC" ? "↑" : "↓"
+        if (sortField2 === field)
+            return (sortDir2 === "ASC" ? "↑" : "↓") + "2"
+        return ""
+    }
+
+    function toggleSort(field, modifiers) {
+        if (modifiers & Qt.ShiftModifier) {
+            // Shift-click sets the tie-breaker instead
+            if (field === sortField)
+                return
+            sortRequested2(field, sortField2 === field && sortDir2 === "ASC" ? "DESC" : "ASC")
+        } else if (sortField === field) {
             // Toggle direction
             var newDir = sortDir === "ASC" ? "DESC" : "ASC"
             sortRequested(field, newDir)
@@ -35,15 +35,135 @@ Window {
 
     // Quality types as a JS array managed in QML
     property var qualityTypes: []
+    // Items per quality type, plus types items use that aren't configured
+    property var qualityTypeCounts: ({})
+    property var unlistedQualityTypes: []
+    // Types the last save was asked to remove but kept because items use them
+    property var keptQualityTypes: []
+    // Statuses in display order; edits in place are treated as renames
+    property var statuses: []
+    property var originalStatuses: []
+    // Saved name each row started as ("" for rows added here), so renames
+    // are sent explicitly instead of guessed from positions
+    property var statusOrigins: []
+    // Media type -> index into statuses, so local renames carry over
+    property var defaultStatusIndex: ({})
+    readonly property var mediaTypes: ["Movie", "TV", "Anime", "Manga"]
+    property int posterDone: 0
+    property int posterTotal: 0
+    property var cacheStats: null
+    property var posterSizes: []
+    // Test API key result: "" untested, "testing", "ok" or "failed"
+    property string apiKeyState: ""
+    property string apiKeyMessage: ""
+
+    Connections {
+        target: controller
+        function onPosterProgress(done, total) {
+            settingsWin.posterDone = done
+            settingsWin.posterTotal = total
+        }
+        function onCacheStatsReady(statsJson) {
+            settingsWin.cacheStats = JSON.parse(statsJson)
+        }
+        function onApiKeyTested(valid, message) {
+            settingsWin.apiKeyState = valid ? "ok" : "failed"
+            settingsWin.apiKeyMessage = message
+        }
+    }
+
+    function formatBytes(bytes) {
+        if (bytes < 1024) return bytes + " B"
+        if (bytes < 1024 * 1024) return (bytes / 1024).toFixed(1) + " KB"
+        return (bytes / (1024 * 1024)).toFixed(1) + " MB"
+    }
 
     onVisibleChanged: {
         if (visible) {
             apiKeyField.text = controller.tmdb_api_key
+            apiKeyState = ""
+            keyringCheck.checked = controller.use_keyring
             adultCheck.checked = controller.include_adult
+            idleMaintenanceCheck.checked = controller.idle_maintenance
+            ignoreArticlesCheck.checked = controller.ignore_articles
+            prefetchPostersCheck.checked = controller.prefetch_search_posters
+            uniformPostersCheck.checked = controller.uniform_posters
+            languageField.text = controller.tmdb_language
+            regionField.text = controller.tmdb_region
+            resultPagesSpin.value = controller.tmdb_result_pages
+            posterSizes = controller.getPosterSizes().split("\n").filter(function(s) { return s !== "" })
+            posterSizeCombo.currentIndex = Math.max(0, posterSizes.indexOf(controller.poster_size))
             loadQualityTypes()
+            loadStatuses()
+            cacheStats = null
+            controller.getCacheStats()
         }
     }
 
+    function loadStatuses() {
+        originalStatuses = controller.getStatusOptions().split("\n").filter(function(s) { return s !== "" })
+        statuses = originalStatuses.slice()
+        statusOrigins = originalStatuses.slice()
+        var defaults = JSON.parse(controller.getDefaultStatuses())
+        var indices = {}
+        for (var type in defaults) indices[type] = Math.max(0, statuses.indexOf(defaults[type]))
+        defaultStatusIndex = indices
+    }
+
+    function setDefaultStatusIndex(type, idx) {
+        var indices = Object.assign({}, defaultStatusIndex)
+        indices[type] = idx
+        defaultStatusIndex = indices
+    }
+
+    function addStatus() {
+        var name = newStatusField.text.trim().replace(/,/g, "")
+        if (name === "") return
+        for (var i = 0; i < statuses.length; i++) {
+            if (statuses[i].toLowerCase() === name.toLowerCase()) return
+        }
+        var arr = statuses.slice()
+        arr.push(name)
+        statuses = arr
+        statusOrigins = statusOrigins.concat([""])
+        newStatusField.text = ""
+    }
+
+    function renameStatus(idx, name) {
+        var arr = statuses.slice()
+        arr[idx] = name.trim().replace(/,/g, "")
+        statuses = arr
+    }
+
+    function removeStatus(idx) {
+        var arr = statuses.slice()
+        arr.splice(idx, 1)
+        statuses = arr
+        var origins = statusOrigins.slice()
+        origins.splice(idx, 1)
+        statusOrigins = origins
+        // Defaults pointing at the removed status go back to the first one
+        var indices = {}
+        for (var type in defaultStatusIndex) {
+            var i = defaultStatusIndex[type]
+            indices[type] = i === idx ? 0 : (i > idx ? i - 1 : i)
+        }
+        defaultStatusIndex = indices
+    }
+
+    function statusesEdited() {
+        return statuses.join(",") !== originalStatuses.join(",")
+    }
+
+    // Rename edited rows first so their items move, then save the list
+    function saveStatusEdits() {
+        for (var i = 0; i < statuses.length; i++) {
+            if (statusOrigins[i] !== "" && statusOrigins[i] !== statuses[i])
+                controller.renameStatus(statusOrigins[i], statuses[i])
+        }
+        controller.saveStatuses(statuses.join(","))
+    }
+
     function sortedQualityTypes(arr) {
         return arr.slice().sort(function(a, b) {
             return a.toLowerCase().localeCompare(b.toLowerCase())
@@ -58,6 +178,14 @@ Window {
             var arr = raw.split("\n").map(function(s) { return s.trim() }).filter(function(s) { return s !== "" })
             qualityTypes = sortedQualityTypes(arr)
         }
+        var counts = {}
+        var unlisted = []
+        JSON.parse(controller.getQualityTypesWithCounts()).forEach(function(entry) {
+            counts[entry.name] = entry.count
+            if (entry.unlisted) unlisted.push(entry.name + " (" + entry.count + ")")
+        })
+        qualityTypeCounts = counts
+        unlistedQualityTypes = unlisted
     }
 
     function addQualityType() {
@@ -75,6 +203,24 @@ Window {
         newQtField.text = ""
     }
 
+    // Renames apply right away so items tagged with the old name follow
+    function renameQualityType(idx, name) {
+        name = name.trim()
+        if (name === qualityTypes[idx]) return true
+        for (var i = 0; i < qualityTypes.length; i++) {
+            if (i !== idx && qualityTypes[i].toLowerCase() === name.toLowerCase()) return false
+        }
+        if (name === "" || !controller.renameQualityType(qualityTypes[idx], name)) return false
+        var counts = Object.assign({}, qualityTypeCounts)
+        counts[name] = (counts[name] || 0) + (counts[qualityTypes[idx]] || 0)
+        delete counts[qualityTypes[idx]]
+        qualityTypeCounts = counts
+        var arr = qualityTypes.slice()
+        arr[idx] = name
+        qualityTypes = sortedQualityTypes(arr)
+        return true
+    }
+
     function removeQualityType(idx) {
         var arr = qualityTypes.slice()
         arr.splice(idx, 1)
@@ -128,24 +274,65 @@ Window {
                     spacing: 4
 
                     Text { text: "TMDB API Key"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
-                    TextField {
-                        id: apiKeyField
+                    RowLayout {
                         Layout.fillWidth: true
-                        color: _t.textPrimary
-                        font.pixelSize: 13
-                        echoMode: TextInput.Password
-                        placeholderText: "Enter your TMDB API key..."
-                        placeholderTextColor: _t.textMuted
-                        background: Rectangle {
-                            color: _t.surfaceDark
-                            border.color: apiKeyField.activeFocus ? _t.accent : _t.borderSubtle
+                        spacing: 8
+
+                        TextField {
+                            id: apiKeyField
+                            Layout.fillWidth: true
+                            color: _t.textPrimary
+                            font.pixelSize: 13
+                            echoMode: TextInput.Password
+                            placeholderText: "Enter your TMDB API key..."
+                            placeholderTextColor: _t.textMuted
+                            background: Rectangle {
+                                color: _t.surfaceDark
+                                border.color: apiKeyField.activeFocus ? _t.accent : _t.borderSubtle
+                                radius: 8
+                            }
+                            onTextEdited: settingsWin.apiKeyState = ""
+                        }
+
+                        Rectangle {
+                            Layout.preferredWidth: testKeyText.implicitWidth + 24
+                            Layout.preferredHeight: 36
                             radius: 8
+                            color: testKeyMouse.containsMouse ? _t.surfaceElevated : _t.surfaceCardHover
+                            border.color: _t.borderSubtle
+
+                            Text {
+                                id: testKeyText
+                                anchors.centerIn: parent
+                                text: settingsWin.apiKeyState === "testing" ? "Testing..." : "Test"
+                                color: _t.textPrimary
+                                font.pixelSize: 13
+                            }
+                            MouseArea {
+                                id: testKeyMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                                enabled: settingsWin.apiKeyState !== "testing"
+                                onClicked: {
+                                    settingsWin.apiKeyState = "testing"
+                                    controller.testApiKey(apiKeyField.text)
+                                }
+                            }
                         }
                     }
                     Text {
-                        text: "Get a free key at themoviedb.org"
-                        color: _t.textMuted
+                        text: settingsWin.apiKeyState === "ok" ? "✓ " + settingsWin.apiKeyMessage
+                            : settingsWin.apiKeyState === "failed" ? "✗ " + settingsWin.apiKeyMessage
+                            : "Get a free key at themoviedb.org"
+                        color: settingsWin.apiKeyState === "ok" ? _t.success
+                            : settingsWin.apiKeyState === "failed" ? _t.danger
+                            : _t.textMuted
                         font.pixelSize: 11
+                        wrapMode: Text.Wrap
+                        Layout.fillWidth: true
+                    }
+                    CheckBox {
+                        id: keyringCheck
+                        text: "Store the API key in the system keyring"
+                        palette.text: _t.textPrimary
                     }
                 }
 
@@ -162,6 +349,174 @@ Window {
                     }
                 }
 
+                // Search language / region
+                ColumnLayout {
+                    Layout.fillWidth: true
+                    Layout.leftMargin: 20
+                    Layout.rightMargin: 20
+                    spacing: 4
+
+                    Text { text: "TMDB Language / Region"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
+                    RowLayout {
+                        spacing: 8
+
+                        TextField {
+                            id: languageField
+                            Layout.preferredWidth: 100
+                            color: _t.textPrimary
+                            font.pixelSize: 13
+                            placeholderText: "en-US"
+                            placeholderTextColor: _t.textMuted
+                            background: Rectangle {
+                                color: _t.surfaceDark
+                                border.color: languageField.activeFocus ? _t.accent : _t.borderSubtle
+                                radius: 8
+                            }
+                        }
+                        TextField {
+                            id: regionField
+                            Layout.preferredWidth: 70
+                            color: _t.textPrimary
+                            font.pixelSize: 13
+                            placeholderText: "Region"
+                            placeholderTextColor: _t.textMuted
+                            maximumLength: 2
+                            background: Rectangle {
+                                color: _t.surfaceDark
+                                border.color: regionField.activeFocus ? _t.accent : _t.borderSubtle
+                                radius: 8
+                            }
+                        }
+                    }
+                    Text {
+                        text: "Localized titles and overviews for movie/TV search, e.g. de-DE and DE"
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                    }
+                }
+
+                // Result pages
+                ColumnLayout {
+                    Layout.leftMargin: 20
+                    Layout.rightMargin: 20
+                    spacing: 4
+
+                    Text { text: "TMDB Result Pages"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
+                    SpinBox {
+                        id: resultPagesSpin
+                        from: 1
+                        to: 5
+                        editable: true
+                    }
+                    Text {
+                        text: "Pages of 20 results fetched per movie/TV search; fewer is faster on slow connections"
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                    }
+                }
+
+                // Poster size
+                ColumnLayout {
+                    Layout.leftMargin: 20
+                    Layout.rightMargin: 20
+                    spacing: 4
+
+                    Text { text: "TMDB Poster Size"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
+                    ComboBox {
+                        id: posterSizeCombo
+                        Layout.preferredWidth: 160
+                        model: settingsWin.posterSizes
+                        background: Rectangle { color: _t.surfaceDark; border.color: posterSizeCombo.activeFocus ? _t.accent : _t.borderSubtle; radius: 8; implicitHeight: 36 }
+                        contentItem: Text { leftPadding: 12; text: posterSizeCombo.displayText; color: _t.textPrimary; font.pixelSize: 13; verticalAlignment: Text.AlignVCenter }
+                        indicator: Text { x: posterSizeCombo.width - width - 8; anchors.verticalCenter: parent.verticalCenter; text: "▾"; color: _t.textMuted; font.pixelSize: 14 }
+                        delegate: ItemDelegate {
+                            required property int index
+                            required property var modelData
+                            width: posterSizeCombo.width
+                            contentItem: Text { text: modelData; color: highlighted ? _t.textWhite : _t.textPrimary; font.pixelSize: 13; leftPadding: 8 }
+                            background: Rectangle { color: highlighted ? _t.accent : "transparent" }
+                            highlighted: posterSizeCombo.highlightedIndex === index
+                        }
+                    }
+                    Text {
+                        text: "Smaller sizes save bandwidth; applies to newly downloaded posters"
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                    }
+                }
+
+                // Search poster prefetch
+                ColumnLayout {
+                    Layout.leftMargin: 20
+                    Layout.rightMargin: 20
+                    spacing: 4
+
+                    CheckBox {
+                        id: prefetchPostersCheck
+                        text: "Download posters for search results"
+                        palette.text: _t.textPrimary
+                    }
+                    Text {
+                        text: "Shows thumbnails in the search dialog right away; unused ones are cleaned up over time"
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                    }
+                }
+
+                // Uniform grid posters
+                ColumnLayout {
+                    Layout.leftMargin: 20
+                    Layout.rightMargin: 20
+                    spacing: 4
+
+                    CheckBox {
+                        id: uniformPostersCheck
+                        text: "Uniform 2:3 posters in the grid"
+                        palette.text: _t.textPrimary
+                    }
+                    Text {
+                        text: "Crops or letterboxes art with other shapes; turn off to show each poster's own ratio"
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                    }
+                }
+
+                // Idle maintenance
+                ColumnLayout {
+                    Layout.leftMargin: 20
+                    Layout.rightMargin: 20
+                    spacing: 4
+
+                    CheckBox {
+                        id: idleMaintenanceCheck
+                        text: "Run background maintenance when idle"
+                        palette.text: _t.textPrimary
+                    }
+                    Text {
+                        text: "Cleans up unused cached posters and compacts the database log"
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                    }
+                }
+
+                // Title sorting
+                ColumnLayout {
+                    Layout.leftMargin: 20
+                    Layout.rightMargin: 20
+                    spacing: 4
+
+                    CheckBox {
+                        id: ignoreArticlesCheck
+                        text: "Ignore leading articles when sorting by title"
+                        palette.text: _t.textPrimary
+                    }
+                    Text {
+                        text: "Sorts \"The Matrix\" under M and \"2 Fast 2 Furious\" before \"22 Jump Street\""
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                    }
+                }
+
                 // Quality Types
                 ColumnLayout {
                     Layout.fillWidth: true
@@ -207,12 +562,28 @@ Window {
                                             anchors.rightMargin: 6
                                             spacing: 8
 
-                                            Text {
+                                            TextField {
+                                                id: qtNameField
                                                 text: modelData
                                                 color: _t.textPrimary
                                                 font.pixelSize: 13
                                                 Layout.fillWidth: true
-                                                elide: Text.ElideRight
+                                                leftPadding: 0
+                                                background: Rectangle {
+                                                    color: "transparent"
+                                                    border.color: qtNameField.activeFocus ? _t.accent : "transparent"
+                                                    radius: 4
+                                                }
+                                                onEditingFinished: {
+                                                    if (!settingsWin.renameQualityType(index, text)) text = modelData
+                                                }
+                                            }
+
+                                            Text {
+                                                property int count: settingsWin.qualityTypeCounts[modelData] || 0
+                                                text: count === 1 ? "1 item" : count + " items"
+                                                color: _t.textMuted
+                                                font.pixelSize: 11
                                             }
 
                                             Rectangle {
@@ -260,6 +631,24 @@ Window {
                         }
                     }
 
+                    Text {
+                        text: "Used by items but not listed: " + settingsWin.unlistedQualityTypes.join(", ")
+                        color: _t.warning
+                        font.pixelSize: 11
+                        wrapMode: Text.Wrap
+                        Layout.fillWidth: true
+                        visible: settingsWin.unlistedQualityTypes.length > 0
+                    }
+
+                    Text {
+                        text: "Kept on the last save because items still use them: " + settingsWin.keptQualityTypes.join(", ")
+                        color: _t.warning
+                        font.pixelSize: 11
+                        wrapMode: Text.Wrap
+                        Layout.fillWidth: true
+                        visible: settingsWin.keptQualityTypes.length > 0
+                    }
+
                     // Add new quality type
                     RowLayout {
                         Layout.fillWidth: true
@@ -301,6 +690,161 @@ Window {
                     }
                 }
 
+                // Statuses
+                ColumnLayout {
+                    Layout.fillWidth: true
+                    Layout.leftMargin: 20
+                    Layout.rightMargin: 20
+                    spacing: 8
+
+                    Text { text: "Statuses"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
+
+                    Repeater {
+                        model: settingsWin.statuses
+
+                        RowLayout {
+                            Layout.fillWidth: true
+                            spacing: 8
+
+                            TextField {
+                                id: statusNameField
+                                Layout.fillWidth: true
+                                text: modelData
+                                color: _t.textPrimary
+                                font.pixelSize: 13
+                                background: Rectangle {
+                                    color: _t.surfaceDark
+                                    border.color: statusNameField.activeFocus ? _t.accent : _t.borderSubtle
+                                    radius: 8
+                                }
+                                onEditingFinished: if (text.trim() !== "") settingsWin.renameStatus(index, text)
+                            }
+
+                            Rectangle {
+                                Layout.preferredWidth: removeStatusText.implicitWidth + 12
+                                Layout.preferredHeight: 24
+                                radius: 4
+                                color: removeStatusMouse.containsMouse ? "#3def4444" : "transparent"
+                                visible: settingsWin.statuses.length > 1
+
+                                Text {
+                                    id: removeStatusText
+                                    anchors.centerIn: parent
+                                    text: "Remove"
+                                    color: removeStatusMouse.containsMouse ? _t.danger : _t.textMuted
+                                    font.pixelSize: 11
+                                }
+                                MouseArea {
+                                    id: removeStatusMouse
+                                    anchors.fill: parent
+                                    hoverEnabled: true
+                                    cursorShape: Qt.PointingHandCursor
+                                    onClicked: settingsWin.removeStatus(index)
+                                }
+                            }
+                        }
+                    }
+
+                    RowLayout {
+                        Layout.fillWidth: true
+                        spacing: 8
+
+                        TextField {
+                            id: newStatusField
+                            Layout.fillWidth: true
+                            placeholderText: "New status..."
+                            placeholderTextColor: _t.textMuted
+                            color: _t.textPrimary
+                            font.pixelSize: 13
+                            background: Rectangle {
+                                color: _t.surfaceDark
+                                border.color: newStatusField.activeFocus ? _t.accent : _t.borderSubtle
+                                radius: 8
+                            }
+                            onAccepted: settingsWin.addStatus()
+                        }
+
+                        Rectangle {
+                            Layout.preferredWidth: 50
+                            Layout.preferredHeight: 36
+                            radius: 8
+                            color: addStatusMouse.containsMouse ? _t.accentHover : _t.accent
+
+                            Text {
+                                anchors.centerIn: parent
+                                text: "Add"
+                                color: _t.textWhite
+                                font.pixelSize: 13
+                                font.bold: true
+                            }
+                            MouseArea {
+                                id: addStatusMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                                onClicked: settingsWin.addStatus()
+                            }
+                        }
+                    }
+
+                    Text {
+                        text: "Editing a name renames it for existing items. Statuses still in use can't be removed."
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                        wrapMode: Text.Wrap
+                        Layout.fillWidth: true
+                    }
+                }
+
+                // Default status per media type
+                ColumnLayout {
+                    Layout.fillWidth: true
+                    Layout.leftMargin: 20
+                    Layout.rightMargin: 20
+                    spacing: 8
+
+                    Text { text: "Default Status"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
+
+                    Repeater {
+                        model: settingsWin.mediaTypes
+
+                        RowLayout {
+                            Layout.fillWidth: true
+                            spacing: 12
+
+                            property string mediaType: modelData
+
+                            Text {
+                                text: mediaType
+                                color: _t.textPrimary
+                                font.pixelSize: 13
+                                Layout.preferredWidth: 60
+                            }
+                            ComboBox {
+                                id: defaultStatusCombo
+                                Layout.preferredWidth: 180
+                                model: settingsWin.statuses
+                                currentIndex: settingsWin.defaultStatusIndex[mediaType] || 0
+                                onActivated: (idx) => settingsWin.setDefaultStatusIndex(mediaType, idx)
+                                background: Rectangle { color: _t.surfaceDark; border.color: defaultStatusCombo.activeFocus ? _t.accent : _t.borderSubtle; radius: 8; implicitHeight: 36 }
+                                contentItem: Text { leftPadding: 12; text: defaultStatusCombo.displayText; color: _t.textPrimary; font.pixelSize: 13; verticalAlignment: Text.AlignVCenter }
+                                indicator: Text { x: defaultStatusCombo.width - width - 8; anchors.verticalCenter: parent.verticalCenter; text: "▾"; color: _t.textMuted; font.pixelSize: 14 }
+                                delegate: ItemDelegate {
+                                    required property int index
+                                    required property var modelData
+                                    width: defaultStatusCombo.width
+                                    contentItem: Text { text: modelData; color: highlighted ? _t.textWhite : _t.textPrimary; font.pixelSize: 13; leftPadding: 8 }
+                                    background: Rectangle { color: highlighted ? _t.accent : "transparent" }
+                                    highlighted: defaultStatusCombo.highlightedIndex === index
+                                }
+                            }
+                        }
+                    }
+
+                    Text {
+                        text: "The status a page opens on and new items from search start in"
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                    }
+                }
+
                 // Row Height (Table View)
                 ColumnLayout {
                     Layout.fillWidth: true
@@ -338,6 +882,220 @@ Window {
                     }
                 }
 
+                // Posters
+                ColumnLayout {
+                    Layout.fillWidth: true
+                    Layout.leftMargin: 20
+                    Layout.rightMargin: 20
+                    spacing: 8
+
+                    Text { text: "Posters"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
+
+                    RowLayout {
+                        Layout.fillWidth: true
+                        spacing: 12
+
+                        Rectangle {
+                            Layout.preferredWidth: refetchText.implicitWidth + 24
+                            Layout.preferredHeight: 32
+                            radius: 8
+                            color: controller.refetching_posters
+                                ? (refetchMouse.containsMouse ? _t.dangerHover : _t.danger)
+                                : (refetchMouse.containsMouse ? _t.accentHover : _t.accent)
+
+                            Text {
+                                id: refetchText
+                                anchors.centerIn: parent
+                                text: controller.refetching_posters ? "Cancel" : "Re-fetch Missing Posters"
+                                color: _t.textWhite
+                                font.pixelSize: 13
+                                font.bold: true
+                            }
+                            MouseArea {
+                                id: refetchMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                                onClicked: {
+                                    if (controller.refetching_posters) controller.cancelPosterRefetch()
+                                    else controller.refetchMissingPosters()
+                                }
+                            }
+                        }
+
+                        Text {
+                            text: settingsWin.posterTotal > 0 ? settingsWin.posterDone + " / " + settingsWin.posterTotal : ""
+                            color: _t.textMuted
+                            font.pixelSize: 12
+                            visible: controller.refetching_posters
+                        }
+                    }
+
+                    Text {
+                        text: "Downloads posters for items whose image is missing, using their TMDB/AniList ID"
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                    }
+                }
+
+                // Image cache
+                ColumnLayout {
+                    Layout.fillWidth: true
+                    Layout.leftMargin: 20
+                    Layout.rightMargin: 20
+                    spacing: 8
+
+                    Text { text: "Image Cache"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
+
+                    Text {
+                        text: settingsWin.cacheStats
+                            ? settingsWin.cacheStats.file_count + " files, "
+                              + settingsWin.formatBytes(settingsWin.cacheStats.total_bytes) + " ("
+                              + settingsWin.cacheStats.orphaned_count + " unused)"
+                            : "Calculating..."
+                        color: _t.textPrimary
+                        font.pixelSize: 13
+                    }
+
+                    RowLayout {
+                        Layout.fillWidth: true
+                        spacing: 8
+
+                        Rectangle {
+                            Layout.preferredWidth: clearOrphansText.implicitWidth + 24
+                            Layout.preferredHeight: 32
+                            radius: 8
+                            color: clearOrphansMouse.containsMouse ? _t.surfaceElevated : _t.surfaceCardHover
+                            border.color: _t.borderSubtle
+
+                            Text {
+                                id: clearOrphansText
+                                anchors.centerIn: parent
+                                text: "Clear Unused"
+                                color: _t.textPrimary
+                                font.pixelSize: 13
+                            }
+                            MouseArea {
+                                id: clearOrphansMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                                onClicked: controller.clearImageCache(true)
+                            }
+                        }
+
+                        Rectangle {
+                            Layout.preferredWidth: clearAllText.implicitWidth + 24
+                            Layout.preferredHeight: 32
+                            radius: 8
+                            color: clearAllMouse.containsMouse ? _t.dangerHover : _t.danger
+
+                            Text {
+                                id: clearAllText
+                                anchors.centerIn: parent
+                                text: "Clear All"
+                                color: _t.textWhite
+                                font.pixelSize: 13
+                                font.bold: true
+                            }
+                            MouseArea {
+                                id: clearAllMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                                onClicked: controller.clearImageCache(false)
+                            }
+                        }
+                    }
+
+                    Text {
+                        text: "Clear All removes every cached poster; use Re-fetch Missing Posters to download them again"
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                    }
+                }
+
+                // Title cleanup
+                ColumnLayout {
+                    Layout.fillWidth: true
+                    Layout.leftMargin: 20
+                    Layout.rightMargin: 20
+                    spacing: 8
+
+                    Text { text: "Titles"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
+
+                    RowLayout {
+                        Layout.fillWidth: true
+                        spacing: 12
+
+                        Rectangle {
+                            Layout.preferredWidth: normalizeText.implicitWidth + 24
+                            Layout.preferredHeight: 32
+                            radius: 8
+                            color: normalizeMouse.containsMouse ? _t.surfaceElevated : _t.surfaceCardHover
+                            border.color: _t.borderSubtle
+
+                            Text {
+                                id: normalizeText
+                                anchors.centerIn: parent
+                                text: "Normalize Titles..."
+                                color: _t.textPrimary
+                                font.pixelSize: 13
+                            }
+                            MouseArea {
+                                id: normalizeMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                                onClicked: normalizeDialog.open()
+                            }
+                        }
+
+                        CheckBox {
+                            id: titleCaseCheck
+                            text: "Also apply title case"
+                            palette.text: _t.textPrimary
+                        }
+                    }
+
+                    Text {
+                        text: "Trims stray spaces from every title; native and romaji titles are left as they are"
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                    }
+                }
+
+                // Log file
+                ColumnLayout {
+                    id: logSection
+                    Layout.fillWidth: true
+                    Layout.leftMargin: 20
+                    Layout.rightMargin: 20
+                    spacing: 8
+
+                    property string logPath: settingsWin.visible ? controller.getLogFilePath() : ""
+
+                    Text { text: "Log"; color: _t.textSecondary; font.pixelSize: 12; font.bold: true }
+
+                    Rectangle {
+                        Layout.preferredWidth: openLogText.implicitWidth + 24
+                        Layout.preferredHeight: 32
+                        radius: 8
+                        color: openLogMouse.containsMouse ? _t.surfaceElevated : _t.surfaceCardHover
+                        border.color: _t.borderSubtle
+                        opacity: logSection.logPath !== "" ? 1 : 0.5
+
+                        Text {
+                            id: openLogText
+                            anchors.centerIn: parent
+                            text: "Open Log"
+                            color: _t.textPrimary
+                            font.pixelSize: 13
+                        }
+                        MouseArea {
+                            id: openLogMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                            enabled: logSection.logPath !== ""
+                            onClicked: Qt.openUrlExternally("file:///" + logSection.logPath.replace(/\\/g, "/").replace(/^\/+/, ""))
+                        }
+                    }
+
+                    Text {
+                        text: logSection.logPath !== "" ? logSection.logPath : "Nothing logged yet"
+                        color: _t.textMuted
+                        font.pixelSize: 11
+                        elide: Text.ElideMiddle
+                        Layout.fillWidth: true
+                    }
+                }
+
                 Item { Layout.preferredHeight: 8 }
             }
         }
@@ -387,8 +1145,20 @@ Window {
                         MouseArea {
                             id: sSaveMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
                             onClicked: {
-                                controller.saveSettings(apiKeyField.text, adultCheck.checked, settingsWin.getQualityTypesString())
+                                settingsWin.keptQualityTypes = []
+                                controller.saveSettings(apiKeyField.text, keyringCheck.checked, adultCheck.checked, settingsWin.getQualityTypesString())
                                 controller.setRowHeight(Math.round(rowHeightSlider.value))
+                                controller.setIdleMaintenance(idleMaintenanceCheck.checked)
+                                controller.setIgnoreArticles(ignoreArticlesCheck.checked)
+                                controller.setPrefetchSearchPosters(prefetchPostersCheck.checked)
+                                controller.setUniformPosters(uniformPostersCheck.checked)
+                                if (posterSizeCombo.currentText !== "") controller.setPosterSize(posterSizeCombo.currentText)
+                                controller.setTmdbLocale(languageField.text, regionField.text)
+                                controller.setTmdbResultPages(resultPagesSpin.value)
+                                if (settingsWin.statusesEdited()) settingsWin.saveStatusEdits()
+                                settingsWin.mediaTypes.forEach(function(type) {
+                                    controller.setDefaultStatus(type, settingsWin.statuses[settingsWin.defaultStatusIndex[type] || 0] || "")
+                                })
                                 settingsWin.close()
                             }
                         }
@@ -397,4 +1167,75 @@ Window {
             }
         }
     }
+
+    Dialog {
+        id: normalizeDialog
+        modal: true
+        anchors.centerIn: parent
+
+        background: Rectangle {
+            color: _t.surfaceCard
+            border.color: _t.borderSubtle
+            radius: 12
+        }
+
+        header: Rectangle {
+            color: "transparent"
+            height: 44
+            Text {
+                anchors.left: parent.left
+                anchors.leftMargin: 16
+                anchors.verticalCenter: parent.verticalCenter
+                text: "Normalize Titles"
+                color: _t.textPrimary
+                font.pixelSize: 15
+                font.bold: true
+            }
+        }
+
+        Label {
+            text: titleCaseCheck.checked
+                ? "Trim and title-case every title in the library?"
+                : "Trim stray spaces from every title in the library?"
+            color: _t.textPrimary
+        }
+
+        footer: Rectangle {
+            color: "transparent"
+            height: 52
+            RowLayout {
+                anchors.fill: parent
+                anchors.rightMargin: 12
+                Item { Layout.fillWidth: true }
+                Rectangle {
+                    Layout.preferredWidth: 60; Layout.preferredHeight: 32
+                    color: "transparent"
+                    Text {
+                        anchors.centerIn: parent; text: "Cancel"
+                        color: nCancelMouse.containsMouse ? _t.textPrimary : _t.textSecondary
+                        font.pixelSize: 13
+                    }
+                    MouseArea {
+                        id: nCancelMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                        onClicked: normalizeDialog.reject()
+                    }
+                }
+                Rectangle {
+                    Layout.preferredWidth: 90; Layout.preferredHeight: 32
+                    radius: 8
+                    color: nOkMouse.containsMouse ? _t.accentHover : _t.accent
+                    Text {
+                        anchors.centerIn: parent; text: "Normalize"
+                        color: _t.textWhite; font.pixelSize: 13; font.bold: true
+                    }
+                    MouseArea {
+                        id: nOkMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                        onClicked: normalizeDialog.accept()
+                    }
+                }
+            }
+        }
+
+        onAccepted: controller.normalizeTitles(titleCaseCheck.checked)
+    }
 }
@@ -2,6 +2,7 @@ import QtQuick
 import QtQuick.Controls
 import QtQuick.Layouts
 import QtQuick.Window
+import QtQuick.Dialogs
 import com.mediatracker
 
 ApplicationWindow {
@@ -70,11 +71,17 @@ ApplicationWindow {
     property string activeStatus: "On Drive"
     property string viewMode: "grid"
     property string searchTerm: ""
-    property var selectedIds: []
+    property string recentView: controller.recent_view // "added"/"updated" across all types, "" for a page
     property int lastClickedRow: -1
     property bool preserveScrollOnNextReload: false
     property real preservedScrollY: 0
     property var savedScrollByContext: ({})
+    property var statusOptions: ["On Drive", "To Download", "To Work On"]
+    property var statusCounts: JSON.parse(controller.status_counts || "{}")
+    property var navStatusCounts: ({})   // { page: { status: count } } for sidebar tooltips
+    property var recentItems: []          // MediaItem objects, most recent first
+    property var qualityFilterOptions: [] // [{ quality, label }], "" = all
+    property var sourceFilterOptions: []  // [{ source, label }], "" = all
 
     // ---- Clipboard helper (uses Qt's native clipboard) ----
     TextInput {
@@ -87,15 +94,40 @@ ApplicationWindow {
         clipboardHelper.copy()
     }
 
+    // ---- Status hotkeys: 1/2/3 move the selection to that status ----
+    Repeater {
+        model: statusOptions
+        delegate: Item {
+            Shortcut {
+                sequence: String(index + 1)
+                enabled: mediaModel.selected_count > 0 && !searchInput.activeFocus && !editDialog.visible
+                onActivated: moveSelectedToStatusIndex(index)
+            }
+        }
+    }
+
     // ---- Backend Objects ----
     AppController {
         id: controller
         onItemsChanged: {
-            mediaModel.reload(activePage, activeStatus, searchTerm, controller.sort_field, controller.sort_dir)
+            refreshRecentItems()
+            refreshQualityFilterOptions()
+            refreshSourceFilterOptions()
+            reloadMediaModel()
             if (preserveScrollOnNextReload) {
                 Qt.callLater(restoreScrollPosition)
             }
         }
+        onItemsPatched: (ids) => {
+            refreshRecentItems()
+            refreshQualityFilterOptions()
+            refreshSourceFilterOptions()
+            if (recentView !== "")
+                reloadMediaModel()
+            else
+                mediaModel.applyChanges(ids, controller.viewQuery())
+        }
+        onSearchPostersUpdated: (index) => searchModel.refreshPoster(index)
         onSearchResultsReady: {
             searchModel.loadFromState()
             if (editDialog.visible) editDialog.onSearchDone()
@@ -103,12 +135,32 @@ ApplicationWindow {
         onSearchingChanged: (searching) => {
             if (editDialog.visible) editDialog.searching = searching
         }
-        onToastMessage: (message, type_) => toast.show(message, type_)
-        onCountsChanged: {} // counts are properties, auto-update
+        onToastMessage: (message, type_) => {
+            toast.show(message, type_)
+            // A failed batch add never reports the end of its saving phase
+            if (type_ === "error") batchProgress.phase = ""
+        }
+        onBatchProgress: (done, total, phase) => {
+            batchProgress.done = done
+            batchProgress.total = total
+            batchProgress.phase = phase === "saving" && done >= total ? "" : phase
+        }
+        onQualityTypesInUse: (countsJson) => {
+            var counts = JSON.parse(countsJson)
+            settingsDialog.keptQualityTypes = Object.keys(counts).map(function(t) { return t + " (" + counts[t] + ")" })
+        }
+        onNotificationsChanged: notificationModel.reload()
+        onCountsChanged: refreshNavStatusCounts()
+        onRecentItemsChanged: refreshRecentItems()
+        onStatusesChanged: {
+            statusOptions = controller.getStatusOptions().split("\n")
+            activeStatus = controller.active_status
+        }
         Component.onCompleted: {
             controller.loadConfig()
+            statusOptions = controller.getStatusOptions().split("\n")
             activePage = "Movie"
-            activeStatus = "On Drive"
+            activeStatus = defaultStatusFor("Movie")
             viewMode = controller.view_mode !== "" ? controller.view_mode : "grid"
             controller.navigateTo("Movie")
         }
@@ -116,6 +168,7 @@ ApplicationWindow {
 
     MediaModel { id: mediaModel }
     SearchModel { id: searchModel }
+    NotificationModel { id: notificationModel }
 
     // ---- Main Layout ----
     RowLayout {
@@ -147,13 +200,14 @@ ApplicationWindow {
                     model: [
                         { page: "Movie", icon: "🎬", label: "Movies" },
                         { page: "TV", icon: "📺", label: "TV Shows" },
-                        { page: "Anime", icon: "⛩", label: "Anime" }
+                        { page: "Anime", icon: "⛩", label: "Anime" },
+                        { page: "Manga", icon: "📖", label: "Manga" }
                     ]
                     delegate: Rectangle {
                         Layout.fillWidth: true
                         Layout.preferredHeight: 40
                         radius: _t.borderRadius
-                        color: activePage === modelData.page ? _t.accent : (navMouse.containsMouse ? _t.surfaceCardHover : "transparent")
+                        color: activePage === modelData.page && recentView === "" ? _t.accent : (navMouse.containsMouse ? _t.surfaceCardHover : "transparent")
 
                         RowLayout {
                             anchors.fill: parent
@@ -167,19 +221,24 @@ ApplicationWindow {
                             }
                             Text {
                                 text: modelData.label
-                                color: activePage === modelData.page ? _t.textWhite : _t.textSecondary
+                                color: activePage === modelData.page && recentView === "" ? _t.textWhite : _t.textSecondary
                                 font.pixelSize: 14
                                 Layout.fillWidth: true
                             }
                             Text {
                                 text: modelData.page === "Movie" ? controller.movie_count :
                                       modelData.page === "TV" ? controller.tv_count :
-                                      controller.anime_count
+                                      modelData.page === "Anime" ? controller.anime_count :
+                                      controller.manga_count
                                 color: _t.textMuted
                                 font.pixelSize: 12
                             }
                         }
 
+                        ToolTip.visible: navMouse.containsMouse
+                        ToolTip.delay: 600
+                        ToolTip.text: statusBreakdown(modelData.page)
+
                         MouseArea {
                             id: navMouse
                             anchors.fill: parent
@@ -188,9 +247,9 @@ ApplicationWindow {
                             onClicked: {
                                 captureScrollPosition()
                                 activePage = modelData.page
-                                activeStatus = "On Drive"
+                                activeStatus = defaultStatusFor(modelData.page)
                                 searchTerm = ""
-                                selectedIds = []
+                                mediaModel.clearSelection()
                                 lastClickedRow = -1
                                 restoreSavedScrollForContext(activePage, activeStatus, viewMode)
                                 controller.navigateTo(modelData.page)
@@ -199,8 +258,83 @@ ApplicationWindow {
                     }
                 }
 
+                // Newest items across every media type
+                Rectangle {
+                    Layout.fillWidth: true
+                    Layout.preferredHeight: 40
+                    radius: _t.borderRadius
+                    color: recentView !== "" ? _t.accent : (recentNavMouse.containsMouse ? _t.surfaceCardHover : "transparent")
+
+                    RowLayout {
+                        anchors.fill: parent
+                        anchors.leftMargin: 12
+                        anchors.rightMargin: 12
+                        spacing: 10
+                        Text { text: "🕘"; font.pixelSize: 16 }
+                        Text {
+                            text: "Recent"
+                            color: recentView !== "" ? _t.textWhite : _t.textSecondary
+                            font.pixelSize: 14
+                            Layout.fillWidth: true
+                        }
+                    }
+                    MouseArea {
+                        id: recentNavMouse
+                        anchors.fill: parent
+                        hoverEnabled: true
+                        cursorShape: Qt.PointingHandCursor
+                        onClicked: {
+                            if (recentView !== "") return
+                            captureScrollPosition()
+                            searchInput.text = ""
+                            mediaModel.clearSelection()
+                            lastClickedRow = -1
+                            controller.showRecent(false)
+                        }
+                    }
+                }
+
                 Item { Layout.fillHeight: true }
 
+                // Notifications button
+                Rectangle {
+                    Layout.fillWidth: true
+                    Layout.preferredHeight: 40
+                    radius: _t.borderRadius
+                    color: notificationsMouse.containsMouse ? _t.surfaceCardHover : "transparent"
+
+                    RowLayout {
+                        anchors.fill: parent
+                        anchors.leftMargin: 12
+                        anchors.rightMargin: 12
+                        spacing: 10
+                        Text { text: "🔔"; font.pixelSize: 16 }
+                        Text { text: "Notifications"; color: _t.textSecondary; font.pixelSize: 14; Layout.fillWidth: true }
+                        Rectangle {
+                            visible: notificationModel.unread_count > 0
+                            Layout.preferredWidth: Math.max(20, unreadText.implicitWidth + 10)
+                            Layout.preferredHeight: 20
+                            radius: 10
+                            color: _t.accent
+                            Text {
+                                id: unreadText
+                                anchors.centerIn: parent
+                                text: notificationModel.unread_count
+                                color: _t.textWhite
+                                font.pixelSize: 11
+                                font.bold: true
+                            }
+                        }
+                    }
+                    MouseArea {
+                        id: notificationsMouse
+                        anchors.fill: parent
+                        hoverEnabled: true
+                        cursorShape: Qt.PointingHandCursor
+                        onClicked: notificationsDialog.open()
+                    }
+                }
+
                 // Settings button
                 Rectangle {
                     Layout.fillWidth: true
@@ -255,7 +389,9 @@ ApplicationWindow {
 
                     // Page title
                     Text {
-                        text: activePage === "Movie" ? "Movies" : activePage === "TV" ? "TV Shows" : "Anime"
+                        text: recentView === "added" ? "Recently Added"
+                            : recentView === "updated" ? "Recently Updated"
+                            : activePage === "Movie" ? "Movies" : activePage === "TV" ? "TV Shows" : activePage
                         color: _t.textWhite
                         font.pixelSize: 20
                         font.bold: true
@@ -274,6 +410,7 @@ ApplicationWindow {
                     Rectangle {
                         Layout.preferredWidth: 220
                         Layout.preferredHeight: 36
+                        visible: recentView === ""
                         radius: _t.borderRadius
                         color: _t.surfaceDark
                         border.color: searchInput.activeFocus ? _t.accent : _t.borderSubtle
@@ -293,6 +430,7 @@ ApplicationWindow {
                                 searchTerm = text
                                 controller.setSearchTerm(text)
                             }
+                            onAccepted: controller.searchNow()
                         }
                     }
 
@@ -350,11 +488,61 @@ ApplicationWindow {
                 }
             }
 
+            // ---- Recent view: added / updated ----
+            Rectangle {
+                Layout.fillWidth: true
+                Layout.preferredHeight: 44
+                color: _t.surface
+                visible: recentView !== ""
+
+                RowLayout {
+                    anchors.fill: parent
+                    anchors.leftMargin: 20
+                    spacing: 4
+
+                    Repeater {
+                        model: [{ view: "added", label: "Added" }, { view: "updated", label: "Updated" }]
+                        delegate: Rectangle {
+                            Layout.preferredHeight: 32
+                            Layout.preferredWidth: recentTabText.implicitWidth + 24
+                            radius: 6
+                            color: isActive ? _t.accent : (recentTabMouse.containsMouse ? _t.surfaceCardHover : "transparent")
+                            property bool isActive: recentView === modelData.view
+
+                            Text {
+                                id: recentTabText
+                                anchors.centerIn: parent
+                                text: modelData.label
+                                color: parent.isActive ? _t.textWhite : _t.textSecondary
+                                font.pixelSize: 13
+                            }
+                            MouseArea {
+                                id: recentTabMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                                onClicked: {
+                                    mediaModel.clearSelection()
+                                    lastClickedRow = -1
+                                    controller.showRecent(modelData.view === "updated")
+                                }
+                            }
+                        }
+                    }
+
+                    Item { Layout.fillWidth: true }
+                }
+
+                Rectangle {
+                    anchors.bottom: parent.bottom
+                    width: parent.width; height: 1
+                    color: _t.borderSubtle
+                }
+            }
+
             // ---- Status Tabs ----
             Rectangle {
                 Layout.fillWidth: true
                 Layout.preferredHeight: 44
                 color: _t.surface
+                visible: recentView === ""
 
                 RowLayout {
                     anchors.fill: parent
@@ -362,18 +550,19 @@ ApplicationWindow {
                     spacing: 4
 
                     Repeater {
-                        model: ["On Drive", "To Download", "To Work On"]
+                        model: statusOptions
                         delegate: Rectangle {
                             Layout.preferredHeight: 32
                             Layout.preferredWidth: statusText.implicitWidth + 24
                             radius: 6
-                            color: activeStatus === modelData ? _t.accent : (statusMouse.containsMouse ? _t.surfaceCardHover : "transparent")
+                            color: isActive ? _t.accent : (statusMouse.containsMouse ? _t.surfaceCardHover : "transparent")
+                            property bool isActive: activeStatus === modelData && !controller.favorites_only
 
                             Text {
                                 id: statusText
                                 anchors.centerIn: parent
                                 text: modelData + " (" + statusCountFor(modelData) + ")"
-                                color: activeStatus === modelData ? _t.textWhite : _t.textSecondary
+                                color: parent.isActive ? _t.textWhite : _t.textSecondary
                                 font.pixelSize: 13
                             }
                             MouseArea {
@@ -381,16 +570,207 @@ ApplicationWindow {
                                 onClicked: {
                                     captureScrollPosition()
                                     activeStatus = modelData
-                                    selectedIds = []
+                                    mediaModel.clearSelection()
                                     lastClickedRow = -1
                                     restoreSavedScrollForContext(activePage, activeStatus, viewMode)
+                                    if (controller.favorites_only) controller.setFavoritesOnly(false)
                                     controller.setStatus(modelData)
                                 }
                             }
                         }
                     }
 
+                    // Favorites across every status
+                    Rectangle {
+                        Layout.preferredHeight: 32
+                        Layout.preferredWidth: favText.implicitWidth + 24
+                        radius: 6
+                        color: controller.favorites_only ? _t.accent : (favMouse.containsMouse ? _t.surfaceCardHover : "transparent")
+
+                        Text {
+                            id: favText
+                            anchors.centerIn: parent
+                            text: "★ Favorites"
+                            color: controller.favorites_only ? _t.textWhite : _t.textSecondary
+                            font.pixelSize: 13
+                        }
+                        MouseArea {
+                            id: favMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                            onClicked: {
+                                mediaModel.clearSelection()
+                                lastClickedRow = -1
+                                controller.setFavoritesOnly(!controller.favorites_only)
+                            }
+                        }
+                    }
+
+                    // Items with no poster or a missing cached file
+                    Rectangle {
+                        Layout.preferredHeight: 32
+                        Layout.preferredWidth: missingPosterText.implicitWidth + 24
+                        radius: 6
+                        color: controller.missing_poster ? _t.accent : (missingPosterMouse.containsMouse ? _t.surfaceCardHover : "transparent")
+
+                        Text {
+                            id: missingPosterText
+                            anchors.centerIn: parent
+                            text: "No poster"
+                            color: controller.missing_poster ? _t.textWhite : _t.textSecondary
+                            font.pixelSize: 13
+                        }
+                        MouseArea {
+                            id: missingPosterMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                            onClicked: {
+                                mediaModel.clearSelection()
+                                lastClickedRow = -1
+                                controller.showMissingPosters(!controller.missing_poster)
+                            }
+                        }
+                    }
+
+                    // Items missing year, quality or source
+                    Rectangle {
+                        Layout.preferredHeight: 32
+                        Layout.preferredWidth: incompleteText.implicitWidth + 24
+                        radius: 6
+                        color: controller.incomplete_only ? _t.accent : (incompleteMouse.containsMouse ? _t.surfaceCardHover : "transparent")
+
+                        Text {
+                            id: incompleteText
+                            anchors.centerIn: parent
+                            text: "Incomplete"
+                            color: controller.incomplete_only ? _t.textWhite : _t.textSecondary
+                            font.pixelSize: 13
+                        }
+                        MouseArea {
+                            id: incompleteMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                            onClicked: {
+                                mediaModel.clearSelection()
+                                lastClickedRow = -1
+                                controller.setIncompleteFilter(!controller.incomplete_only)
+                            }
+                        }
+                    }
+
+                    // Watched state, independent of status; click cycles through
+                    Rectangle {
+                        Layout.preferredHeight: 32
+                        Layout.preferredWidth: watchedText.implicitWidth + 24
+                        radius: 6
+                        color: controller.watched_filter !== "" ? _t.accent : (watchedMouse.containsMouse ? _t.surfaceCardHover : "transparent")
+
+                        Text {
+                            id: watchedText
+                            anchors.centerIn: parent
+                            text: controller.watched_filter === "unwatched" ? "Unwatched" : "Watched"
+                            color: controller.watched_filter !== "" ? _t.textWhite : _t.textSecondary
+                            font.pixelSize: 13
+                        }
+                        MouseArea {
+                            id: watchedMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                            onClicked: {
+                                mediaModel.clearSelection()
+                                lastClickedRow = -1
+                                var next = { "": "watched", "watched": "unwatched", "unwatched": "" }
+                                controller.setWatchedFilter(next[controller.watched_filter])
+                            }
+                        }
+                    }
+
                     Item { Layout.fillWidth: true }
+
+                    // Year range (blank = open on that side)
+                    Repeater {
+                        model: ["From", "To"]
+                        delegate: TextField {
+                            required property int index
+                            required property string modelData
+                            // Re-sync when the controller resets the range (page change)
+                            property int boundYear: index === 0 ? controller.min_year : controller.max_year
+                            onBoundYearChanged: text = boundYear > 0 ? String(boundYear) : ""
+                            Layout.preferredWidth: 64
+                            Layout.preferredHeight: 32
+                            Layout.rightMargin: index === 1 ? 16 : 4
+                            placeholderText: modelData
+                            placeholderTextColor: _t.textMuted
+                            color: _t.textPrimary
+                            font.pixelSize: 13
+                            validator: IntValidator { bottom: 0; top: 2099 }
+                            background: Rectangle { color: _t.surfaceDark; border.color: parent.activeFocus ? _t.accent : _t.borderSubtle; radius: 8 }
+                            onEditingFinished: {
+                                var year = parseInt(text) || 0
+                                if (year === boundYear) return
+                                mediaModel.clearSelection()
+                                lastClickedRow = -1
+                                if (index === 0) controller.setYearRange(year, controller.max_year)
+                                else controller.setYearRange(controller.min_year, year)
+                            }
+                        }
+                    }
+
+                    // Quality filter (only types actually used on this page)
+                    ComboBox {
+                        id: qualityFilterCombo
+                        Layout.preferredWidth: 180
+                        Layout.rightMargin: 16
+                        visible: qualityFilterOptions.length > 1
+                        model: qualityFilterOptions
+                        textRole: "label"
+                        currentIndex: {
+                            for (var i = 0; i < qualityFilterOptions.length; i++) {
+                                if (qualityFilterOptions[i].quality === controller.quality_filter) return i
+                            }
+                            return 0
+                        }
+                        onActivated: (index) => {
+                            mediaModel.clearSelection()
+                            lastClickedRow = -1
+                            controller.setQualityFilter(qualityFilterOptions[index].quality)
+                        }
+                        background: Rectangle { color: _t.surfaceDark; border.color: qualityFilterCombo.activeFocus ? _t.accent : _t.borderSubtle; radius: 8; implicitHeight: 32 }
+                        contentItem: Text { leftPadding: 12; text: qualityFilterCombo.displayText; color: controller.quality_filter !== "" ? _t.textPrimary : _t.textSecondary; font.pixelSize: 13; verticalAlignment: Text.AlignVCenter; elide: Text.ElideRight }
+                        indicator: Text { x: qualityFilterCombo.width - width - 8; anchors.verticalCenter: parent.verticalCenter; text: "▾"; color: _t.textMuted; font.pixelSize: 14 }
+                        delegate: ItemDelegate {
+                            required property int index
+                            required property var modelData
+                            width: qualityFilterCombo.width
+                            contentItem: Text { text: modelData.label; color: highlighted ? _t.textWhite : _t.textPrimary; font.pixelSize: 13; leftPadding: 8 }
+                            background: Rectangle { color: highlighted ? _t.accent : "transparent" }
+                            highlighted: qualityFilterCombo.highlightedIndex === index
+                        }
+                    }
+
+                    // Source filter (sources used anywhere in the library)
+                    ComboBox {
+                        id: sourceFilterCombo
+                        Layout.preferredWidth: 160
+                        Layout.rightMargin: 16
+                        visible: sourceFilterOptions.length > 1
+                        model: sourceFilterOptions
+                        textRole: "label"
+                        currentIndex: {
+                            for (var i = 0; i < sourceFilterOptions.length; i++) {
+                                if (sourceFilterOptions[i].source === controller.source_filter) return i
+                            }
+                            return 0
+                        }
+                        onActivated: (index) => {
+                            mediaModel.clearSelection()
+                            lastClickedRow = -1
+                            controller.setSourceFilter(sourceFilterOptions[index].source)
+                        }
+                        background: Rectangle { color: _t.surfaceDark; border.color: sourceFilterCombo.activeFocus ? _t.accent : _t.borderSubtle; radius: 8; implicitHeight: 32 }
+                        contentItem: Text { leftPadding: 12; text: sourceFilterCombo.displayText; color: controller.source_filter !== "" ? _t.textPrimary : _t.textSecondary; font.pixelSize: 13; verticalAlignment: Text.AlignVCenter; elide: Text.ElideRight }
+                        indicator: Text { x: sourceFilterCombo.width - width - 8; anchors.verticalCenter: parent.verticalCenter; text: "▾"; color: _t.textMuted; font.pixelSize: 14 }
+                        delegate: ItemDelegate {
+                            required property int index
+                            required property var modelData
+                            width: sourceFilterCombo.width
+                            contentItem: Text { text: modelData.label; color: highlighted ? _t.textWhite : _t.textPrimary; font.pixelSize: 13; leftPadding: 8 }
+                            background: Rectangle { color: highlighted ? _t.accent : "transparent" }
+                            highlighted: sourceFilterCombo.highlightedIndex === index
+                        }
+                    }
                 }
 
                 Rectangle {
@@ -400,6 +780,63 @@ ApplicationWindow {
                 }
             }
 
+            // ---- Jump back in (recently opened items) ----
+            Rectangle {
+                Layout.fillWidth: true
+                Layout.preferredHeight: 44
+                color: _t.surfaceDark
+                visible: recentItems.length > 0 && searchTerm === ""
+
+                RowLayout {
+                    anchors.fill: parent
+                    anchors.leftMargin: 16
+                    anchors.rightMargin: 16
+                    spacing: 8
+
+                    Text {
+                        text: "Jump back in"
+                        color: _t.textMuted
+                        font.pixelSize: 12
+                        font.bold: true
+                    }
+
+                    ListView {
+                        Layout.fillWidth: true
+                        Layout.preferredHeight: 28
+                        orientation: ListView.Horizontal
+                        spacing: 6
+                        clip: true
+                        boundsBehavior: Flickable.StopAtBounds
+                        model: recentItems
+
+                        delegate: Rectangle {
+                            height: 28
+                            width: Math.min(220, recentText.implicitWidth + 20)
+                            radius: 14
+                            color: recentMouse.containsMouse ? _t.surfaceCardHover : _t.surfaceCard
+                            border.color: _t.borderSubtle
+                            border.width: 1
+
+                            Text {
+                                id: recentText
+                                anchors.fill: parent
+                                anchors.leftMargin: 10
+                                anchors.rightMargin: 10
+                                verticalAlignment: Text.AlignVCenter
+                                text: modelData.title + "  ·  " + modelData.media_type
+                                color: _t.textSecondary
+                                font.pixelSize: 12
+                                elide: Text.ElideRight
+                            }
+                            MouseArea {
+                                id: recentMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                                onClicked: openRecentItem(modelData)
+                            }
+                        }
+                    }
+                }
+            }
+
             // ---- Content Area ----
             Item {
                 Layout.fillWidth: true
@@ -411,7 +848,7 @@ ApplicationWindow {
                     anchors.fill: parent
                     visible: viewMode === "grid"
                     model: mediaModel
-                    selectedIds: root.selectedIds
+                    showMediaType: recentView !== ""
                     onItemClicked: (row, modifiers) => handleItemClick(row, modifiers)
                     onItemDoubleClicked: (row) => handleItemDoubleClick(row)
                     onItemRightClicked: (row, mx, my) => showContextMenu(row, mx, my)
@@ -423,14 +860,18 @@ ApplicationWindow {
                     anchors.fill: parent
                     visible: viewMode === "table"
                     model: mediaModel
-                    selectedIds: root.selectedIds
+                    showMediaType: recentView !== ""
                     rowHeight: controller.row_height
                     sortField: controller.sort_field
                     sortDir: controller.sort_dir
+                    sortField2: controller.sort_field2
+                    sortDir2: controller.sort_dir2
+                    highlightMissing: controller.incomplete_only
                     onItemClicked: (row, modifiers) => handleItemClick(row, modifiers)
                     onItemDoubleClicked: (row) => handleItemDoubleClick(row)
                     onItemRightClicked: (row, mx, my) => showContextMenu(row, mx, my)
                     onSortRequested: (field, dir) => controller.setSortOrder(field, dir)
+                    onSortRequested2: (field, dir) => controller.setSortOrder2(field, dir)
                 }
 
                 // Empty state
@@ -453,6 +894,8 @@ ApplicationWindow {
         property string targetTitle: ""
         property string targetRomajiTitle: ""
         property string targetNativeTitle: ""
+        property bool targetFavorite: false
+        property bool targetWatched: false
 
         DarkItem {
             text: "Edit"
@@ -462,14 +905,14 @@ ApplicationWindow {
         // Copy Name — simple for Movie/TV
         DarkItem {
             text: "Copy Name"
-            visible: activePage !== "Anime"
+            visible: activePage !== "Anime" && activePage !== "Manga"
             onTriggered: copyToClipboard(contextMenu.targetTitle)
         }
 
-        // Copy Name — submenu for Anime (English, Romaji, Japanese)
+        // Copy Name — submenu for Anime/Manga (English, Romaji, Japanese)
         DarkMenu {
             title: "Copy Name"
-            visible: activePage === "Anime"
+            visible: activePage === "Anime" || activePage === "Manga"
 
             DarkItem {
                 text: {
@@ -498,12 +941,53 @@ ApplicationWindow {
             }
         }
 
+        DarkItem {
+            text: contextMenu.targetFavorite ? "Remove from Favorites" : "Add to Favorites"
+            onTriggered: controller.toggleFavorite(contextMenu.targetId)
+        }
+
+        DarkItem {
+            text: contextMenu.targetWatched ? "Mark Unwatched" : "Mark Watched"
+            onTriggered: controller.setWatched(String(contextMenu.targetId), !contextMenu.targetWatched)
+        }
+
+        DarkItem {
+            text: "Duplicate"
+            onTriggered: { captureScrollPosition(); controller.duplicateItem(contextMenu.targetId) }
+        }
+
+        DarkItem {
+            text: "Re-download Poster"
+            onTriggered: controller.refetchPoster(contextMenu.targetId)
+        }
+
+        DarkItem {
+            text: "Set Poster from URL..."
+            onTriggered: {
+                posterUrlDialog.itemId = contextMenu.targetId
+                posterUrlInput.text = ""
+                posterUrlDialog.open()
+            }
+        }
+
         DarkSep {}
         DarkMenu {
+            id: moveMenu
             title: "Move to..."
-            DarkItem { text: "On Drive"; visible: activeStatus !== "On Drive"; onTriggered: { captureScrollPosition(); controller.moveItems(String(contextMenu.targetId), "On Drive") } }
-            DarkItem { text: "To Download"; visible: activeStatus !== "To Download"; onTriggered: { captureScrollPosition(); controller.moveItems(String(contextMenu.targetId), "To Download") } }
-            DarkItem { text: "To Work On"; visible: activeStatus !== "To Work On"; onTriggered: { captureScrollPosition(); controller.moveItems(String(contextMenu.targetId), "To Work On") } }
+            Instantiator {
+                model: statusOptions
+                delegate: DarkItem {
+                    text: modelData
+                    visible: activeStatus !== modelData
+                    onTriggered: { captureScrollPosition(); controller.moveItems(String(contextMenu.targetId), modelData) }
+                }
+                onObjectAdded: (index, object) => moveMenu.insertItem(index, object)
+                onObjectRemoved: (index, object) => moveMenu.removeItem(object)
+            }
+        }
+        DarkItem {
+            text: "Advance status"
+            onTriggered: { captureScrollPosition(); controller.advanceStatus(String(contextMenu.targetId)) }
         }
         DarkSep {}
         DarkItem {
@@ -515,9 +999,13 @@ ApplicationWindow {
         }
 
         DarkSep {}
+        DarkItem {
+            text: "Export view as CSV..."
+            onTriggered: exportDialog.open()
+        }
         DarkItem {
             text: "Refresh"
-            onTriggered: mediaModel.reload(activePage, activeStatus, searchTerm, controller.sort_field, controller.sort_dir)
+            onTriggered: reloadMediaModel()
         }
     }
 
@@ -526,41 +1014,69 @@ ApplicationWindow {
         id: bulkContextMenu
 
         DarkItem {
-            text: selectedIds.length + " items selected"
+            text: mediaModel.selected_count + " items selected"
             enabled: false
         }
         DarkSep {}
         DarkMenu {
+            id: bulkMoveMenu
             title: "Move all to..."
+            Instantiator {
+                model: statusOptions
+                delegate: DarkItem {
+                    text: modelData
+                    visible: activeStatus !== modelData
+                    onTriggered: { captureScrollPosition(); controller.moveItems(mediaModel.getSelectedIds(), modelData) }
+                }
+                onObjectAdded: (index, object) => bulkMoveMenu.insertItem(index, object)
+                onObjectRemoved: (index, object) => bulkMoveMenu.removeItem(object)
+            }
+        }
+        DarkItem {
+            text: "Advance status"
+            onTriggered: { captureScrollPosition(); controller.advanceStatus(mediaModel.getSelectedIds()) }
+        }
+        DarkItem {
+            text: "Mark watched"
+            onTriggered: controller.setWatched(mediaModel.getSelectedIds(), true)
+        }
+        DarkItem {
+            text: "Mark unwatched"
+            onTriggered: controller.setWatched(mediaModel.getSelectedIds(), false)
+        }
+        DarkMenu {
+            title: "Copy titles"
             DarkItem {
-                text: "On Drive"
-                visible: activeStatus !== "On Drive"
-                onTriggered: { captureScrollPosition(); controller.moveItems(selectedIds.join(","), "On Drive"); selectedIds = [] }
+                text: "As plain text"
+                onTriggered: copyToClipboard(controller.getSelectedAsText(mediaModel.getSelectedIds(), "plain", false))
             }
             DarkItem {
-                text: "To Download"
-                visible: activeStatus !== "To Download"
-                onTriggered: { captureScrollPosition(); controller.moveItems(selectedIds.join(","), "To Download"); selectedIds = [] }
+                text: "As Markdown list"
+                onTriggered: copyToClipboard(controller.getSelectedAsText(mediaModel.getSelectedIds(), "markdown", false))
             }
             DarkItem {
-                text: "To Work On"
-                visible: activeStatus !== "To Work On"
-                onTriggered: { captureScrollPosition(); controller.moveItems(selectedIds.join(","), "To Work On"); selectedIds = [] }
+                text: "With Romaji/Japanese titles"
+                visible: activePage === "Anime" || activePage === "Manga"
+                onTriggered: copyToClipboard(controller.getSelectedAsText(mediaModel.getSelectedIds(), "plain", true))
             }
         }
         DarkSep {}
         DarkItem {
-            text: "Delete " + selectedIds.length + " items"
+            text: "Delete " + mediaModel.selected_count + " items"
             onTriggered: {
-                deleteDialog.itemIds = selectedIds.slice()
+                deleteDialog.itemIds = mediaModel.getSelectedIds().split(",")
                 deleteDialog.open()
             }
         }
 
         DarkSep {}
+        DarkItem {
+            text: "Export view as CSV..."
+            onTriggered: exportDialog.open()
+        }
         DarkItem {
             text: "Refresh"
-            onTriggered: mediaModel.reload(activePage, activeStatus, searchTerm, controller.sort_field, controller.sort_dir)
+            onTriggered: reloadMediaModel()
         }
     }
 
@@ -571,6 +1087,9 @@ ApplicationWindow {
         anchors.centerIn: parent
 
         property var itemIds: []
+        property var preview: ({ count: 0, titles: [] })
+
+        onAboutToShow: preview = JSON.parse(controller.previewDelete(itemIds.join(",")))
 
         background: Rectangle {
             color: _t.surfaceCard
@@ -592,9 +1111,29 @@ ApplicationWindow {
             }
         }
 
-        Label {
-            text: "Delete " + deleteDialog.itemIds.length + " item(s)? This cannot be undone."
-            color: _t.textPrimary
+        ColumnLayout {
+            spacing: 8
+
+            Label {
+                text: (deleteDialog.preview.count === 1 ? "Delete this item?" : "Delete these " + deleteDialog.preview.count + " items?")
+                      + " This cannot be undone."
+                color: _t.textPrimary
+            }
+
+            ListView {
+                Layout.preferredWidth: 360
+                Layout.preferredHeight: Math.min(contentHeight, 200)
+                clip: true
+                model: deleteDialog.preview.titles
+                ScrollBar.vertical: ScrollBar {}
+                delegate: Text {
+                    width: ListView.view.width
+                    text: "• " + modelData
+                    color: _t.textSecondary
+                    font.pixelSize: 12
+                    elide: Text.ElideRight
+                }
+            }
         }
 
         footer: Rectangle {
@@ -636,7 +1175,251 @@ ApplicationWindow {
         onAccepted: {
             captureScrollPosition()
             controller.deleteItems(itemIds.join(","))
-            selectedIds = []
+            mediaModel.clearSelection()
+        }
+    }
+
+    // ---- CSV export of the current view ----
+    FileDialog {
+        id: exportDialog
+        title: "Export View as CSV"
+        fileMode: FileDialog.SaveFile
+        nameFilters: ["CSV files (*.csv)"]
+        defaultSuffix: "csv"
+        onAccepted: controller.exportCurrentView(selectedFile.toString())
+    }
+
+    // ---- Poster URL Dialog ----
+    Dialog {
+        id: posterUrlDialog
+        modal: true
+        anchors.centerIn: parent
+        width: 420
+
+        property int itemId: -1
+
+        onOpened: posterUrlInput.forceActiveFocus()
+
+        background: Rectangle {
+            color: _t.surfaceCard
+            border.color: _t.borderSubtle
+            radius: 12
+        }
+
+        header: Rectangle {
+            color: "transparent"
+            height: 44
+            Text {
+                anchors.left: parent.left
+                anchors.leftMargin: 16
+                anchors.verticalCenter: parent.verticalCenter
+                text: "Set Poster from URL"
+                color: _t.textPrimary
+                font.pixelSize: 15
+                font.bold: true
+            }
+        }
+
+        Rectangle {
+            width: parent.width
+            height: 36
+            radius: _t.borderRadius
+            color: _t.surfaceDark
+            border.color: posterUrlInput.activeFocus ? _t.accent : _t.borderSubtle
+            border.width: 1
+
+            TextField {
+                id: posterUrlInput
+                anchors.fill: parent
+                anchors.leftMargin: 10
+                anchors.rightMargin: 10
+                placeholderText: "https://..."
+                placeholderTextColor: _t.textMuted
+                color: _t.textPrimary
+                font.pixelSize: 13
+                background: null
+                onAccepted: posterUrlDialog.accept()
+            }
+        }
+
+        footer: Rectangle {
+            color: "transparent"
+            height: 52
+            RowLayout {
+                anchors.fill: parent
+                anchors.rightMargin: 12
+                Item { Layout.fillWidth: true }
+                Rectangle {
+                    Layout.preferredWidth: 60; Layout.preferredHeight: 32
+                    color: "transparent"
+                    Text {
+                        anchors.centerIn: parent; text: "Cancel"
+                        color: urlCancelMouse.containsMouse ? _t.textPrimary : _t.textSecondary
+                        font.pixelSize: 13
+                    }
+                    MouseArea {
+                        id: urlCancelMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                        onClicked: posterUrlDialog.reject()
+                    }
+                }
+                Rectangle {
+                    Layout.preferredWidth: 70; Layout.preferredHeight: 32
+                    radius: 8
+                    color: urlOkMouse.containsMouse ? _t.accentHover : _t.accent
+                    Text {
+                        anchors.centerIn: parent; text: "Set"
+                        color: _t.textWhite; font.pixelSize: 13; font.bold: true
+                    }
+                    MouseArea {
+                        id: urlOkMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                        onClicked: posterUrlDialog.accept()
+                    }
+                }
+            }
+        }
+
+        onAccepted: {
+            if (posterUrlInput.text.trim() !== "")
+                controller.setPosterUrl(itemId, posterUrlInput.text.trim())
+        }
+    }
+
+    // ---- Notifications Dialog ----
+    Dialog {
+        id: notificationsDialog
+        modal: true
+        anchors.centerIn: parent
+        width: 520
+        height: Math.min(560, root.height - 80)
+
+        // Seeing the list counts as reading it
+        onOpened: notificationModel.markRead()
+
+        background: Rectangle {
+            color: _t.surfaceCard
+            border.color: _t.borderSubtle
+            radius: 12
+        }
+
+        header: Rectangle {
+            color: "transparent"
+            height: 44
+            Text {
+                anchors.left: parent.left
+                anchors.leftMargin: 16
+                anchors.verticalCenter: parent.verticalCenter
+                text: "Notifications"
+                color: _t.textPrimary
+                font.pixelSize: 15
+                font.bold: true
+            }
+        }
+
+        ListView {
+            id: notificationList
+            anchors.fill: parent
+            clip: true
+            spacing: 6
+            model: notificationModel
+            boundsBehavior: Flickable.StopAtBounds
+            ScrollBar.vertical: ScrollBar {}
+
+            delegate: Rectangle {
+                width: notificationList.width
+                height: notificationColumn.implicitHeight + 16
+                radius: 8
+                color: _t.surfaceDark
+
+                RowLayout {
+                    anchors.fill: parent
+                    anchors.margins: 8
+                    spacing: 10
+
+                    Rectangle {
+                        Layout.alignment: Qt.AlignTop
+                        Layout.topMargin: 5
+                        Layout.preferredWidth: 8
+                        Layout.preferredHeight: 8
+                        radius: 4
+                        color: model.toastType === "error" ? _t.danger :
+                               model.toastType === "warning" ? _t.warning :
+                               model.toastType === "success" ? _t.success : _t.accent
+                    }
+
+                    ColumnLayout {
+                        id: notificationColumn
+                        Layout.fillWidth: true
+                        spacing: 2
+
+                        Text {
+                            text: model.message
+                            color: _t.textPrimary
+                            font.pixelSize: 13
+                            wrapMode: Text.Wrap
+                            Layout.fillWidth: true
+                        }
+                        Text {
+                            text: model.details
+                            color: _t.textSecondary
+                            font.pixelSize: 12
+                            wrapMode: Text.Wrap
+                            Layout.fillWidth: true
+                            visible: text !== ""
+                        }
+                        Text {
+                            text: new Date(model.timestamp).toLocaleString(Qt.locale(), Locale.ShortFormat)
+                            color: _t.textMuted
+                            font.pixelSize: 11
+                        }
+                    }
+                }
+            }
+
+            Text {
+                anchors.centerIn: parent
+                visible: notificationList.count === 0
+                text: "No notifications"
+                color: _t.textMuted
+                font.pixelSize: 13
+            }
+        }
+
+        footer: Rectangle {
+            color: "transparent"
+            height: 52
+            RowLayout {
+                anchors.fill: parent
+                anchors.leftMargin: 12
+                anchors.rightMargin: 12
+                Rectangle {
+                    Layout.preferredWidth: 60; Layout.preferredHeight: 32
+                    color: "transparent"
+                    visible: notificationList.count > 0
+                    Text {
+                        anchors.centerIn: parent; text: "Clear"
+                        color: notificationsClearMouse.containsMouse ? _t.danger : _t.textSecondary
+                        font.pixelSize: 13
+                    }
+                    MouseArea {
+                        id: notificationsClearMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                        onClicked: notificationModel.clear()
+                    }
+                }
+                Item { Layout.fillWidth: true }
+                Rectangle {
+                    Layout.preferredWidth: 70; Layout.preferredHeight: 32
+                    radius: 8
+                    color: notificationsCloseMouse.containsMouse ? _t.accentHover : _t.accent
+                    Text {
+                        anchors.centerIn: parent; text: "Close"
+                        color: _t.textWhite; font.pixelSize: 13; font.bold: true
+                    }
+                    MouseArea {
+                        id: notificationsCloseMouse; anchors.fill: parent; hoverEnabled: true; cursorShape: Qt.PointingHandCursor
+                        onClicked: notificationsDialog.close()
+                    }
+                }
+            }
         }
     }
 
@@ -648,6 +1431,7 @@ ApplicationWindow {
         mediaModel: mediaModel
         activePage: root.activePage
         activeStatus: root.activeStatus
+        statusOptions: root.statusOptions
         onAboutToSave: captureScrollPosition()
     }
 
@@ -657,15 +1441,70 @@ ApplicationWindow {
         controller: controller
     }
 
+    // ---- Batch add progress ----
+    Rectangle {
+        id: batchProgress
+        anchors.horizontalCenter: parent.horizontalCenter
+        anchors.bottom: parent.bottom
+        anchors.bottomMargin: 16
+        width: 320
+        height: 52
+        radius: 8
+        z: 999
+        color: _t.surfaceElevated
+        border.color: _t.borderSubtle
+        visible: phase !== ""
+
+        property int done: 0
+        property int total: 0
+        property string phase: "" // "posters" or "saving"; empty when idle
+
+        ColumnLayout {
+            anchors.fill: parent
+            anchors.margins: 12
+            spacing: 6
+
+            Text {
+                text: (batchProgress.phase === "posters" ? "Downloading posters " : "Saving items ")
+                      + batchProgress.done + " / " + batchProgress.total
+                color: _t.textPrimary
+                font.pixelSize: 12
+            }
+            Rectangle {
+                Layout.fillWidth: true
+                Layout.preferredHeight: 4
+                radius: 2
+                color: _t.surfaceDark
+                Rectangle {
+                    width: batchProgress.total > 0 ? parent.width * batchProgress.done / batchProgress.total : 0
+                    height: parent.height
+                    radius: 2
+                    color: _t.accent
+                }
+            }
+        }
+    }
+
     // ---- Toast ----
     Toast { id: toast }
 
     // ---- Helper Functions ----
+    function refreshNavStatusCounts() {
+        var all = {}
+        var pages = ["Movie", "TV", "Anime", "Manga"]
+        for (var i = 0; i < pages.length; i++) {
+            all[pages[i]] = JSON.parse(controller.getStatusCounts(pages[i]) || "{}")
+        }
+        navStatusCounts = all
+    }
+
+    function statusBreakdown(page) {
+        var counts = navStatusCounts[page] || {}
+        return statusOptions.map(function(s) { return (counts[s] || 0) + " " + s }).join(", ")
+    }
+
     function statusCountFor(statusName) {
-        if (statusName === "On Drive") return controller.on_drive_count
-        if (statusName === "To Download") return controller.to_download_count
-        if (statusName === "To Work On") return controller.to_work_on_count
-        return 0
+        return statusCounts[statusName] || 0
     }
 
     function contextKey(page, status, mode) {
@@ -707,33 +1546,90 @@ ApplicationWindow {
     }
 
     function handleItemClick(row, modifiers) {
-        var id = mediaModel.getItemId(row)
-
         if (modifiers & Qt.ControlModifier) {
             // Ctrl+Click: toggle this item
-            var arr = selectedIds.slice()
-            var idx = arr.indexOf(id)
-            if (idx >= 0) {
-                arr.splice(idx, 1)
-            } else {
-                arr.push(id)
-            }
-            selectedIds = arr
+            mediaModel.toggleSelected(row)
             lastClickedRow = row
         } else if (modifiers & Qt.ShiftModifier) {
             // Shift+Click: range select from last clicked
             if (lastClickedRow < 0) lastClickedRow = 0
-            var start = Math.min(lastClickedRow, row)
-            var end = Math.max(lastClickedRow, row)
-            var rangeIds = []
-            for (var i = start; i <= end; i++) {
-                rangeIds.push(mediaModel.getItemId(i))
-            }
-            selectedIds = rangeIds
+            mediaModel.selectRange(lastClickedRow, row)
         } else {
             // Plain click: select only this item
-            selectedIds = [id]
-            lastClickedRow = row
+            selectOnly(row)
+        }
+    }
+
+    function selectOnly(row) {
+        mediaModel.clearSelection()
+        mediaModel.toggleSelected(row)
+        lastClickedRow = row
+    }
+
+    function isRowSelected(row) {
+        return mediaModel.data(mediaModel.index(row, 0), 282) || false
+    }
+
+    // Matches the status navigateTo picks for the page
+    function defaultStatusFor(page) {
+        return JSON.parse(controller.getDefaultStatuses())[page] || statusOptions[0]
+    }
+
+    function moveSelectedToStatusIndex(idx) {
+        if (mediaModel.selected_count === 0 || statusOptions[idx] === activeStatus) return
+        captureScrollPosition()
+        controller.moveItemsToStatusIndex(mediaModel.getSelectedIds(), idx)
+    }
+
+    function refreshQualityFilterOptions() {
+        var options = [{ quality: "", label: "All qualities" }]
+        var inUse = JSON.parse(controller.getQualityTypesInUse(activePage) || "[]")
+        for (var i = 0; i < inUse.length; i++) {
+            options.push({ quality: inUse[i].quality, label: inUse[i].quality + " (" + inUse[i].count + ")" })
+        }
+        qualityFilterOptions = options
+    }
+
+    function refreshSourceFilterOptions() {
+        var options = [{ source: "", label: "All sources" }]
+        var sources = controller.getDistinctSources()
+        if (sources !== "") {
+            var list = sources.split("\n")
+            for (var i = 0; i < list.length; i++) options.push({ source: list[i], label: list[i] })
+        }
+        sourceFilterOptions = options
+    }
+
+    function reloadMediaModel() {
+        if (recentView !== "")
+            mediaModel.loadRecent(controller.recent_limit, recentView === "updated")
+        else
+            mediaModel.reload(controller.viewQuery())
+    }
+
+    function refreshRecentItems() {
+        recentItems = JSON.parse(controller.getRecentItems() || "[]")
+    }
+
+    // Switch to the item's page and status, then open it for editing
+    function openRecentItem(item) {
+        captureScrollPosition()
+        searchTerm = ""
+        searchInput.text = ""
+        mediaModel.clearSelection()
+        lastClickedRow = -1
+        if (activePage !== item.media_type || recentView !== "") {
+            activePage = item.media_type
+            controller.navigateTo(item.media_type)
+        }
+        if (controller.favorites_only) controller.setFavoritesOnly(false)
+        activeStatus = item.status
+        controller.setStatus(item.status)
+
+        var row = mediaModel.rowForId(item.id)
+        if (row >= 0) {
+            selectOnly(row)
+            editDialog.openEdit(row)
         }
     }
 
@@ -744,12 +1640,9 @@ ApplicationWindow {
     function showContextMenu(row, mx, my) {
         // If right-clicked item is not in current selection, select it alone
         var id = mediaModel.getItemId(row)
-        if (selectedIds.indexOf(id) < 0) {
-            selectedIds = [id]
-            lastClickedRow = row
-        }
+        if (!isRowSelected(row)) selectOnly(row)
 
-        if (selectedIds.length > 1) {
+        if (mediaModel.selected_count > 1) {
             bulkContextMenu.popup()
         } else {
             contextMenu.targetRow = row
@@ -757,6 +1650,8 @@ ApplicationWindow {
             contextMenu.targetTitle = mediaModel.getItemTitle(row)
             contextMenu.targetRomajiTitle = mediaModel.getItemRomajiTitle(row)
             contextMenu.targetNativeTitle = mediaModel.getItemNativeTitle(row)
+            contextMenu.targetFavorite = mediaModel.data(mediaModel.index(row, 0), 273) || false
+            contextMenu.targetWatched = mediaModel.data(mediaModel.index(row, 0), 289) || false
             contextMenu.popup()
         }
     }
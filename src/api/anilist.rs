@@ -1,9 +1,9 @@
+use super::ApiError;
 use crate::models::SearchResult;
 use reqwest::Client;
 use serde_json::{json, Value};
 
-const ANILIST_URL: &str = "https://graphql.anilist.co";
-const MAX_RETRIES: u32 = 3;
+pub const ANILIST_URL: &str = "https://graphql.anilist.co";
 
 fn strip_html_tags(s: &str) -> String {
     let mut result = String::new();
@@ -19,6 +19,26 @@ fn strip_html_tags(s: &str) -> String {
     result
 }
 
+/// Readable form of an AniList enum value: "TV_SHORT" -> "TV Short",
+/// "WINTER" -> "Winter". Acronyms stay upper case.
+fn enum_label(value: &str) -> String {
+    value
+        .split('_')
+        .map(|word| match word {
+            "TV" | "OVA" | "ONA" => word.to_string(),
+            _ => {
+                let lower = word.to_lowercase();
+                let mut chars = lower.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn resolve_title(title: &Value) -> (String, Option<String>, Option<String>) {
     let english = title["english"].as_str().map(|s| s.to_string());
     let romaji = title["romaji"].as_str().map(|s| s.to_string());
@@ -36,39 +56,22 @@ async fn make_request(
     client: &Client,
     query: &str,
     variables: &Value,
-) -> Result<Value, String> {
+) -> Result<Value, ApiError> {
     let body = json!({
         "query": query,
         "variables": variables,
     });
 
-    for retry in 0..=MAX_RETRIES {
-        let resp = client
-            .post(ANILIST_URL)
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| format!("AniList request failed: {}", e))?;
-
-        if resp.status().as_u16() == 429 && retry < MAX_RETRIES {
-            let delay = 5 * (1 << retry); // 5s, 10s, 20s
-            tokio::time::sleep(std::time::Duration::from_secs(delay)).await;
-            continue;
-        }
-
-        if !resp.status().is_success() {
-            return Err(format!("AniList error: HTTP {}", resp.status()));
-        }
-
-        let data: Value = resp
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse AniList response: {}", e))?;
-
-        return Ok(data);
+    let resp = super::http::send_with_retry("AniList", client.post(ANILIST_URL).json(&body)).await?;
+    if !resp.status().is_success() {
+        tracing::warn!("AniList query failed: HTTP {}", resp.status());
+        return Err(ApiError::from_status(resp.status(), format!("AniList error: HTTP {}", resp.status())));
     }
 
-    Err("AniList: max retries exceeded".into())
+    resp.json().await.map_err(|e| {
+        tracing::warn!("Unreadable AniList response: {}", e);
+        ApiError::Parse(format!("Failed to parse AniList response: {}", e))
+    })
 }
 
 pub async fn search_anime(
@@ -76,7 +79,7 @@ pub async fn search_anime(
     query: &str,
     year: Option<i32>,
     include_adult: bool,
-) -> Result<Vec<SearchResult>, String> {
+) -> Result<Vec<SearchResult>, ApiError> {
     let gql = if !include_adult {
         r#"
             query ($search: String, $seasonYear: Int) {
@@ -89,7 +92,12 @@ pub async fn search_anime(
                             native
                         }
                         seasonYear
+                        season
+                        format
+                        episodes
                         description
+                        averageScore
+                        popularity
                         coverImage {
                             large
                         }
@@ -109,7 +117,12 @@ pub async fn search_anime(
                             native
                         }
                         seasonYear
+                        season
+                        format
+                        episodes
                         description
+                        averageScore
+                        popularity
                         coverImage {
                             large
                         }
@@ -144,9 +157,110 @@ pub async fn search_anime(
                 poster_url: m["coverImage"]["large"]
                     .as_str()
                     .map(|s| s.to_string()),
+                total_episodes: m["episodes"].as_i64().map(|n| n as i32),
+                // averageScore is out of 100
+                vote_average: m["averageScore"].as_f64().map(|s| s / 10.0),
+                popularity: m["popularity"].as_f64(),
+                vote_count: None,
+                anime_format: m["format"].as_str().map(enum_label),
+                season: m["season"].as_str().map(enum_label),
             }
         })
         .collect();
 
     Ok(results)
 }
+
+pub async fn search_manga(
+    client: &Client,
+    query: &str,
+    year: Option<i32>,
+    include_adult: bool,
+) -> Result<Vec<SearchResult>, ApiError> {
+    // Manga have no season; filter on start date (FuzzyDateInt, YYYYMMDD) instead.
+    // A null $isAdult leaves the filter off.
+    let gql = r#"
+        query ($search: String, $startAfter: FuzzyDateInt, $startBefore: FuzzyDateInt, $isAdult: Boolean) {
+            Page(page: 1, perPage: 50) {
+                media(search: $search, startDate_greater: $startAfter, startDate_lesser: $startBefore, type: MANGA, sort: SEARCH_MATCH, isAdult: $isAdult) {
+                    id
+                    title {
+                        english
+                        romaji
+                        native
+                    }
+                    startDate {
+                        year
+                    }
+                    chapters
+                    description
+                    averageScore
+                    popularity
+                    coverImage {
+                        large
+                    }
+                }
+            }
+        }
+    "#;
+
+    let mut variables = json!({ "search": query });
+    if let Some(y) = year {
+        variables["startAfter"] = json!(y * 10000);
+        variables["startBefore"] = json!((y + 1) * 10000);
+    }
+    if !include_adult {
+        variables["isAdult"] = json!(false);
+    }
+
+    let data = make_request(client, gql, &variables).await?;
+
+    let results = data["data"]["Page"]["media"]
+        .as_array()
+        .unwrap_or(&vec![])
+        .iter()
+        .map(|m| {
+            let (title, native_title, romaji_title) = resolve_title(&m["title"]);
+            SearchResult {
+                api_id: m["id"].as_i64().unwrap_or(0),
+                title,
+                native_title,
+                romaji_title,
+                year: m["startDate"]["year"].as_i64().map(|y| y as i32),
+                overview: m["description"]
+                    .as_str()
+                    .map(strip_html_tags),
+                poster_url: m["coverImage"]["large"]
+                    .as_str()
+                    .map(|s| s.to_string()),
+                total_episodes: m["chapters"].as_i64().map(|n| n as i32),
+                // averageScore is out of 100
+                vote_average: m["averageScore"].as_f64().map(|s| s / 10.0),
+                popularity: m["popularity"].as_f64(),
+                vote_count: None,
+                anime_format: None,
+                season: None,
+            }
+        })
+        .collect();
+
+    Ok(results)
+}
+
+/// Look up the current cover image for a single AniList entry.
+pub async fn get_cover_url(client: &Client, anilist_id: i64) -> Result<Option<String>, ApiError> {
+    let gql = r#"
+        query ($id: Int) {
+            Media(id: $id) {
+                coverImage {
+                    large
+                }
+            }
+        }
+    "#;
+
+    let data = make_request(client, gql, &json!({ "id": anilist_id })).await?;
+    Ok(data["data"]["Media"]["coverImage"]["large"]
+        .as_str()
+        .map(|s| s.to_string()))
+}
@@ -0,0 +1,43 @@
+//! Errors from the TMDB and AniList clients and poster downloads. Each
+//! variant carries the message shown to the user; the variant says what went
+//! wrong so callers can react to it (e.g. point at the API key on a 401).
+
+use reqwest::StatusCode;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error)]
+pub enum ApiError {
+    /// Couldn't reach the server, or it didn't answer in time
+    #[error("{0}")]
+    Network(String),
+    /// The API key was rejected (HTTP 401)
+    #[error("{0}")]
+    Unauthorized(String),
+    /// Still rate limited after retrying (HTTP 429)
+    #[error("{0}")]
+    RateLimited(String),
+    /// HTTP 404
+    #[error("{0}")]
+    NotFound(String),
+    /// Any other error status
+    #[error("{0}")]
+    Http(String),
+    /// The response wasn't what we expected, e.g. invalid JSON or not an image
+    #[error("{0}")]
+    Parse(String),
+    /// Saving a download to disk failed
+    #[error("{0}")]
+    Io(String),
+}
+
+impl ApiError {
+    /// The variant for an error status, with `message` as its text
+    pub fn from_status(status: StatusCode, message: String) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED => ApiError::Unauthorized(message),
+            StatusCode::TOO_MANY_REQUESTS => ApiError::RateLimited(message),
+            StatusCode::NOT_FOUND => ApiError::NotFound(message),
+            _ => ApiError::Http(message),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Sending requests with retries on rate limits and server errors, shared
+//! by the TMDB and AniList clients.
+
+use reqwest::header::RETRY_AFTER;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::time::Duration;
+
+use super::ApiError;
+
+/// Retries after a 429 before giving up
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+/// Retries after a 5xx; these rarely clear up quickly
+const MAX_SERVER_ERROR_RETRIES: u32 = 2;
+/// Longest Retry-After we wait out; a search shouldn't hang for minutes
+const MAX_RETRY_AFTER_SECS: u64 = 30;
+
+/// Send `request`, retrying 429s (after the Retry-After delay when given,
+/// else 5s, 10s, 20s) and 5xx responses (after 1s, 2s). The last response is
+/// returned whatever its status, so callers still check it. `provider`
+/// names the API in error messages.
+pub async fn send_with_retry(provider: &str, request: RequestBuilder) -> Result<Response, ApiError> {
+    let mut attempt = 0;
+    loop {
+        // Only streaming bodies can't be cloned, and none of ours are
+        let resp = request
+            .try_clone()
+            .ok_or_else(|| ApiError::Http(format!("{} request can't be retried", provider)))?
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::warn!("{} request failed: {}", provider, e);
+                ApiError::Network(format!("{} request failed: {}", provider, e))
+            })?;
+
+        let status = resp.status();
+        let delay = if status == StatusCode::TOO_MANY_REQUESTS && attempt < MAX_RATE_LIMIT_RETRIES {
+            retry_after(&resp).unwrap_or(Duration::from_secs(5 << attempt))
+        } else if status.is_server_error() && attempt < MAX_SERVER_ERROR_RETRIES {
+            Duration::from_secs(1 << attempt)
+        } else {
+            return Ok(resp);
+        };
+        tracing::info!("{} returned HTTP {}; retrying in {:?}", provider, status, delay);
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// The Retry-After header in seconds (the HTTP-date form isn't used by
+/// either API), capped at MAX_RETRY_AFTER_SECS
+fn retry_after(resp: &Response) -> Option<Duration> {
+    let secs: u64 = resp.headers().get(RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()?;
+    Some(Duration::from_secs(secs.min(MAX_RETRY_AFTER_SECS)))
+}
@@ -1,2 +1,89 @@
 pub mod anilist;
+mod error;
+mod http;
 pub mod tmdb;
+
+pub use error::ApiError;
+
+use reqwest::Client;
+use std::collections::HashSet;
+
+use crate::models::SearchResult;
+
+/// How long the connectivity probe waits before declaring us offline
+const CONNECTIVITY_TIMEOUT_SECS: u64 = 3;
+/// Overall timeout for any other request
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+
+/// HTTP client with the app's request timeout. Build one and clone it, so
+/// requests share a connection pool.
+pub fn new_client() -> Client {
+    Client::builder()
+        .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .unwrap_or_default()
+}
+
+/// Base URL of the provider that serves searches for `media_type`.
+pub fn provider_base_url(media_type: &str) -> &'static str {
+    if crate::models::is_anilist_type(media_type) {
+        anilist::ANILIST_URL
+    } else {
+        tmdb::BASE_URL
+    }
+}
+
+/// Drop results whose `api_id` already appeared earlier in the list (TMDB's
+/// two pages can overlap), keeping the first occurrence and the original order.
+pub fn dedupe_results(results: Vec<SearchResult>) -> Vec<SearchResult> {
+    let mut seen = HashSet::new();
+    results.into_iter().filter(|r| seen.insert(r.api_id)).collect()
+}
+
+/// Quick HEAD request to a provider. Any HTTP response counts as reachable
+/// (AniList answers HEAD with an error status); only connection failures and
+/// timeouts count as offline.
+pub async fn is_reachable(client: &Client, url: &str) -> bool {
+    client
+        .head(url)
+        .timeout(std::time::Duration::from_secs(CONNECTIVITY_TIMEOUT_SECS))
+        .send()
+        .await
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(api_id: i64, title: &str) -> SearchResult {
+        SearchResult {
+            api_id,
+            title: title.to_string(),
+            native_title: None,
+            romaji_title: None,
+            year: None,
+            overview: None,
+            poster_url: None,
+            total_episodes: None,
+            vote_average: None,
+            popularity: None,
+            vote_count: None,
+            anime_format: None,
+            season: None,
+        }
+    }
+
+    #[test]
+    fn dedupe_keeps_first_occurrence_in_order() {
+        let results = vec![
+            result(1, "Dune"),
+            result(2, "Dune: Part Two"),
+            result(1, "Dune (page 2)"),
+            result(3, "Dune (1984)"),
+            result(2, "Dune: Part Two (page 2)"),
+        ];
+        let titles: Vec<String> = dedupe_results(results).into_iter().map(|r| r.title).collect();
+        assert_eq!(titles, ["Dune", "Dune: Part Two", "Dune (1984)"]);
+    }
+}
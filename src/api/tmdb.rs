@@ -1,9 +1,122 @@
+use super::ApiError;
 use crate::models::SearchResult;
 use reqwest::Client;
 use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::OnceLock;
 
-const BASE_URL: &str = "https://api.themoviedb.org/3";
-const IMAGE_BASE_URL: &str = "https://image.tmdb.org/t/p/w500";
+pub const BASE_URL: &str = "https://api.themoviedb.org/3";
+const DEFAULT_IMAGE_BASE_URL: &str = "https://image.tmdb.org/t/p/";
+pub const DEFAULT_POSTER_SIZE: &str = "w500";
+/// Most search result pages fetched per query (20 results each)
+pub const MAX_RESULT_PAGES: u32 = 5;
+const DEFAULT_POSTER_SIZES: [&str; 7] = ["w92", "w154", "w185", "w342", "w500", "w780", "original"];
+
+/// Image base URL and poster sizes from `/configuration`.
+#[derive(Debug, Clone)]
+pub struct ImageConfig {
+    pub base_url: String,
+    pub poster_sizes: Vec<String>,
+}
+
+impl Default for ImageConfig {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_IMAGE_BASE_URL.to_string(),
+            poster_sizes: DEFAULT_POSTER_SIZES.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl ImageConfig {
+    /// `size` if TMDB offers it, otherwise the default size.
+    fn poster_size<'a>(&'a self, size: &'a str) -> &'a str {
+        if self.poster_sizes.iter().any(|s| s == size) {
+            size
+        } else if self.poster_sizes.iter().any(|s| s == DEFAULT_POSTER_SIZE) {
+            DEFAULT_POSTER_SIZE
+        } else {
+            self.poster_sizes.last().map(|s| s.as_str()).unwrap_or("original")
+        }
+    }
+
+    fn poster_url(&self, size: &str, path: Option<&str>) -> Option<String> {
+        path.map(|p| format!("{}{}{}", self.base_url, self.poster_size(size), p))
+    }
+}
+
+// Fetched once per run; a failed fetch isn't cached so the next search retries.
+static IMAGE_CONFIG: OnceLock<ImageConfig> = OnceLock::new();
+
+/// The image configuration fetched so far, or the built-in defaults.
+pub fn cached_image_config() -> ImageConfig {
+    IMAGE_CONFIG.get().cloned().unwrap_or_default()
+}
+
+async fn image_config(client: &Client, api_key: &str) -> ImageConfig {
+    if let Some(cfg) = IMAGE_CONFIG.get() {
+        return cfg.clone();
+    }
+    let params = [("api_key", api_key.to_string())];
+    let Ok((data, _)) = tmdb_search(client, "configuration", &params).await else {
+        return ImageConfig::default();
+    };
+
+    let base_url = data["images"]["secure_base_url"]
+        .as_str()
+        .unwrap_or(DEFAULT_IMAGE_BASE_URL)
+        .to_string();
+    let poster_sizes: Vec<String> = data["images"]["poster_sizes"]
+        .as_array()
+        .map(|a| a.iter().filter_map(|s| s.as_str().map(|s| s.to_string())).collect())
+        .unwrap_or_default();
+    let cfg = if poster_sizes.is_empty() {
+        ImageConfig { base_url, ..ImageConfig::default() }
+    } else {
+        ImageConfig { base_url, poster_sizes }
+    };
+    IMAGE_CONFIG.get_or_init(|| cfg).clone()
+}
+
+/// Check an API key against `/configuration`. The error says whether TMDB
+/// rejected the key or couldn't be reached.
+pub async fn test_api_key(client: &Client, api_key: &str) -> Result<(), ApiError> {
+    let resp = client
+        .get(format!("{}/configuration", BASE_URL))
+        .query(&[("api_key", api_key)])
+        .send()
+        .await
+        .map_err(|e| {
+            ApiError::Network(if e.is_timeout() {
+                "TMDB didn't respond in time".to_string()
+            } else {
+                format!("Couldn't reach TMDB: {}", e)
+            })
+        })?;
+
+    match resp.status() {
+        status if status.is_success() => Ok(()),
+        reqwest::StatusCode::UNAUTHORIZED => {
+            Err(ApiError::Unauthorized("TMDB rejected the key (HTTP 401)".to_string()))
+        }
+        status => Err(ApiError::from_status(status, format!("TMDB error: HTTP {}", status))),
+    }
+}
+
+/// Language and region sent with searches
+#[derive(Debug, Clone)]
+pub struct Locale {
+    pub language: String, // e.g. "en-US", "ja-JP"
+    pub region: String,   // ISO 3166-1 code, empty for none
+}
+
+/// The localized field, or the original-language one when TMDB has no translation
+fn localized_title(r: &Value, field: &str, original_field: &str) -> String {
+    match r[field].as_str().map(str::trim) {
+        Some(title) if !title.is_empty() => title.to_string(),
+        _ => r[original_field].as_str().unwrap_or("").to_string(),
+    }
+}
 
 fn extract_year(date_str: &str) -> Option<i32> {
     if date_str.len() >= 4 {
@@ -13,40 +126,48 @@ fn extract_year(date_str: &str) -> Option<i32> {
     }
 }
 
-fn poster_url(path: Option<&str>) -> Option<String> {
-    path.map(|p| format!("{}{}", IMAGE_BASE_URL, p))
-}
-
-fn parse_movie_results(data: &Value) -> Vec<SearchResult> {
+fn parse_movie_results(data: &Value, images: &ImageConfig, poster_size: &str) -> Vec<SearchResult> {
     data["results"]
         .as_array()
         .unwrap_or(&vec![])
         .iter()
         .map(|r| SearchResult {
             api_id: r["id"].as_i64().unwrap_or(0),
-            title: r["title"].as_str().unwrap_or("").to_string(),
+            title: localized_title(r, "title", "original_title"),
             native_title: None,
             romaji_title: None,
             year: r["release_date"].as_str().and_then(|d| extract_year(d)),
             overview: r["overview"].as_str().map(|s| s.to_string()),
-            poster_url: poster_url(r["poster_path"].as_str()),
+            poster_url: images.poster_url(poster_size, r["poster_path"].as_str()),
+            total_episodes: None,
+            vote_average: r["vote_average"].as_f64().filter(|v| *v > 0.0),
+            popularity: r["popularity"].as_f64(),
+            vote_count: r["vote_count"].as_i64(),
+            anime_format: None,
+            season: None,
         })
         .collect()
 }
 
-fn parse_tv_results(data: &Value) -> Vec<SearchResult> {
+fn parse_tv_results(data: &Value, images: &ImageConfig, poster_size: &str) -> Vec<SearchResult> {
     data["results"]
         .as_array()
         .unwrap_or(&vec![])
         .iter()
         .map(|r| SearchResult {
             api_id: r["id"].as_i64().unwrap_or(0),
-            title: r["name"].as_str().unwrap_or("").to_string(),
+            title: localized_title(r, "name", "original_name"),
             native_title: None,
             romaji_title: None,
             year: r["first_air_date"].as_str().and_then(|d| extract_year(d)),
             overview: r["overview"].as_str().map(|s| s.to_string()),
-            poster_url: poster_url(r["poster_path"].as_str()),
+            poster_url: images.poster_url(poster_size, r["poster_path"].as_str()),
+            total_episodes: None,
+            vote_average: r["vote_average"].as_f64().filter(|v| *v > 0.0),
+            popularity: r["popularity"].as_f64(),
+            vote_count: r["vote_count"].as_i64(),
+            anime_format: None,
+            season: None,
         })
         .collect()
 }
@@ -55,95 +176,163 @@ async fn tmdb_search(
     client: &Client,
     endpoint: &str,
     params: &[(&str, String)],
-) -> Result<(Value, i64), String> {
-    let resp = client
-        .get(&format!("{}/{}", BASE_URL, endpoint))
-        .query(params)
-        .send()
-        .await
-        .map_err(|e| format!("TMDB request failed: {}", e))?;
+) -> Result<(Value, i64), ApiError> {
+    let request = client.get(format!("{}/{}", BASE_URL, endpoint)).query(params);
+    let resp = super::http::send_with_retry("TMDB", request).await?;
 
     if !resp.status().is_success() {
-        return Err(format!("TMDB error: HTTP {}", resp.status()));
+        tracing::warn!("TMDB {} failed: HTTP {}", endpoint, resp.status());
+        return Err(ApiError::from_status(resp.status(), format!("TMDB error: HTTP {}", resp.status())));
     }
 
-    let data: Value = resp
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse TMDB response: {}", e))?;
+    let data: Value = resp.json().await.map_err(|e| {
+        tracing::warn!("Unreadable TMDB {} response: {}", endpoint, e);
+        ApiError::Parse(format!("Failed to parse TMDB response: {}", e))
+    })?;
 
     let total_pages = data["total_pages"].as_i64().unwrap_or(1);
     Ok((data, total_pages))
 }
 
+/// Fetch up to `pages` result pages (clamped to 1..=MAX_RESULT_PAGES),
+/// stopping early at TMDB's `total_pages`. Only a failed first page is an
+/// error; a later one just ends the list. Results are deduped by id, since
+/// TMDB's paging can repeat an entry when its ranking shifts between requests.
+async fn search_pages(
+    client: &Client,
+    endpoint: &str,
+    params: &[(&str, String)],
+    pages: u32,
+    parse: impl Fn(&Value) -> Vec<SearchResult>,
+) -> Result<Vec<SearchResult>, ApiError> {
+    let pages = pages.clamp(1, MAX_RESULT_PAGES) as i64;
+    let mut results = Vec::new();
+    let mut seen = HashSet::new();
+    let mut total_pages = 1;
+    let mut page = 1;
+    while page <= pages.min(total_pages) {
+        let mut page_params = params.to_vec();
+        page_params.push(("page", page.to_string()));
+        let data = match tmdb_search(client, endpoint, &page_params).await {
+            Ok((data, total)) => {
+                total_pages = total;
+                data
+            }
+            Err(e) if page == 1 => return Err(e),
+            Err(_) => break,
+        };
+        results.extend(parse(&data).into_iter().filter(|r| seen.insert(r.api_id)));
+        page += 1;
+    }
+    Ok(results)
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn search_movie(
     client: &Client,
     api_key: &str,
     query: &str,
     year: Option<i32>,
     include_adult: bool,
-) -> Result<Vec<SearchResult>, String> {
+    poster_size: &str,
+    locale: &Locale,
+    pages: u32,
+) -> Result<Vec<SearchResult>, ApiError> {
+    let images = image_config(client, api_key).await;
     let mut base_params = vec![
         ("api_key", api_key.to_string()),
         ("query", query.to_string()),
-        ("language", "en-US".to_string()),
+        ("language", locale.language.clone()),
         ("include_adult", include_adult.to_string()),
-        ("page", "1".to_string()),
     ];
     if let Some(y) = year {
         base_params.push(("year", y.to_string()));
     }
-
-    // Fetch page 1
-    let (data1, total_pages) = tmdb_search(client, "search/movie", &base_params).await?;
-    let mut results = parse_movie_results(&data1);
-
-    // Fetch page 2 if available
-    if total_pages > 1 {
-        let mut params2 = base_params.clone();
-        for p in params2.iter_mut() {
-            if p.0 == "page" { p.1 = "2".to_string(); }
-        }
-        if let Ok((data2, _)) = tmdb_search(client, "search/movie", &params2).await {
-            results.extend(parse_movie_results(&data2));
-        }
+    // Release dates (and the year filter) follow this region's releases
+    if !locale.region.is_empty() {
+        base_params.push(("region", locale.region.clone()));
     }
 
-    Ok(results)
+    search_pages(client, "search/movie", &base_params, pages, |data| {
+        parse_movie_results(data, &images, poster_size)
+    })
+    .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn search_tv(
     client: &Client,
     api_key: &str,
     query: &str,
     year: Option<i32>,
     include_adult: bool,
-) -> Result<Vec<SearchResult>, String> {
+    poster_size: &str,
+    locale: &Locale,
+    pages: u32,
+) -> Result<Vec<SearchResult>, ApiError> {
+    let images = image_config(client, api_key).await;
     let mut base_params = vec![
         ("api_key", api_key.to_string()),
         ("query", query.to_string()),
-        ("language", "en-US".to_string()),
+        ("language", locale.language.clone()),
         ("include_adult", include_adult.to_string()),
-        ("page", "1".to_string()),
     ];
     if let Some(y) = year {
         base_params.push(("first_air_date_year", y.to_string()));
     }
 
-    // Fetch page 1
-    let (data1, total_pages) = tmdb_search(client, "search/tv", &base_params).await?;
-    let mut results = parse_tv_results(&data1);
+    search_pages(client, "search/tv", &base_params, pages, |data| {
+        parse_tv_results(data, &images, poster_size)
+    })
+    .await
+}
 
-    // Fetch page 2 if available
-    if total_pages > 1 {
-        let mut params2 = base_params.clone();
-        for p in params2.iter_mut() {
-            if p.0 == "page" { p.1 = "2".to_string(); }
-        }
-        if let Ok((data2, _)) = tmdb_search(client, "search/tv", &params2).await {
-            results.extend(parse_tv_results(&data2));
-        }
-    }
+/// Fetch season and episode counts from the TV details endpoint.
+pub async fn get_tv_details(
+    client: &Client,
+    api_key: &str,
+    tmdb_id: i64,
+) -> Result<(Option<i32>, Option<i32>), ApiError> {
+    let params = [
+        ("api_key", api_key.to_string()),
+        ("language", "en-US".to_string()),
+    ];
+    let (data, _) = tmdb_search(client, &format!("tv/{}", tmdb_id), &params).await?;
 
-    Ok(results)
+    let seasons = data["number_of_seasons"].as_i64().map(|n| n as i32);
+    let episodes = data["number_of_episodes"].as_i64().map(|n| n as i32);
+    Ok((seasons, episodes))
+}
+
+/// IMDb id ("tt...") for a movie or TV show, if TMDB knows one. `kind` is
+/// "movie" or "tv".
+pub async fn get_imdb_id(
+    client: &Client,
+    api_key: &str,
+    kind: &str,
+    tmdb_id: i64,
+) -> Result<Option<String>, ApiError> {
+    let params = [("api_key", api_key.to_string())];
+    let (data, _) = tmdb_search(client, &format!("{}/{}/external_ids", kind, tmdb_id), &params).await?;
+    Ok(data["imdb_id"]
+        .as_str()
+        .filter(|id| id.starts_with("tt"))
+        .map(|id| id.to_string()))
+}
+
+/// Look up the current poster for a movie or TV show. `kind` is "movie" or "tv".
+pub async fn get_poster_url(
+    client: &Client,
+    api_key: &str,
+    kind: &str,
+    tmdb_id: i64,
+    poster_size: &str,
+) -> Result<Option<String>, ApiError> {
+    let images = image_config(client, api_key).await;
+    let params = [
+        ("api_key", api_key.to_string()),
+        ("language", "en-US".to_string()),
+    ];
+    let (data, _) = tmdb_search(client, &format!("{}/{}", kind, tmdb_id), &params).await?;
+    Ok(images.poster_url(poster_size, data["poster_path"].as_str()))
 }
@@ -12,20 +12,42 @@ pub mod qobject {
         #[qproperty(QString, active_status)]
         #[qproperty(QString, view_mode)]
         #[qproperty(QString, search_term)]
+        #[qproperty(bool, favorites_only)] // ignores active_status when set
+        #[qproperty(QString, quality_filter)] // empty = any quality
+        #[qproperty(QString, source_filter)] // empty = any source
+        #[qproperty(i32, min_year)] // 0 = unbounded
+        #[qproperty(i32, max_year)] // 0 = unbounded
+        #[qproperty(bool, missing_poster)] // only items without a usable poster
+        #[qproperty(bool, incomplete_only)] // only items missing year, quality or source
+        #[qproperty(QString, watched_filter)] // "watched"/"unwatched", empty = both
+        #[qproperty(QString, recent_view)] // "added"/"updated" while showing recent items across types, else ""
+        #[qproperty(i32, recent_limit)] // rows the recent view shows
         #[qproperty(bool, loading)]
         #[qproperty(i32, movie_count)]
         #[qproperty(i32, tv_count)]
         #[qproperty(i32, anime_count)]
+        #[qproperty(i32, manga_count)]
         #[qproperty(i32, item_count)]
-        #[qproperty(i32, on_drive_count)]
-        #[qproperty(i32, to_download_count)]
-        #[qproperty(i32, to_work_on_count)]
+        #[qproperty(QString, status_counts)] // JSON object: status -> count for the active page
         #[qproperty(QString, sort_field)]
         #[qproperty(QString, sort_dir)]
+        #[qproperty(QString, sort_field2)] // tie-breaker for sort_field
+        #[qproperty(QString, sort_dir2)]
         #[qproperty(i32, row_height)]
         // Settings
         #[qproperty(QString, tmdb_api_key)]
+        #[qproperty(bool, use_keyring)] // keep the API key in the system keyring
         #[qproperty(bool, include_adult)]
+        #[qproperty(QString, poster_size)]
+        #[qproperty(QString, tmdb_language)]
+        #[qproperty(QString, tmdb_region)]
+        #[qproperty(i32, tmdb_result_pages)]
+        #[qproperty(bool, idle_maintenance)]
+        #[qproperty(bool, ignore_articles)] // sort titles without a leading "The"/"A"/"An"
+        #[qproperty(bool, prefetch_search_posters)]
+        #[qproperty(bool, uniform_posters)] // grid shows posters framed to 2:3
+        #[qproperty(bool, refetching_posters)]
+        #[qproperty(bool, online)] // result of the last connectivity check before a search
         type AppController = super::AppControllerRust;
 
         // Navigation
@@ -33,6 +55,12 @@ pub mod qobject {
         #[cxx_name = "navigateTo"]
         fn navigate_to(self: Pin<&mut Self>, page: &QString);
 
+        /// Switch to the newest items across all media types, by creation
+        /// or (with `by_updated`) last update. navigateTo leaves the view.
+        #[qinvokable]
+        #[cxx_name = "showRecent"]
+        fn show_recent(self: Pin<&mut Self>, by_updated: bool);
+
         #[qinvokable]
         #[cxx_name = "setStatus"]
         fn set_status_filter(self: Pin<&mut Self>, status: &QString);
@@ -41,6 +69,64 @@ pub mod qobject {
         #[cxx_name = "setSearchTerm"]
         fn set_search_term_filter(self: Pin<&mut Self>, term: &QString);
 
+        /// Apply the search term right away instead of after the typing pause
+        #[qinvokable]
+        #[cxx_name = "searchNow"]
+        fn search_now(self: Pin<&mut Self>);
+
+        #[qinvokable]
+        #[cxx_name = "setFavoritesOnly"]
+        fn set_favorites_only_filter(self: Pin<&mut Self>, enabled: bool);
+
+        #[qinvokable]
+        /// Only list items whose poster is unset or whose cached file is gone
+        #[cxx_name = "showMissingPosters"]
+        fn show_missing_posters(self: Pin<&mut Self>, enabled: bool);
+
+        #[qinvokable]
+        #[cxx_name = "setIncompleteFilter"]
+        fn set_incomplete_filter(self: Pin<&mut Self>, enabled: bool);
+
+        #[qinvokable]
+        #[cxx_name = "setWatchedFilter"]
+        fn set_watched_filter_value(self: Pin<&mut Self>, value: &QString);
+
+        #[qinvokable]
+        #[cxx_name = "setQualityFilter"]
+        fn set_quality_filter_value(self: Pin<&mut Self>, quality: &QString);
+
+        #[qinvokable]
+        #[cxx_name = "setSourceFilter"]
+        fn set_source_filter_value(self: Pin<&mut Self>, source: &QString);
+
+        /// Inclusive year range; 0 leaves that side open. Items without a
+        /// year are hidden while either bound is set.
+        #[qinvokable]
+        #[cxx_name = "setYearRange"]
+        fn set_year_range(self: Pin<&mut Self>, min_year: i32, max_year: i32);
+
+        /// Replace every list filter at once from a JSON object, e.g.
+        /// {"status":"On Drive","min_year":1990,"has_poster":false}
+        #[qinvokable]
+        #[cxx_name = "setAdvancedFilter"]
+        fn set_advanced_filter(self: Pin<&mut Self>, json: &QString);
+
+        /// The library view (page, status tab, search and every filter and
+        /// sort setting) as JSON for MediaModel.reload/applyChanges
+        #[qinvokable]
+        #[cxx_name = "viewQuery"]
+        fn view_query(&self) -> QString;
+
+        /// Newline-separated non-empty sources used anywhere in the library
+        #[qinvokable]
+        #[cxx_name = "getDistinctSources"]
+        fn get_distinct_sources(&self) -> QString;
+
+        /// JSON array of {"quality", "count"} for quality types used on a page
+        #[qinvokable]
+        #[cxx_name = "getQualityTypesInUse"]
+        fn get_quality_types_in_use(&self, media_type: &QString) -> QString;
+
         #[qinvokable]
         #[cxx_name = "setViewMode"]
         fn set_view_mode_pref(self: Pin<&mut Self>, mode: &QString);
@@ -60,38 +146,217 @@ pub mod qobject {
             source: &QString,
             notes: &QString,
             poster_url: &QString,
+            progress: i32,       // -1 for none
+            total_episodes: i32, // 0 for unknown
+            acquired_date: &QString, // YYYY-MM-DD or empty
+            watched_date: &QString,  // YYYY-MM-DD or empty
         );
 
+        /// Image source for the poster field: remote URLs as is, local paths
+        /// as file:// URLs
+        #[qinvokable]
+        #[cxx_name = "posterPreviewUrl"]
+        fn poster_preview_url(&self, value: &QString) -> QString;
+
+        /// Local path behind a file:// URL, for showing in the poster field
+        #[qinvokable]
+        #[cxx_name = "posterPathFromUrl"]
+        fn poster_path_from_url(&self, url: &QString) -> QString;
+
         #[qinvokable]
         #[cxx_name = "deleteItems"]
         fn delete_items(self: Pin<&mut Self>, ids: &QString); // comma-separated
 
+        /// `{"count": n, "titles": [...]}` for the items `deleteItems` would
+        /// remove, so the confirmation can list them
+        #[qinvokable]
+        #[cxx_name = "previewDelete"]
+        fn preview_delete(&self, ids: &QString) -> QString;
+
+        #[qinvokable]
+        #[cxx_name = "toggleFavorite"]
+        fn toggle_favorite(self: Pin<&mut Self>, id: i32);
+
+        /// Add a copy of an item, e.g. to track a second copy at another quality
+        #[qinvokable]
+        #[cxx_name = "duplicateItem"]
+        fn duplicate_item(self: Pin<&mut Self>, id: i32);
+
+        /// Give an item its id on the other provider ("tmdb" or "anilist")
+        /// without touching the one it already has
+        #[qinvokable]
+        #[cxx_name = "linkExternalId"]
+        fn link_external_id(self: Pin<&mut Self>, id: i32, provider: &QString, external_id: i32);
+
+        /// Remember that an item was opened, for the "Jump back in" row
+        #[qinvokable]
+        #[cxx_name = "recordView"]
+        fn record_view(self: Pin<&mut Self>, id: i32);
+
+        /// JSON array of recently opened items, most recent first
+        #[qinvokable]
+        #[cxx_name = "getRecentItems"]
+        fn get_recent_items(&self) -> QString;
+
+        /// Web page for an item: IMDb when its id is known, else TMDB or
+        /// AniList. Empty when it has no external id.
+        #[qinvokable]
+        #[cxx_name = "getExternalUrl"]
+        fn get_external_url(&self, id: i32) -> QString;
+
+        /// Items as "Title (Year)" lines, or a Markdown list when `format` is
+        /// "markdown". `alt_titles` adds romaji/native titles for Anime/Manga.
+        #[qinvokable]
+        #[cxx_name = "getSelectedAsText"]
+        fn get_selected_as_text(&self, ids: &QString, format: &QString, alt_titles: bool) -> QString;
+
+        /// Write the items the library view currently shows (page, status,
+        /// search, filters and sort) to a CSV file. `path` may be a file:// URL.
+        #[qinvokable]
+        #[cxx_name = "exportCurrentView"]
+        fn export_current_view(self: Pin<&mut Self>, path: &QString);
+
         #[qinvokable]
         #[cxx_name = "moveItems"]
         fn move_items(self: Pin<&mut Self>, ids: &QString, new_status: &QString);
 
+        /// Move items to the status at `index` in `getStatusOptions()` order
+        #[qinvokable]
+        #[cxx_name = "moveItemsToStatusIndex"]
+        fn move_items_to_status_index(self: Pin<&mut Self>, ids: &QString, index: i32);
+
+        /// Move each item one status further along the configured order
+        #[qinvokable]
+        #[cxx_name = "advanceStatus"]
+        fn advance_status(self: Pin<&mut Self>, ids: &QString);
+
+        /// Mark items watched or unwatched; separate from their status
+        #[qinvokable]
+        #[cxx_name = "setWatched"]
+        fn set_watched(self: Pin<&mut Self>, ids: &QString, watched: bool);
+
         // Online search
         #[qinvokable]
         #[cxx_name = "searchOnline"]
         fn search_online(self: Pin<&mut Self>, query: &QString, year: i32);
 
+        /// Abandon the search in flight; its results are never shown
+        #[qinvokable]
+        #[cxx_name = "cancelSearch"]
+        fn cancel_search(self: Pin<&mut Self>);
+
+        /// JSON array of recent search queries for a media type, newest first
+        #[qinvokable]
+        #[cxx_name = "getSearchHistory"]
+        fn get_search_history(&self, media_type: &QString) -> QString;
+
+        #[qinvokable]
+        #[cxx_name = "clearSearchHistory"]
+        fn clear_search_history(self: Pin<&mut Self>);
+
+        /// Add results (comma-separated indices) with `status`, or the media
+        /// type's default status when empty. Results already in the library
+        /// are skipped unless `allow_duplicates` is set.
         #[qinvokable]
         #[cxx_name = "addSearchResults"]
-        fn add_search_results(self: Pin<&mut Self>, indices: &QString); // comma-separated
+        fn add_search_results(self: Pin<&mut Self>, indices: &QString, status: &QString, allow_duplicates: bool);
+
+        /// Add one result with some fields replaced first. JSON object keys:
+        /// title, year, status, quality_type, source, notes
+        #[qinvokable]
+        #[cxx_name = "addSearchResultWithDetails"]
+        fn add_search_result_with_details(self: Pin<&mut Self>, index: i32, overrides: &QString, allow_duplicates: bool);
+
+        /// The last add's BatchAddResult as JSON (added, skipped and failed
+        /// items, with why each was skipped); `{}` before the first
+        #[qinvokable]
+        #[cxx_name = "getLastBatchReport"]
+        fn get_last_batch_report(&self) -> QString;
+
+        // Posters
+        #[qinvokable]
+        #[cxx_name = "refetchPoster"]
+        fn refetch_poster(self: Pin<&mut Self>, id: i32);
+
+        /// Download and cache a poster from `url` in place of the item's
+        /// current one. The row's posterSource role reads "manual" afterwards
+        /// until a provider poster replaces it.
+        #[qinvokable]
+        #[cxx_name = "setPosterUrl"]
+        fn set_poster_url(self: Pin<&mut Self>, id: i32, url: &QString);
+
+        #[qinvokable]
+        #[cxx_name = "refetchMissingPosters"]
+        fn refetch_missing_posters(self: Pin<&mut Self>);
+
+        #[qinvokable]
+        #[cxx_name = "cancelPosterRefetch"]
+        fn cancel_poster_refetch(self: Pin<&mut Self>);
+
+        /// Newest log file under data/logs, or "" if nothing has been logged
+        #[qinvokable]
+        #[cxx_name = "getLogFilePath"]
+        fn get_log_file_path(&self) -> QString;
+
+        /// Computes image cache stats on a worker thread; result arrives via cacheStatsReady
+        #[qinvokable]
+        #[cxx_name = "getCacheStats"]
+        fn get_cache_stats(self: Pin<&mut Self>);
+
+        #[qinvokable]
+        #[cxx_name = "clearImageCache"]
+        fn clear_image_cache(self: Pin<&mut Self>, orphans_only: bool);
+
+        /// Trim stray whitespace from titles, optionally title-casing them too
+        #[qinvokable]
+        #[cxx_name = "normalizeTitles"]
+        fn normalize_titles(self: Pin<&mut Self>, title_case: bool);
 
         // Settings
         #[qinvokable]
         #[cxx_name = "saveSettings"]
-        fn save_settings(self: Pin<&mut Self>, api_key: &QString, include_adult: bool, quality_types: &QString);
+        fn save_settings(self: Pin<&mut Self>, api_key: &QString, use_keyring: bool, include_adult: bool, quality_types: &QString);
+
+        /// Check a TMDB API key without saving it; result arrives via apiKeyTested
+        #[qinvokable]
+        #[cxx_name = "testApiKey"]
+        fn test_api_key(self: Pin<&mut Self>, api_key: &QString);
 
         #[qinvokable]
         #[cxx_name = "getQualityTypes"]
         fn get_quality_types(&self) -> QString;
 
+        /// JSON array of {name, count, unlisted}: configured types first, then
+        /// ones items use that aren't in the config
+        #[qinvokable]
+        #[cxx_name = "getQualityTypesWithCounts"]
+        fn get_quality_types_with_counts(&self) -> QString;
+
+        /// Rename a quality type and retag its items. Returns false if refused.
+        #[qinvokable]
+        #[cxx_name = "renameQualityType"]
+        fn rename_quality_type(self: Pin<&mut Self>, old_name: &QString, new_name: &QString) -> bool;
+
         #[qinvokable]
         #[cxx_name = "getStatusOptions"]
         fn get_status_options(&self) -> QString;
 
+        /// JSON object of status -> item count for one media type
+        #[qinvokable]
+        #[cxx_name = "getStatusCounts"]
+        fn get_status_counts_json(&self, media_type: &QString) -> QString;
+
+        /// Replace the status list (add, remove, reorder). Renames go through
+        /// renameStatus; removing a status items still use is refused.
+        #[qinvokable]
+        #[cxx_name = "saveStatuses"]
+        fn save_statuses(self: Pin<&mut Self>, csv: &QString); // comma-separated
+
+        /// Rename one status and move its items along
+        #[qinvokable]
+        #[cxx_name = "renameStatus"]
+        fn rename_status(self: Pin<&mut Self>, old_name: &QString, new_name: &QString);
+
         /// Load saved config values into controller properties (call on startup)
         #[qinvokable]
         #[cxx_name = "loadConfig"]
@@ -101,27 +366,103 @@ pub mod qobject {
         #[cxx_name = "setSortOrder"]
         fn set_sort_order(self: Pin<&mut Self>, field: &QString, dir: &QString);
 
+        #[qinvokable]
+        #[cxx_name = "setSortOrder2"]
+        fn set_sort_order2(self: Pin<&mut Self>, field: &QString, dir: &QString);
+
         #[qinvokable]
         #[cxx_name = "setRowHeight"]
         fn set_row_height_pref(self: Pin<&mut Self>, height: i32);
 
+        #[qinvokable]
+        #[cxx_name = "setPosterSize"]
+        fn set_poster_size_pref(self: Pin<&mut Self>, size: &QString);
+
+        /// Language ("en-US") and optional region ("DE") for TMDB searches
+        #[qinvokable]
+        #[cxx_name = "setTmdbLocale"]
+        fn set_tmdb_locale_pref(self: Pin<&mut Self>, language: &QString, region: &QString);
+
+        /// How many TMDB result pages a search fetches (1-5)
+        #[qinvokable]
+        #[cxx_name = "setTmdbResultPages"]
+        fn set_tmdb_result_pages_pref(self: Pin<&mut Self>, pages: i32);
+
+        /// Newline-separated TMDB poster sizes, smallest first
+        #[qinvokable]
+        #[cxx_name = "getPosterSizes"]
+        fn get_poster_sizes(&self) -> QString;
+
+        // Maintenance
+        #[qinvokable]
+        #[cxx_name = "setIdleMaintenance"]
+        fn set_idle_maintenance_pref(self: Pin<&mut Self>, enabled: bool);
+
+        #[qinvokable]
+        #[cxx_name = "setIgnoreArticles"]
+        fn set_ignore_articles_pref(self: Pin<&mut Self>, enabled: bool);
+
+        #[qinvokable]
+        #[cxx_name = "setPrefetchSearchPosters"]
+        fn set_prefetch_search_posters_pref(self: Pin<&mut Self>, enabled: bool);
+
+        #[qinvokable]
+        #[cxx_name = "setUniformPosters"]
+        fn set_uniform_posters_pref(self: Pin<&mut Self>, enabled: bool);
+
+        /// JSON object of media type -> default status (every type included)
+        #[qinvokable]
+        #[cxx_name = "getDefaultStatuses"]
+        fn get_default_statuses(&self) -> QString;
+
+        /// Set the status a media type starts in; empty clears it
+        #[qinvokable]
+        #[cxx_name = "setDefaultStatus"]
+        fn set_default_status(self: Pin<&mut Self>, media_type: &QString, status: &QString);
+
+        /// JSON array of task names waiting for the next idle period
+        #[qinvokable]
+        #[cxx_name = "getMaintenanceQueue"]
+        fn get_maintenance_queue(&self) -> QString;
+
         // Signals
         #[qsignal]
         #[cxx_name = "itemsChanged"]
         fn items_changed(self: Pin<&mut Self>);
 
+        /// Only these items (comma-separated ids) were added, edited or
+        /// deleted; MediaModel can patch its rows instead of reloading
+        #[qsignal]
+        #[cxx_name = "itemsPatched"]
+        fn items_patched(self: Pin<&mut Self>, ids: QString);
+
         #[qsignal]
         #[cxx_name = "searchResultsReady"]
         fn search_results_ready(self: Pin<&mut Self>);
 
+        #[qsignal]
+        #[cxx_name = "recentItemsChanged"]
+        fn recent_items_changed(self: Pin<&mut Self>);
+
+        /// A prefetched poster for search result `index` is ready
+        #[qsignal]
+        #[cxx_name = "searchPostersUpdated"]
+        fn search_posters_updated(self: Pin<&mut Self>, index: i32);
+
         #[qsignal]
         #[cxx_name = "searchingChanged"]
         fn searching_changed(self: Pin<&mut Self>, searching: bool);
 
+        /// Emitted via show_toast, which also records it for NotificationModel
         #[qsignal]
         #[cxx_name = "toastMessage"]
         fn toast_message(self: Pin<&mut Self>, message: QString, toast_type: QString);
 
+        /// The notification history changed; NotificationModel should reload
+        #[qsignal]
+        #[cxx_name = "notificationsChanged"]
+        fn notifications_changed(self: Pin<&mut Self>);
+
         #[qsignal]
         #[cxx_name = "countsChanged"]
         fn counts_changed(self: Pin<&mut Self>);
@@ -129,6 +470,34 @@ pub mod qobject {
         #[qsignal]
         #[cxx_name = "settingsLoaded"]
         fn settings_loaded(self: Pin<&mut Self>);
+
+        #[qsignal]
+        #[cxx_name = "statusesChanged"]
+        fn statuses_changed(self: Pin<&mut Self>);
+
+        #[qsignal]
+        #[cxx_name = "apiKeyTested"]
+        fn api_key_tested(self: Pin<&mut Self>, valid: bool, message: QString);
+
+        /// saveSettings kept removed quality types that items still use.
+        /// JSON object of quality type -> item count.
+        #[qsignal]
+        #[cxx_name = "qualityTypesInUse"]
+        fn quality_types_in_use(self: Pin<&mut Self>, counts_json: QString);
+
+        #[qsignal]
+        #[cxx_name = "cacheStatsReady"]
+        fn cache_stats_ready(self: Pin<&mut Self>, stats_json: QString);
+
+        #[qsignal]
+        #[cxx_name = "posterProgress"]
+        fn poster_progress(self: Pin<&mut Self>, done: i32, total: i32);
+
+        /// Progress of `addSearchResults`; phase is "posters" while
+        /// downloading, then "saving" while inserting
+        #[qsignal]
+        #[cxx_name = "batchProgress"]
+        fn batch_progress(self: Pin<&mut Self>, done: i32, total: i32, phase: QString);
     }
 
     // Threading must be outside extern blocks
@@ -138,14 +507,51 @@ pub mod qobject {
 use core::pin::Pin;
 use cxx_qt::Threading;
 use cxx_qt_lib::QString;
+use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
-use crate::api;
+use crate::api::{self, ApiError};
 use crate::config;
 use crate::db;
 use crate::images;
-use crate::models::{AppConfig, MediaItem, SearchResult};
+use crate::maintenance::scheduler::IdleScheduler;
+use crate::models::{is_anilist_type, AppConfig, BatchAddResult, MediaItem, SearchResult};
+
+/// Posters downloaded concurrently (and committed together) per batch chunk
+const POSTER_CHUNK_SIZE: usize = 4;
+/// Max simultaneous poster downloads when adding search results
+const POSTER_DOWNLOAD_LIMIT: usize = 6;
+/// Max simultaneous TMDB detail lookups (season counts, IMDb ids) per add
+const DETAILS_LOOKUP_LIMIT: usize = 4;
+
+/// Pages the sidebar can route to; each maps to a `media_type` value
+pub const MEDIA_TYPES: [&str; 4] = ["Movie", "TV", "Anime", "Manga"];
+/// How many recently opened items to remember
+const RECENT_ITEMS_LIMIT: usize = 12;
+/// Rows in the "recently added/updated" view
+const RECENT_VIEW_LIMIT: i32 = 100;
+/// Online search queries remembered per media type
+const SEARCH_HISTORY_LIMIT: usize = 10;
+/// Typing pause before the library search term is applied
+const SEARCH_TERM_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(250);
+/// Toasts kept in the notification history
+const NOTIFICATION_LIMIT: usize = 200;
+
+/// A toast as kept in the notification history
+#[derive(Debug, Clone)]
+pub struct Notification {
+    /// Milliseconds since the Unix epoch
+    pub timestamp: i64,
+    pub toast_type: String, // "success", "error" or "warning"
+    pub message: String,
+    /// What the toast had no room for, e.g. which items failed; may be empty
+    pub details: String,
+    pub read: bool,
+}
 
 /// Shared app state accessible from the bridge
 pub struct AppState {
@@ -154,6 +560,40 @@ pub struct AppState {
     pub config_path: PathBuf,
     pub data_dir: PathBuf,
     pub search_results: Mutex<Vec<SearchResult>>,
+    /// Media type `search_results` were fetched for
+    pub search_media_type: Mutex<String>,
+    /// Local thumbnails prefetched for `search_results`, by result index, as
+    /// paths relative to `data_dir`
+    pub cached_poster_paths: Mutex<Vec<Option<String>>>,
+    /// Bumped on every new result set so stale prefetches are dropped
+    pub search_generation: AtomicUsize,
+    /// Bumped whenever a search is started or cancelled; only the search
+    /// issued under the current value may publish results
+    pub search_request: AtomicUsize,
+    /// The search in flight, until it has published its results
+    pub search_task: Mutex<Option<tokio::task::AbortHandle>>,
+    /// Bumped on every library search keystroke; a pending debounced reload
+    /// only runs if no newer one came in
+    pub search_term_generation: AtomicUsize,
+    pub maintenance: IdleScheduler,
+    pub poster_refetch_cancel: AtomicBool,
+    /// Per-media-type totals; None until computed or after a change invalidates them
+    pub counts_cache: Mutex<Option<HashMap<String, i64>>>,
+    /// Recently opened item ids, most recent first (session only)
+    pub recent_item_ids: Mutex<Vec<i64>>,
+    /// Toasts shown this session, newest first, up to NOTIFICATION_LIMIT
+    pub notifications: Mutex<VecDeque<Notification>>,
+    /// Outcome of the last `addSearchResults`, for its detail view
+    pub last_batch_report: Mutex<Option<BatchAddResult>>,
+    /// Problem reading the config at startup, shown once the UI is up
+    pub config_warning: Mutex<Option<String>>,
+    /// Runs searches and poster downloads off the UI thread
+    pub runtime: tokio::runtime::Runtime,
+    /// Built on first use; clones share its connection pool
+    http_client: std::sync::OnceLock<reqwest::Client>,
+    /// Told when config.json is edited outside the app; set once the
+    /// controller has loaded its settings
+    config_listener: Mutex<Option<cxx_qt::CxxQtThread<qobject::AppController>>>,
 }
 
 /// Global app state, initialized once
@@ -161,26 +601,109 @@ static APP_STATE: std::sync::OnceLock<Arc<AppState>> = std::sync::OnceLock::new(
 
 pub fn init_app_state() -> Arc<AppState> {
     let data_dir = get_data_dir();
+    // Config first: it holds the log level, and the DB setup is worth logging
+    let loaded = config::manager::load_config(&data_dir).expect("Failed to load config");
+    crate::logging::init(&data_dir, &loaded.config.log_level);
+    tracing::info!("Starting media-tracker {}; data dir {}", env!("CARGO_PKG_VERSION"), data_dir.display());
     let conn = db::connection::init_db(&data_dir).expect("Failed to initialize database");
-    let (cfg, config_path) = config::manager::load_config(&data_dir).expect("Failed to load config");
+    let config_warning = loaded.parse_error.as_ref().map(|e| {
+        tracing::warn!("Could not read {}: {}", loaded.path.display(), e);
+        if loaded.recovered_from_backup {
+            "Settings file was unreadable; restored the previous settings from config.json.bak".to_string()
+        } else {
+            "Your config couldn't be read, defaults loaded. The file was left as is; it's moved to config.json.invalid on the next save.".to_string()
+        }
+    });
+    let config_warning = config_warning.or_else(|| {
+        let e = loaded.keyring_error.as_ref()?;
+        tracing::warn!("Could not read the API key from the system keyring: {}", e);
+        Some(if loaded.config.tmdb_api_key.is_empty() {
+            "System keyring unavailable; no TMDB API key loaded".to_string()
+        } else {
+            "System keyring unavailable; using the API key from config.json".to_string()
+        })
+    });
 
     let state = Arc::new(AppState {
         db: Mutex::new(conn),
-        config: Mutex::new(cfg),
-        config_path,
+        config: Mutex::new(loaded.config),
+        config_path: loaded.path,
         data_dir,
         search_results: Mutex::new(Vec::new()),
+        search_media_type: Mutex::new(String::new()),
+        cached_poster_paths: Mutex::new(Vec::new()),
+        search_generation: AtomicUsize::new(0),
+        search_request: AtomicUsize::new(0),
+        search_task: Mutex::new(None),
+        search_term_generation: AtomicUsize::new(0),
+        maintenance: IdleScheduler::new(),
+        poster_refetch_cancel: AtomicBool::new(false),
+        counts_cache: Mutex::new(None),
+        recent_item_ids: Mutex::new(Vec::new()),
+        notifications: Mutex::new(VecDeque::new()),
+        last_batch_report: Mutex::new(None),
+        config_warning: Mutex::new(config_warning),
+        runtime: tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to start async runtime"),
+        http_client: std::sync::OnceLock::new(),
+        config_listener: Mutex::new(None),
     });
 
     APP_STATE.set(state.clone()).ok();
+    config::watcher::start(state.config_path.clone(), apply_external_config);
     state
 }
 
+/// Swap in settings edited outside the app and have the controller pick them up
+fn apply_external_config(config: AppConfig) {
+    let state = get_app_state();
+    *state.config.lock().unwrap() = config;
+    let listener = state.config_listener.lock().unwrap().clone();
+    if let Some(qt_thread) = listener {
+        let _ = qt_thread.queue(|mut ctrl: Pin<&mut qobject::AppController>| {
+            ctrl.as_mut().load_config();
+            ctrl.as_mut().show_toast(
+                QString::from("Settings reloaded from config.json"),
+                QString::from("success"),
+            );
+            ctrl.as_mut().settings_loaded();
+            ctrl.as_mut().statuses_changed();
+            ctrl.as_mut().reload_items();
+        });
+    }
+}
+
 pub fn get_app_state() -> Arc<AppState> {
     APP_STATE.get().expect("App state not initialized").clone()
 }
 
-fn get_data_dir() -> PathBuf {
+/// Save settings changed as a side effect of something else, where a failed
+/// write isn't worth interrupting the user over
+fn save_config_logged(cfg: &AppConfig, config_path: &std::path::Path) {
+    if let Err(e) = config::manager::save_config(cfg, config_path) {
+        tracing::warn!("Could not save {}: {}", config_path.display(), e);
+    }
+}
+
+/// The shared HTTP client, so back-to-back requests reuse connections
+fn http_client() -> reqwest::Client {
+    let state = get_app_state();
+    state.http_client.get_or_init(api::new_client).clone()
+}
+
+/// Whether the search issued as `request` is still the one to show
+fn is_current_search(request: usize) -> bool {
+    get_app_state().search_request.load(Ordering::SeqCst) == request
+}
+
+/// Run `future` on the shared runtime
+fn spawn_async(future: impl std::future::Future<Output = ()> + Send + 'static) {
+    get_app_state().runtime.spawn(future);
+}
+
+pub fn get_data_dir() -> PathBuf {
     let exe_path = std::env::current_exe().expect("Failed to get executable path");
     let exe_dir = exe_path.parent().expect("Failed to get executable directory");
     exe_dir.join("data")
@@ -192,111 +715,671 @@ pub struct AppControllerRust {
     active_status: QString,
     view_mode: QString,
     search_term: QString,
+    favorites_only: bool,
+    quality_filter: QString,
+    source_filter: QString,
+    min_year: i32,
+    max_year: i32,
+    missing_poster: bool,
+    incomplete_only: bool,
+    watched_filter: QString,
+    recent_view: QString,
+    recent_limit: i32,
     loading: bool,
     movie_count: i32,
     tv_count: i32,
     anime_count: i32,
+    manga_count: i32,
     item_count: i32,
-    on_drive_count: i32,
-    to_download_count: i32,
-    to_work_on_count: i32,
+    status_counts: QString,
     sort_field: QString,
     sort_dir: QString,
+    sort_field2: QString,
+    sort_dir2: QString,
     row_height: i32,
     tmdb_api_key: QString,
+    use_keyring: bool,
     include_adult: bool,
+    poster_size: QString,
+    tmdb_language: QString,
+    tmdb_region: QString,
+    tmdb_result_pages: i32,
+    idle_maintenance: bool,
+    ignore_articles: bool,
+    prefetch_search_posters: bool,
+    uniform_posters: bool,
+    refetching_posters: bool,
+    online: bool,
+}
+
+/// Which library rows the list shows and in what order: the page, status
+/// tab, search box and every filter and sort setting. Built from the
+/// controller's properties by `current_view`; MediaModel gets it as JSON.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ViewQuery {
+    pub page: String,
+    pub status: String,
+    pub search: String,
+    pub sort_field: String,
+    pub sort_dir: String,
+    pub sort_field2: String,
+    pub sort_dir2: String,
+    pub favorites_only: bool,
+    pub quality: String,
+    pub source: String,
+    /// 0 (or anything non-positive) leaves that side of the range open
+    pub min_year: i32,
+    pub max_year: i32,
+    pub missing_poster: bool,
+    pub incomplete: bool,
+    /// "watched" / "unwatched"; anything else shows both
+    pub watched: String,
+}
+
+impl ViewQuery {
+    pub fn filter(&self) -> db::queries::ItemFilter<'_> {
+        let year_bound = |year: i32| (year > 0).then_some(year);
+        db::queries::ItemFilter {
+            media_type: Some(&self.page),
+            // Favorites view spans every status
+            status: if self.favorites_only { None } else { Some(&self.status) },
+            favorites_only: self.favorites_only,
+            quality: Some(&self.quality),
+            source: Some(&self.source),
+            min_year: year_bound(self.min_year),
+            max_year: year_bound(self.max_year),
+            has_poster: self.missing_poster.then_some(false),
+            incomplete: self.incomplete,
+            watched: match self.watched.as_str() {
+                "watched" => Some(true),
+                "unwatched" => Some(false),
+                _ => None,
+            },
+        }
+    }
+
+    pub fn sort(&self, ignore_articles: bool) -> db::queries::ItemSort<'_> {
+        db::queries::ItemSort {
+            field: &self.sort_field,
+            dir: &self.sort_dir,
+            then_by: (!self.sort_field2.is_empty()).then_some((self.sort_field2.as_str(), self.sort_dir2.as_str())),
+            ignore_articles,
+        }
+    }
+
+    /// The rows in the view, in order
+    pub fn items(&self) -> Result<Vec<MediaItem>, rusqlite::Error> {
+        let state = get_app_state();
+        let ignore_articles = state.config.lock().unwrap().ignore_articles;
+        let filter = self.filter();
+        let sort = self.sort(ignore_articles);
+        let mut items = {
+            let conn = state.db.lock().unwrap();
+            if self.search.is_empty() {
+                db::queries::query_items(&conn, &filter, &sort)?
+            } else {
+                db::queries::search_items(&conn, &self.search, &filter, &sort)?
+            }
+        };
+        if let Some(has_poster) = filter.has_poster {
+            // The query can't see whether a local poster file still exists
+            items.retain(|item| images::cache::poster_missing(item.poster_url.as_deref(), &state.data_dir) != has_poster);
+        }
+        Ok(items)
+    }
 }
 
 impl qobject::AppController {
     /// Initialize controller with data from DB/config
     pub fn navigate_to(mut self: Pin<&mut Self>, page: &QString) {
+        mark_activity();
+        if !MEDIA_TYPES.contains(&page.to_string().as_str()) {
+            return;
+        }
+        let initial_status = {
+            let state = get_app_state();
+            let cfg = state.config.lock().unwrap();
+            cfg.default_status_for(&page.to_string())
+        };
+        self.as_mut().set_recent_view(QString::from(""));
         self.as_mut().set_active_page(page.clone());
-        self.as_mut().set_active_status(QString::from("On Drive"));
+        self.as_mut().set_active_status(QString::from(&initial_status));
         self.as_mut().set_search_term(QString::from(""));
+        self.as_mut().set_quality_filter(QString::from(""));
+        self.as_mut().set_source_filter(QString::from(""));
+        self.as_mut().set_min_year(0);
+        self.as_mut().set_max_year(0);
         self.as_mut().reload_items();
         self.as_mut().reload_counts();
     }
 
     pub fn set_status_filter(mut self: Pin<&mut Self>, status: &QString) {
+        mark_activity();
         self.as_mut().set_active_status(status.clone());
         self.as_mut().reload_items();
     }
 
+    pub fn show_recent(mut self: Pin<&mut Self>, by_updated: bool) {
+        mark_activity();
+        let view = if by_updated { "updated" } else { "added" };
+        self.as_mut().set_recent_limit(RECENT_VIEW_LIMIT);
+        self.as_mut().set_recent_view(QString::from(view));
+        self.as_mut().set_search_term(QString::from(""));
+        self.as_mut().reload_items();
+    }
+
     pub fn set_search_term_filter(mut self: Pin<&mut Self>, term: &QString) {
+        mark_activity();
         self.as_mut().set_search_term(term.clone());
+
+        // Query once typing pauses rather than on every keystroke
+        let state = get_app_state();
+        let generation = state.search_term_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let qt_thread = self.qt_thread();
+        spawn_async(async move {
+            tokio::time::sleep(SEARCH_TERM_DEBOUNCE).await;
+            qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+                if get_app_state().search_term_generation.load(Ordering::SeqCst) == generation {
+                    ctrl.as_mut().reload_items();
+                }
+            }).unwrap();
+        });
+    }
+
+    pub fn search_now(mut self: Pin<&mut Self>) {
+        mark_activity();
+        // Drop the pending debounced reload; this one covers it
+        get_app_state().search_term_generation.fetch_add(1, Ordering::SeqCst);
         self.as_mut().reload_items();
     }
 
-    pub fn set_view_mode_pref(mut self: Pin<&mut Self>, mode: &QString) {
-        self.as_mut().set_view_mode(mode.clone());
-        let state = get_app_state();
-        let mut cfg = state.config.lock().unwrap();
-        cfg.view_mode = mode.to_string();
-        let _ = config::manager::save_config(&cfg, &state.config_path);
+    pub fn set_favorites_only_filter(mut self: Pin<&mut Self>, enabled: bool) {
+        mark_activity();
+        self.as_mut().set_favorites_only(enabled);
+        self.as_mut().reload_items();
     }
 
-    pub fn save_item(
-        mut self: Pin<&mut Self>,
-        id: i32,
-        title: &QString,
-        native_title: &QString,
-        romaji_title: &QString,
-        year: i32,
-        status: &QString,
-        quality_type: &QString,
-        source: &QString,
-        notes: &QString,
-        poster_url: &QString,
-    ) {
-        let state = get_app_state();
-        let conn = state.db.lock().unwrap();
-        let media_type = self.active_page().to_string();
+    pub fn show_missing_posters(mut self: Pin<&mut Self>, enabled: bool) {
+        mark_activity();
+        self.as_mut().set_missing_poster(enabled);
+        self.as_mut().reload_items();
+    }
 
-        let normalized_poster_url = opt_string(poster_url)
-            .map(|url| normalize_poster_url_for_storage(&url, &state.data_dir));
+    pub fn set_incomplete_filter(mut self: Pin<&mut Self>, enabled: bool) {
+        mark_activity();
+        self.as_mut().set_incomplete_only(enabled);
+        self.as_mut().reload_items();
+    }
 
-        let item = MediaItem {
-            id: if id >= 0 { Some(id as i64) } else { None },
-            title: title.to_string(),
-            native_title: opt_string(native_title),
-            romaji_title: opt_string(romaji_title),
-            year: if year > 0 { Some(year) } else { None },
-            media_type,
-            status: status.to_string(),
-            quality_type: opt_string(quality_type),
-            source: opt_string(source),
-            notes: opt_string(notes),
-            tmdb_id: None,
-            anilist_id: None,
-            poster_url: normalized_poster_url,
-            created_at: None,
-            updated_at: None,
-        };
+    pub fn set_watched_filter_value(mut self: Pin<&mut Self>, value: &QString) {
+        mark_activity();
+        self.as_mut().set_watched_filter(value.clone());
+        self.as_mut().reload_items();
+    }
 
-        let result = if id >= 0 {
-            db::queries::update_item(&conn, &item).map(|_| "Item updated".to_string())
-        } else {
-            db::queries::add_item(&conn, &item).map(|_| "Item added".to_string())
-        };
+    pub fn set_quality_filter_value(mut self: Pin<&mut Self>, quality: &QString) {
+        mark_activity();
+        self.as_mut().set_quality_filter(quality.clone());
+        self.as_mut().reload_items();
+    }
 
-        drop(conn);
+    pub fn set_source_filter_value(mut self: Pin<&mut Self>, source: &QString) {
+        mark_activity();
+        self.as_mut().set_source_filter(source.clone());
+        self.as_mut().reload_items();
+    }
 
-        match result {
-            Ok(msg) => {
-                self.as_mut().toast_message(QString::from(&msg), QString::from("success"));
-                self.as_mut().reload_items();
-                self.as_mut().reload_counts();
+    pub fn set_year_range(mut self: Pin<&mut Self>, min_year: i32, max_year: i32) {
+        mark_activity();
+        let (min_year, max_year) = year_range(min_year, max_year);
+        self.as_mut().set_min_year(min_year);
+        self.as_mut().set_max_year(max_year);
+        self.as_mut().reload_items();
+    }
+
+    /// Keys: media_type, status, favorites_only, quality_type, source,
+    /// min_year, max_year, has_poster, incomplete, watched. media_type and status keep
+    /// their current value when left out; every other criterion is cleared.
+    pub fn set_advanced_filter(mut self: Pin<&mut Self>, json: &QString) {
+        mark_activity();
+        let filter: serde_json::Value = match serde_json::from_str(&json.to_string()) {
+            Ok(value @ serde_json::Value::Object(_)) => value,
+            _ => {
+                self.as_mut().show_toast(
+                    QString::from("Invalid filter: expected a JSON object"),
+                    QString::from("error"),
+                );
+                return;
             }
-            Err(e) => {
-                self.as_mut().toast_message(
-                    QString::from(&format!("Error: {}", e)),
+        };
+        if let Some(page) = filter["media_type"].as_str() {
+            if !MEDIA_TYPES.contains(&page) {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Unknown media type: {}", page)),
                     QString::from("error"),
                 );
+                return;
             }
+            self.as_mut().set_active_page(QString::from(page));
+        }
+        if let Some(status) = filter["status"].as_str() {
+            self.as_mut().set_active_status(QString::from(status));
         }
+
+        let text = |key: &str| QString::from(filter[key].as_str().unwrap_or(""));
+        let year = |key: &str| filter[key].as_i64().map_or(0, |y| y as i32);
+        let (min_year, max_year) = year_range(year("min_year"), year("max_year"));
+        self.as_mut().set_favorites_only(filter["favorites_only"].as_bool().unwrap_or(false));
+        self.as_mut().set_quality_filter(text("quality_type"));
+        self.as_mut().set_source_filter(text("source"));
+        self.as_mut().set_min_year(min_year);
+        self.as_mut().set_max_year(max_year);
+        self.as_mut().set_missing_poster(filter["has_poster"].as_bool() == Some(false));
+        self.as_mut().set_incomplete_only(filter["incomplete"].as_bool().unwrap_or(false));
+        let watched = match filter["watched"].as_bool() {
+            Some(true) => "watched",
+            Some(false) => "unwatched",
+            None => "",
+        };
+        self.as_mut().set_watched_filter(QString::from(watched));
+        self.as_mut().reload_items();
     }
 
-    pub fn delete_items(mut self: Pin<&mut Self>, ids: &QString) {
+    pub fn view_query(&self) -> QString {
+        QString::from(&serde_json::to_string(&self.current_view()).unwrap_or_else(|_| "{}".into()))
+    }
+
+    fn current_view(&self) -> ViewQuery {
+        ViewQuery {
+            page: self.active_page().to_string(),
+            status: self.active_status().to_string(),
+            search: self.search_term().to_string(),
+            sort_field: self.sort_field().to_string(),
+            sort_dir: self.sort_dir().to_string(),
+            sort_field2: self.sort_field2().to_string(),
+            sort_dir2: self.sort_dir2().to_string(),
+            favorites_only: *self.favorites_only(),
+            quality: self.quality_filter().to_string(),
+            source: self.source_filter().to_string(),
+            min_year: *self.min_year(),
+            max_year: *self.max_year(),
+            missing_poster: *self.missing_poster(),
+            incomplete: *self.incomplete_only(),
+            watched: self.watched_filter().to_string(),
+        }
+    }
+
+    pub fn get_distinct_sources(&self) -> QString {
+        let state = get_app_state();
+        let conn = state.db.lock().unwrap();
+        let sources = db::queries::get_distinct_sources(&conn).unwrap_or_default();
+        QString::from(&sources.join("\n"))
+    }
+
+    pub fn get_quality_types_in_use(&self, media_type: &QString) -> QString {
+        let state = get_app_state();
+        let conn = state.db.lock().unwrap();
+        let types = db::queries::get_quality_types_in_use(&conn, &media_type.to_string()).unwrap_or_default();
+        let json: Vec<serde_json::Value> = types
+            .into_iter()
+            .map(|(quality, count)| serde_json::json!({ "quality": quality, "count": count }))
+            .collect();
+        QString::from(&serde_json::to_string(&json).unwrap_or_else(|_| "[]".into()))
+    }
+
+    pub fn toggle_favorite(mut self: Pin<&mut Self>, id: i32) {
+        mark_activity();
+        let state = get_app_state();
+        let conn = state.db.lock().unwrap();
+        let result = db::queries::toggle_favorite(&conn, id as i64);
+        drop(conn);
+
+        match result {
+            Ok(_) => self.as_mut().reload_items(),
+            Err(e) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Error: {}", e)),
+                    QString::from("error"),
+                );
+            }
+        }
+    }
+
+    pub fn duplicate_item(mut self: Pin<&mut Self>, id: i32) {
+        mark_activity();
+        let state = get_app_state();
+        let conn = state.db.lock().unwrap();
+        let result = db::queries::duplicate_item(&conn, id as i64);
+        drop(conn);
+
+        match result {
+            Ok(Some(new_id)) => {
+                invalidate_counts();
+                self.as_mut().show_toast(
+                    QString::from("Item duplicated"),
+                    QString::from("success"),
+                );
+                self.as_mut().patch_items(&[new_id]);
+                self.as_mut().reload_counts();
+            }
+            Ok(None) => {
+                self.as_mut().show_toast(
+                    QString::from("Item not found"),
+                    QString::from("error"),
+                );
+            }
+            Err(e) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Duplicate failed: {}", e)),
+                    QString::from("error"),
+                );
+            }
+        }
+    }
+
+    pub fn link_external_id(mut self: Pin<&mut Self>, id: i32, provider: &QString, external_id: i32) {
+        mark_activity();
+        let provider = provider.to_string();
+        let provider_name = match provider.as_str() {
+            "tmdb" => "TMDB",
+            "anilist" => "AniList",
+            _ => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Unknown provider: {}", provider)),
+                    QString::from("error"),
+                );
+                return;
+            }
+        };
+
+        let state = get_app_state();
+        let conn = state.db.lock().unwrap();
+        // One library item per provider id, or the link would hide a duplicate
+        let result = db::queries::get_by_external_id(&conn, &provider, external_id as i64).and_then(|existing| {
+            match existing {
+                Some(other) if other.id != Some(id as i64) => Ok(Err(other.title)),
+                _ => db::queries::link_external_id(&conn, id as i64, &provider, external_id as i64).map(Ok),
+            }
+        });
+        drop(conn);
+
+        match result {
+            Ok(Ok(true)) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Linked {} id {}", provider_name, external_id)),
+                    QString::from("success"),
+                );
+                self.as_mut().patch_items(&[id as i64]);
+            }
+            Ok(Ok(false)) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Item already has a {} id", provider_name)),
+                    QString::from("warning"),
+                );
+            }
+            Ok(Err(title)) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("{} id {} is already used by \"{}\"", provider_name, external_id, title)),
+                    QString::from("warning"),
+                );
+            }
+            Err(e) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Link failed: {}", e)),
+                    QString::from("error"),
+                );
+            }
+        }
+    }
+
+    pub fn poster_preview_url(&self, value: &QString) -> QString {
+        let value = value.to_string();
+        let value = value.trim();
+        if value.is_empty() || value.starts_with("http://") || value.starts_with("https://") {
+            return QString::from(value);
+        }
+        let state = get_app_state();
+        let path = images::cache::resolve_cached_poster_path(value, &state.data_dir);
+        QString::from(&images::cache::file_url(&path))
+    }
+
+    pub fn poster_path_from_url(&self, url: &QString) -> QString {
+        QString::from(&images::cache::path_from_file_url(&url.to_string()))
+    }
+
+    pub fn record_view(mut self: Pin<&mut Self>, id: i32) {
+        mark_activity();
+        if id < 0 {
+            return;
+        }
+        let state = get_app_state();
+        {
+            let mut recent = state.recent_item_ids.lock().unwrap();
+            recent.retain(|&r| r != id as i64);
+            recent.insert(0, id as i64);
+            recent.truncate(RECENT_ITEMS_LIMIT);
+        }
+        self.as_mut().recent_items_changed();
+    }
+
+    pub fn get_recent_items(&self) -> QString {
+        let state = get_app_state();
+        let ids = state.recent_item_ids.lock().unwrap().clone();
+        let conn = state.db.lock().unwrap();
+        let items = db::queries::get_items_by_ids(&conn, &ids).unwrap_or_default();
+        QString::from(&serde_json::to_string(&items).unwrap_or_else(|_| "[]".into()))
+    }
+
+    pub fn get_external_url(&self, id: i32) -> QString {
+        let item = {
+            let state = get_app_state();
+            let conn = state.db.lock().unwrap();
+            db::queries::get_item_by_id(&conn, id as i64).ok().flatten()
+        };
+        let url = item.and_then(|item| {
+            if let Some(imdb_id) = item.imdb_id.filter(|s| !s.is_empty()) {
+                return Some(format!("https://www.imdb.com/title/{}/", imdb_id));
+            }
+            if let (Some(tmdb_id), Some(kind)) = (item.tmdb_id, tmdb_kind(&item.media_type)) {
+                return Some(format!("https://www.themoviedb.org/{}/{}", kind, tmdb_id));
+            }
+            let anilist_id = item.anilist_id?;
+            let kind = if item.media_type == "Manga" { "manga" } else { "anime" };
+            Some(format!("https://anilist.co/{}/{}", kind, anilist_id))
+        });
+        QString::from(&url.unwrap_or_default())
+    }
+
+    pub fn export_current_view(mut self: Pin<&mut Self>, path: &QString) {
+        mark_activity();
+        if !self.recent_view().is_empty() {
+            self.as_mut().show_toast(
+                QString::from("Open a library page to export its items"),
+                QString::from("warning"),
+            );
+            return;
+        }
+        let path = PathBuf::from(images::cache::path_from_file_url(&path.to_string()));
+
+        // Exactly the rows MediaModel shows for the view
+        let result = self
+            .current_view()
+            .items()
+            .map_err(|e| e.to_string())
+            .and_then(|items| crate::export::export_items_csv(&items, &path));
+
+        match result {
+            Ok(count) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Exported {} item(s) to {}", count, path.display())),
+                    QString::from("success"),
+                );
+            }
+            Err(e) => {
+                tracing::warn!("CSV export failed: {}", e);
+                self.as_mut().show_toast(
+                    QString::from(&format!("Export failed: {}", e)),
+                    QString::from("error"),
+                );
+            }
+        }
+    }
+
+    pub fn get_selected_as_text(&self, ids: &QString, format: &QString, alt_titles: bool) -> QString {
+        let id_vec: Vec<i64> = ids
+            .to_string()
+            .split(',')
+            .filter_map(|s| s.trim().parse().ok())
+            .collect();
+        let items = {
+            let state = get_app_state();
+            let conn = state.db.lock().unwrap();
+            db::queries::get_items_by_ids(&conn, &id_vec).unwrap_or_default()
+        };
+
+        let markdown = format.to_string() == "markdown";
+        let lines: Vec<String> = items
+            .iter()
+            .map(|item| {
+                let line = item_text_line(item, alt_titles);
+                if markdown {
+                    format!("- {}", line)
+                } else {
+                    line
+                }
+            })
+            .collect();
+        QString::from(&lines.join("\n"))
+    }
+
+    pub fn set_view_mode_pref(mut self: Pin<&mut Self>, mode: &QString) {
+        mark_activity();
+        self.as_mut().set_view_mode(mode.clone());
+        let state = get_app_state();
+        let mut cfg = state.config.lock().unwrap();
+        cfg.view_mode = mode.to_string();
+        save_config_logged(&cfg, &state.config_path);
+    }
+
+    pub fn save_item(
+        mut self: Pin<&mut Self>,
+        id: i32,
+        title: &QString,
+        native_title: &QString,
+        romaji_title: &QString,
+        year: i32,
+        status: &QString,
+        quality_type: &QString,
+        source: &QString,
+        notes: &QString,
+        poster_url: &QString,
+        progress: i32,
+        total_episodes: i32,
+        acquired_date: &QString,
+        watched_date: &QString,
+    ) {
+        mark_activity();
+        let acquired_date = opt_string(acquired_date);
+        let watched_date = opt_string(watched_date);
+        for date in acquired_date.iter().chain(watched_date.iter()) {
+            if !is_valid_date(date) {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Invalid date \"{}\" (use YYYY-MM-DD)", date)),
+                    QString::from("error"),
+                );
+                return;
+            }
+        }
+
+        let state = get_app_state();
+        let conn = state.db.lock().unwrap();
+        // An edit keeps the item's own type; the recent view mixes types, so
+        // the active page isn't necessarily it
+        let existing_type = if id >= 0 {
+            db::queries::get_item_by_id(&conn, id as i64).ok().flatten().map(|item| item.media_type)
+        } else {
+            None
+        };
+        let media_type = existing_type.unwrap_or_else(|| self.active_page().to_string());
+
+        let normalized_poster_url = opt_string(poster_url)
+            .map(|url| normalize_poster_url_for_storage(&url, &state.data_dir));
+
+        let item = MediaItem {
+            id: if id >= 0 { Some(id as i64) } else { None },
+            title: title.to_string(),
+            native_title: opt_string(native_title),
+            romaji_title: opt_string(romaji_title),
+            year: if year > 0 { Some(year) } else { None },
+            media_type,
+            status: status.to_string(),
+            quality_type: opt_string(quality_type),
+            source: opt_string(source),
+            notes: opt_string(notes),
+            tmdb_id: None,
+            anilist_id: None,
+            imdb_id: None,
+            anime_format: None,
+            season: None,
+            poster_url: normalized_poster_url,
+            created_at: None,
+            updated_at: None,
+            number_of_seasons: None,
+            number_of_episodes: None,
+            progress: if progress >= 0 { Some(progress) } else { None },
+            total_episodes: if total_episodes > 0 { Some(total_episodes) } else { None },
+            favorite: false, // not touched by update_item
+            acquired_date,
+            watched_date,
+            poster_width: 0, // reset by update_item only if the poster changed
+            poster_height: 0,
+            watched: false, // not touched by update_item
+            poster_manual: false, // kept by update_item unless the poster changed
+        };
+
+        let result = if id >= 0 {
+            db::queries::update_item(&conn, &item).map(|_| (id as i64, "Item updated".to_string()))
+        } else {
+            db::queries::add_item(&conn, &item).map(|new_id| (new_id, "Item added".to_string()))
+        };
+
+        drop(conn);
+
+        match result {
+            Ok((saved_id, msg)) => {
+                invalidate_counts();
+                self.as_mut().show_toast(QString::from(&msg), QString::from("success"));
+                self.as_mut().patch_items(&[saved_id]);
+                self.as_mut().reload_counts();
+            }
+            Err(e) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Error: {}", e)),
+                    QString::from("error"),
+                );
+            }
+        }
+    }
+
+    pub fn preview_delete(&self, ids: &QString) -> QString {
+        let id_vec: Vec<i64> = ids
+            .to_string()
+            .split(',')
+            .filter_map(|s| s.trim().parse().ok())
+            .collect();
+        let items = {
+            let state = get_app_state();
+            let conn = state.db.lock().unwrap();
+            db::queries::get_items_by_ids(&conn, &id_vec).unwrap_or_default()
+        };
+        let titles: Vec<String> = items.iter().map(|item| item_text_line(item, false)).collect();
+        let preview = serde_json::json!({ "count": titles.len(), "titles": titles });
+        QString::from(&preview.to_string())
+    }
+
+    pub fn delete_items(mut self: Pin<&mut Self>, ids: &QString) {
+        mark_activity();
         let id_vec: Vec<i64> = ids
             .to_string()
             .split(',')
@@ -310,26 +1393,24 @@ impl qobject::AppController {
         let state = get_app_state();
         let conn = state.db.lock().unwrap();
 
-        // Collect poster paths before deleting so we can clean up cached images
-        let poster_paths = db::queries::get_poster_urls(&conn, &id_vec).unwrap_or_default();
-
         match db::queries::delete_items_batch(&conn, &id_vec) {
-            Ok(_) => {
+            Ok(unshared_posters) => {
                 drop(conn);
-                for path in &poster_paths {
+                invalidate_counts();
+                for path in &unshared_posters {
                     images::cache::delete_cached_poster(path, &state.data_dir);
                 }
                 let count = id_vec.len();
-                self.as_mut().toast_message(
+                self.as_mut().show_toast(
                     QString::from(&format!("Deleted {} item(s)", count)),
                     QString::from("success"),
                 );
-                self.as_mut().reload_items();
+                self.as_mut().patch_items(&id_vec);
                 self.as_mut().reload_counts();
             }
             Err(e) => {
                 drop(conn);
-                self.as_mut().toast_message(
+                self.as_mut().show_toast(
                     QString::from(&format!("Delete failed: {}", e)),
                     QString::from("error"),
                 );
@@ -338,30 +1419,47 @@ impl qobject::AppController {
     }
 
     pub fn move_items(mut self: Pin<&mut Self>, ids: &QString, new_status: &QString) {
-        let id_vec: Vec<i64> = ids
+        mark_activity();
+        let mut id_vec: Vec<i64> = ids
             .to_string()
             .split(',')
             .filter_map(|s| s.trim().parse().ok())
             .collect();
+        id_vec.sort_unstable();
+        id_vec.dedup();
 
         if id_vec.is_empty() {
             return;
         }
 
         let state = get_app_state();
+        let new_status = new_status.to_string();
+        if !state.config.lock().unwrap().statuses.contains(&new_status) {
+            self.as_mut().show_toast(
+                QString::from(&format!("Unknown status: {}", new_status)),
+                QString::from("error"),
+            );
+            return;
+        }
         let conn = state.db.lock().unwrap();
-        match db::queries::move_items(&conn, &id_vec, &new_status.to_string()) {
-            Ok(_) => {
+        match db::queries::move_items(&conn, &id_vec, &new_status) {
+            Ok(moved) => {
                 drop(conn);
-                self.as_mut().toast_message(
-                    QString::from(&format!("Moved {} item(s)", id_vec.len())),
-                    QString::from("success"),
-                );
+                invalidate_counts();
+                // Ids from a stale selection may have been deleted meanwhile
+                let (message, toast_type) = if moved == id_vec.len() {
+                    (format!("Moved {} item(s)", moved), "success")
+                } else {
+                    let missing = id_vec.len() - moved;
+                    (format!("Moved {} of {} ({} not found)", moved, id_vec.len(), missing), "warning")
+                };
+                self.as_mut().show_toast(QString::from(&message), QString::from(toast_type));
                 self.as_mut().reload_items();
+                self.as_mut().reload_counts();
             }
             Err(e) => {
                 drop(conn);
-                self.as_mut().toast_message(
+                self.as_mut().show_toast(
                     QString::from(&format!("Move failed: {}", e)),
                     QString::from("error"),
                 );
@@ -369,299 +1467,1782 @@ impl qobject::AppController {
         }
     }
 
-    pub fn search_online(mut self: Pin<&mut Self>, query: &QString, year: i32) {
-        let query_str = query.to_string().trim().to_string();
-        if query_str.is_empty() {
-            return;
-        }
-
-        let media_type = self.active_page().to_string();
-        let state = get_app_state();
-        let (api_key, include_adult) = {
+    pub fn move_items_to_status_index(mut self: Pin<&mut Self>, ids: &QString, index: i32) {
+        let status = {
+            let state = get_app_state();
             let cfg = state.config.lock().unwrap();
-            (cfg.tmdb_api_key.clone(), cfg.include_adult)
+            usize::try_from(index)
+                .ok()
+                .and_then(|i| cfg.statuses.get(i).cloned())
         };
+        match status {
+            Some(status) => self.move_items(ids, &QString::from(&status)),
+            None => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Invalid status index: {}", index)),
+                    QString::from("error"),
+                );
+            }
+        }
+    }
 
-        self.as_mut().searching_changed(true);
-
-        let qt_thread = self.qt_thread();
-        let year_opt = if year > 0 { Some(year) } else { None };
-
-        std::thread::spawn(move || {
-            let rt = tokio::runtime::Runtime::new().unwrap();
-            rt.block_on(async {
-                let client = reqwest::Client::builder()
-                    .timeout(std::time::Duration::from_secs(15))
-                    .build()
-                    .unwrap_or_default();
-
-                let results = match media_type.as_str() {
-                    "Movie" => {
-                        if api_key.is_empty() {
-                            Err("TMDB API key not set. Configure in Settings.".to_string())
-                        } else {
-                            api::tmdb::search_movie(&client, &api_key, &query_str, year_opt, include_adult).await
-                        }
-                    }
-                    "TV" => {
-                        if api_key.is_empty() {
-                            Err("TMDB API key not set. Configure in Settings.".to_string())
-                        } else {
-                            api::tmdb::search_tv(&client, &api_key, &query_str, year_opt, include_adult).await
-                        }
-                    }
-                    "Anime" => {
-                        api::anilist::search_anime(&client, &query_str, year_opt, include_adult).await
-                    }
-                    _ => Err("Unknown media type".to_string()),
-                };
-
-                match results {
-                    Ok(results) => {
-                        let count = results.len();
-
-                        // Store results in global state (posters are NOT cached yet —
-                        // they're only downloaded when the user actually adds items)
-                        let state = get_app_state();
-                        *state.search_results.lock().unwrap() = results;
+    pub fn get_search_history(&self, media_type: &QString) -> QString {
+        let state = get_app_state();
+        let cfg = state.config.lock().unwrap();
+        let history = cfg.search_history.get(&media_type.to_string()).cloned().unwrap_or_default();
+        QString::from(&serde_json::to_string(&history).unwrap_or_else(|_| "[]".into()))
+    }
 
-                        qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
-                            ctrl.as_mut().searching_changed(false);
-                            ctrl.as_mut().toast_message(
-                                QString::from(&format!("Found {} results", count)),
-                                QString::from("success"),
-                            );
-                            ctrl.as_mut().search_results_ready();
-                        }).unwrap();
-                    }
-                    Err(e) => {
-                        qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
-                            ctrl.as_mut().searching_changed(false);
-                            ctrl.as_mut().toast_message(
-                                QString::from(&format!("Search failed: {}", e)),
-                                QString::from("error"),
-                            );
-                        }).unwrap();
-                    }
-                }
-            });
-        });
+    pub fn clear_search_history(self: Pin<&mut Self>) {
+        mark_activity();
+        let state = get_app_state();
+        let mut cfg = state.config.lock().unwrap();
+        cfg.search_history.clear();
+        save_config_logged(&cfg, &state.config_path);
     }
 
-    pub fn add_search_results(self: Pin<&mut Self>, indices: &QString) {
-        let idx_vec: Vec<usize> = indices
+    pub fn advance_status(mut self: Pin<&mut Self>, ids: &QString) {
+        mark_activity();
+        let id_vec: Vec<i64> = ids
             .to_string()
             .split(',')
             .filter_map(|s| s.trim().parse().ok())
             .collect();
-
-        if idx_vec.is_empty() {
+        if id_vec.is_empty() {
             return;
         }
 
         let state = get_app_state();
-        let results = state.search_results.lock().unwrap();
-        let media_type = self.active_page().to_string();
-        let active_status = self.active_status().to_string();
+        let statuses = state.config.lock().unwrap().statuses.clone();
+        let conn = state.db.lock().unwrap();
+        let items = db::queries::get_items_by_ids(&conn, &id_vec).unwrap_or_default();
+
+        // Group by target so each status is one move; items already at the
+        // last status (or on one no longer configured) stay put
+        let mut targets: std::collections::BTreeMap<&str, Vec<i64>> = std::collections::BTreeMap::new();
+        let mut unchanged = 0;
+        for item in &items {
+            let next = statuses
+                .iter()
+                .position(|s| *s == item.status)
+                .and_then(|i| statuses.get(i + 1));
+            match (next, item.id) {
+                (Some(next), Some(id)) => targets.entry(next.as_str()).or_default().push(id),
+                _ => unchanged += 1,
+            }
+        }
+
+        // move_items runs its own transaction per call, so no outer one here
+        let moved = targets.iter().try_fold(0, |moved, (status, ids)| {
+            db::queries::move_items(&conn, ids, status).map(|n| moved + n)
+        });
+        drop(conn);
+
+        match moved {
+            Ok(moved) => {
+                invalidate_counts();
+                let (message, toast_type) = if unchanged == 0 {
+                    (format!("Advanced {} item(s)", moved), "success")
+                } else {
+                    (format!("Advanced {} item(s); {} already at the last status", moved, unchanged), "warning")
+                };
+                self.as_mut().show_toast(QString::from(&message), QString::from(toast_type));
+                if moved > 0 {
+                    self.as_mut().reload_items();
+                    self.as_mut().reload_counts();
+                }
+            }
+            Err(e) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Move failed: {}", e)),
+                    QString::from("error"),
+                );
+            }
+        }
+    }
+
+    pub fn set_watched(mut self: Pin<&mut Self>, ids: &QString, watched: bool) {
+        mark_activity();
+        let id_vec: Vec<i64> = ids
+            .to_string()
+            .split(',')
+            .filter_map(|s| s.trim().parse().ok())
+            .collect();
+        if id_vec.is_empty() {
+            return;
+        }
+
+        let state = get_app_state();
+        let conn = state.db.lock().unwrap();
+        let result = db::queries::bulk_set_watched(&conn, &id_vec, watched);
+        drop(conn);
+
+        match result {
+            Ok(changed) => {
+                let label = if watched { "watched" } else { "unwatched" };
+                self.as_mut().show_toast(
+                    QString::from(&format!("Marked {} item(s) {}", changed, label)),
+                    QString::from("success"),
+                );
+                self.as_mut().patch_items(&id_vec);
+            }
+            Err(e) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Update failed: {}", e)),
+                    QString::from("error"),
+                );
+            }
+        }
+    }
+
+    pub fn search_online(mut self: Pin<&mut Self>, query: &QString, year: i32) {
+        mark_activity();
+        let query_str = query.to_string().trim().to_string();
+        if query_str.is_empty() {
+            return;
+        }
+
+        let media_type = self.active_page().to_string();
+        let state = get_app_state();
+        let (api_key, include_adult, poster_size, locale, pages, prefetch) = {
+            let mut cfg = state.config.lock().unwrap();
+            // Re-running a query moves it back to the top
+            let history = cfg.search_history.entry(media_type.clone()).or_default();
+            history.retain(|q| !q.eq_ignore_ascii_case(&query_str));
+            history.insert(0, query_str.clone());
+            history.truncate(SEARCH_HISTORY_LIMIT);
+            save_config_logged(&cfg, &state.config_path);
+
+            let locale = api::tmdb::Locale {
+                language: cfg.tmdb_language.clone(),
+                region: cfg.tmdb_region.clone(),
+            };
+            (
+                cfg.tmdb_api_key.clone(),
+                cfg.include_adult,
+                cfg.poster_size.clone(),
+                locale,
+                cfg.tmdb_result_pages,
+                cfg.prefetch_search_posters,
+            )
+        };
+
+        self.as_mut().searching_changed(true);
+
+        let qt_thread = self.qt_thread();
+        let year_opt = if year > 0 { Some(year) } else { None };
+        let request = state.search_request.fetch_add(1, Ordering::SeqCst) + 1;
+
+        // A newer search supersedes the old one outright
+        let mut search_task = state.search_task.lock().unwrap();
+        if let Some(previous) = search_task.take() {
+            previous.abort();
+        }
+        let task = state.runtime.spawn(async move {
+            let client = http_client();
+
+            // Fail fast instead of waiting out the search timeout
+            let online = api::is_reachable(&client, api::provider_base_url(&media_type)).await;
+            if !online {
+                qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+                    if !is_current_search(request) {
+                        return;
+                    }
+                    ctrl.as_mut().set_online(false);
+                    ctrl.as_mut().searching_changed(false);
+                    ctrl.as_mut().show_toast(
+                        QString::from("You appear to be offline"),
+                        QString::from("error"),
+                    );
+                }).unwrap();
+                return;
+            }
+            qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+                ctrl.as_mut().set_online(true);
+            }).unwrap();
+
+            let results = match media_type.as_str() {
+                "Movie" => {
+                    if api_key.is_empty() {
+                        Err(ApiError::Unauthorized("TMDB API key not set".to_string()))
+                    } else {
+                        api::tmdb::search_movie(&client, &api_key, &query_str, year_opt, include_adult, &poster_size, &locale, pages).await
+                    }
+                }
+                "TV" => {
+                    if api_key.is_empty() {
+                        Err(ApiError::Unauthorized("TMDB API key not set".to_string()))
+                    } else {
+                        api::tmdb::search_tv(&client, &api_key, &query_str, year_opt, include_adult, &poster_size, &locale, pages).await
+                    }
+                }
+                "Anime" => {
+                    api::anilist::search_anime(&client, &query_str, year_opt, include_adult).await
+                }
+                "Manga" => {
+                    api::anilist::search_manga(&client, &query_str, year_opt, include_adult).await
+                }
+                _ => Err(ApiError::NotFound("Unknown media type".to_string())),
+            };
+
+            match results.map(api::dedupe_results) {
+                Ok(results) => {
+                    let count = results.len();
+                    let poster_urls: Vec<Option<String>> =
+                        results.iter().map(|r| r.poster_url.clone()).collect();
+
+                    // Store results in global state. With prefetching on, posters
+                    // go into search_cache below; the library copy is only made on add.
+                    let state = get_app_state();
+                    {
+                        // Holding the task slot keeps a cancel from slipping in
+                        // between the check and the store
+                        let mut search_task = state.search_task.lock().unwrap();
+                        if !is_current_search(request) {
+                            return;
+                        }
+                        // Published; cancelling now must not stop the poster prefetch
+                        search_task.take();
+                        *state.search_results.lock().unwrap() = results;
+                    }
+                    *state.search_media_type.lock().unwrap() = media_type.clone();
+                    let generation = {
+                        let mut cached = state.cached_poster_paths.lock().unwrap();
+                        *cached = vec![None; count];
+                        state.search_generation.fetch_add(1, Ordering::SeqCst) + 1
+                    };
+
+                    qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+                        if !is_current_search(request) {
+                            return;
+                        }
+                        ctrl.as_mut().searching_changed(false);
+                        ctrl.as_mut().show_toast(
+                            QString::from(&format!("Found {} results", count)),
+                            QString::from("success"),
+                        );
+                        ctrl.as_mut().search_results_ready();
+                    }).unwrap();
+
+                    if prefetch {
+                        // Trim before downloading so this search's posters are kept
+                        images::cache::evict_search_cache(
+                            &state.data_dir.join("image_cache"),
+                            images::cache::SEARCH_CACHE_MAX_FILES,
+                        );
+                        prefetch_search_posters(&client, poster_urls, generation, &qt_thread).await;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("{} search for {:?} failed: {}", media_type, query_str, e);
+                    qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+                        if !is_current_search(request) {
+                            return;
+                        }
+                        ctrl.as_mut().searching_changed(false);
+                        ctrl.as_mut().show_toast(
+                            QString::from(&format!("Search failed: {}", api_error_message(&e))),
+                            QString::from("error"),
+                        );
+                    }).unwrap();
+                }
+            }
+        });
+        *search_task = Some(task.abort_handle());
+    }
+
+    pub fn cancel_search(mut self: Pin<&mut Self>) {
+        mark_activity();
+        let state = get_app_state();
+        {
+            let mut search_task = state.search_task.lock().unwrap();
+            state.search_request.fetch_add(1, Ordering::SeqCst);
+            if let Some(task) = search_task.take() {
+                task.abort();
+            }
+        }
+        self.as_mut().searching_changed(false);
+    }
+
+    pub fn add_search_results(self: Pin<&mut Self>, indices: &QString, status: &QString, allow_duplicates: bool) {
+        mark_activity();
+        let idx_vec: Vec<usize> = indices
+            .to_string()
+            .split(',')
+            .filter_map(|s| s.trim().parse().ok())
+            .collect();
+
+        if idx_vec.is_empty() {
+            return;
+        }
+        self.add_results(idx_vec, status.to_string(), None, allow_duplicates);
+    }
+
+    pub fn add_search_result_with_details(
+        mut self: Pin<&mut Self>,
+        index: i32,
+        overrides: &QString,
+        allow_duplicates: bool,
+    ) {
+        mark_activity();
+        let overrides: serde_json::Value = match serde_json::from_str(&overrides.to_string()) {
+            Ok(value @ serde_json::Value::Object(_)) => value,
+            _ => {
+                self.as_mut().show_toast(
+                    QString::from("Invalid details: expected a JSON object"),
+                    QString::from("error"),
+                );
+                return;
+            }
+        };
+        let result_count = get_app_state().search_results.lock().unwrap().len();
+        if index < 0 || index as usize >= result_count {
+            self.as_mut().show_toast(
+                QString::from(&format!("No search result at index {}", index)),
+                QString::from("error"),
+            );
+            return;
+        }
+        let status = overrides["status"].as_str().unwrap_or("").to_string();
+        self.add_results(vec![index as usize], status, Some(overrides), allow_duplicates);
+    }
+
+    pub fn get_last_batch_report(&self) -> QString {
+        let state = get_app_state();
+        let report = state.last_batch_report.lock().unwrap();
+        let json = report
+            .as_ref()
+            .and_then(|r| serde_json::to_string(r).ok())
+            .unwrap_or_else(|| "{}".into());
+        QString::from(&json)
+    }
+
+    /// Shared by both add invokables: build items from the chosen results,
+    /// cache their posters and insert them, skipping duplicates unless
+    /// `allow_duplicates`. `overrides` replaces fields on every item (see
+    /// `apply_result_overrides`).
+    fn add_results(
+        mut self: Pin<&mut Self>,
+        idx_vec: Vec<usize>,
+        status: String,
+        overrides: Option<serde_json::Value>,
+        allow_duplicates: bool,
+    ) {
+        let state = get_app_state();
+        let media_type = self.active_page().to_string();
+        let (api_key, status, known_status) = {
+            let cfg = state.config.lock().unwrap();
+            let status = match status {
+                s if s.is_empty() => cfg.default_status_for(&media_type),
+                s => s,
+            };
+            let known_status = cfg.statuses.contains(&status);
+            (cfg.tmdb_api_key.clone(), status, known_status)
+        };
+        if !known_status {
+            self.as_mut().show_toast(
+                QString::from(&format!("Unknown status: {}", status)),
+                QString::from("error"),
+            );
+            return;
+        }
+        let results = state.search_results.lock().unwrap();
+        let generation = state.search_generation.load(Ordering::SeqCst);
 
         // Collect items and their poster URLs (not yet cached)
         let mut items_to_add: Vec<MediaItem> = Vec::new();
         let mut poster_urls: Vec<Option<String>> = Vec::new();
+        let mut result_indices: Vec<usize> = Vec::new();
         for &idx in &idx_vec {
             if let Some(r) = results.get(idx) {
                 poster_urls.push(r.poster_url.clone());
-                let item = MediaItem {
+                result_indices.push(idx);
+                let mut item = MediaItem {
                     id: None,
                     title: r.title.clone(),
                     native_title: r.native_title.clone(),
                     romaji_title: r.romaji_title.clone(),
                     year: r.year,
                     media_type: media_type.clone(),
-                    status: active_status.clone(),
+                    status: status.clone(),
                     quality_type: None,
                     source: None,
                     notes: None,
-                    tmdb_id: if media_type != "Anime" { Some(r.api_id) } else { None },
-                    anilist_id: if media_type == "Anime" { Some(r.api_id) } else { None },
+                    tmdb_id: if !is_anilist_type(&media_type) { Some(r.api_id) } else { None },
+                    anilist_id: if is_anilist_type(&media_type) { Some(r.api_id) } else { None },
+                    imdb_id: None, // looked up below for TMDB types
+                    anime_format: r.anime_format.clone(),
+                    season: r.season.clone(),
                     poster_url: None, // will be set after caching
                     created_at: None,
                     updated_at: None,
+                    number_of_seasons: None,
+                    number_of_episodes: None,
+                    progress: None,
+                    total_episodes: r.total_episodes,
+                    favorite: false,
+                    acquired_date: None,
+                    watched_date: None,
+                    poster_width: 0,
+                    poster_height: 0,
+                    watched: false,
+                    poster_manual: false,
                 };
+                if let Some(overrides) = &overrides {
+                    apply_result_overrides(&mut item, overrides);
+                }
                 items_to_add.push(item);
             }
         }
-        drop(results);
+        drop(results);
+
+        // Cache posters for the items actually being added, a few at a time
+        let cache_dir = state.data_dir.join("image_cache");
+        let qt_thread = self.qt_thread();
+
+        spawn_async(async move {
+            let client = http_client();
+
+            let semaphore = Arc::new(Semaphore::new(POSTER_DOWNLOAD_LIMIT));
+            let mut downloads = JoinSet::new();
+            for (i, url_opt) in poster_urls.into_iter().enumerate() {
+                let Some(url) = url_opt.filter(|u| !u.is_empty()) else {
+                    continue;
+                };
+                let client = client.clone();
+                let cache_dir = cache_dir.clone();
+                let semaphore = semaphore.clone();
+                downloads.spawn(async move {
+                    let _permit = semaphore.acquire_owned().await;
+                    (i, images::cache::cache_poster(&client, &cache_dir, &url).await)
+                });
+            }
+
+            // A failed poster just leaves that item without one
+            let poster_total = downloads.len() as i32;
+            let mut posters_done = 0;
+            let mut posters_failed = 0;
+            while let Some(joined) = downloads.join_next().await {
+                posters_done += 1;
+                queue_batch_progress(&qt_thread, posters_done, poster_total, "posters");
+                match joined {
+                    Ok((i, Ok(poster))) => {
+                        let item = &mut items_to_add[i];
+                        item.poster_url = Some(images::cache::stored_poster_path(&poster.path, &state.data_dir));
+                        item.poster_width = poster.width;
+                        item.poster_height = poster.height;
+                        // Let the still-open results dialog show the library copy
+                        show_search_poster(result_indices[i], poster.path, generation, &qt_thread);
+                    }
+                    _ => posters_failed += 1,
+                }
+            }
+
+            // Season/episode counts only come from the TV details endpoint and
+            // IMDb ids from external_ids. A failed lookup just leaves them empty.
+            if let Some(kind) = tmdb_kind(&media_type).filter(|_| !api_key.is_empty()) {
+                let semaphore = Arc::new(Semaphore::new(DETAILS_LOOKUP_LIMIT));
+                let mut lookups = JoinSet::new();
+                for (i, item) in items_to_add.iter().enumerate() {
+                    let Some(tmdb_id) = item.tmdb_id else {
+                        continue;
+                    };
+                    let client = client.clone();
+                    let api_key = api_key.clone();
+                    let semaphore = semaphore.clone();
+                    lookups.spawn(async move {
+                        let _permit = semaphore.acquire_owned().await;
+                        let tv_details = match kind {
+                            "tv" => api::tmdb::get_tv_details(&client, &api_key, tmdb_id)
+                                .await
+                                .inspect_err(|e| tracing::warn!("TV details for TMDB {}: {}", tmdb_id, e))
+                                .ok(),
+                            _ => None,
+                        };
+                        let imdb_id = api::tmdb::get_imdb_id(&client, &api_key, kind, tmdb_id)
+                            .await
+                            .inspect_err(|e| tracing::warn!("IMDb id for TMDB {}: {}", tmdb_id, e))
+                            .ok()
+                            .flatten();
+                        (i, tv_details, imdb_id)
+                    });
+                }
+                while let Some(joined) = lookups.join_next().await {
+                    let Ok((i, tv_details, imdb_id)) = joined else {
+                        continue;
+                    };
+                    let item = &mut items_to_add[i];
+                    if let Some((seasons, episodes)) = tv_details {
+                        item.number_of_seasons = seasons;
+                        item.number_of_episodes = episodes;
+                        item.total_episodes = episodes;
+                    }
+                    item.imdb_id = imdb_id;
+                }
+            }
+
+            // The insert holds the db lock for the whole batch; keep it off
+            // the runtime's worker threads
+            let count = items_to_add.len();
+            let progress_thread = qt_thread.clone();
+            let inserted = tokio::task::spawn_blocking(move || {
+                let state = get_app_state();
+                let conn = state.db.lock().unwrap();
+                let on_progress =
+                    |done: usize| queue_batch_progress(&progress_thread, done as i32, count as i32, "saving");
+                db::queries::add_items_batch_with_progress(&conn, &items_to_add, !allow_duplicates, on_progress)
+            })
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|r| r.map_err(|e| e.to_string()));
+
+            let state = get_app_state();
+            match inserted {
+                Ok(result) => {
+                    invalidate_counts();
+                    let mut msg = if allow_duplicates {
+                        format!(
+                            "Added {} to {}, {} already in the library",
+                            result.added,
+                            status,
+                            result.forced_items.len()
+                        )
+                    } else {
+                        format!(
+                            "Added {} to {}, skipped {} duplicates",
+                            result.added, status, result.skipped
+                        )
+                    };
+                    if result.errors > 0 {
+                        msg.push_str(&format!(", {} failed", result.errors));
+                    }
+                    if posters_failed > 0 {
+                        msg.push_str(&format!(", {} poster(s) failed to download", posters_failed));
+                        tracing::warn!("{} poster download(s) failed while adding", posters_failed);
+                    }
+                    let toast_type = if posters_failed > 0 || result.errors > 0 { "warning" } else { "success" };
+                    // The history shows which ones were skipped, duplicated or failed and why
+                    let details = result
+                        .skipped_items
+                        .iter()
+                        .chain(&result.forced_items)
+                        .chain(&result.error_items)
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    *state.last_batch_report.lock().unwrap() = Some(result);
+                    qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+                        ctrl.as_mut().show_toast_with_details(QString::from(&msg), QString::from(toast_type), details);
+                        ctrl.as_mut().reload_items();
+                        ctrl.as_mut().reload_counts();
+                    }).unwrap();
+                }
+                Err(e) => {
+                    tracing::error!("Adding {} search result(s) failed: {}", count, e);
+                    let msg = format!("Error: {}", e);
+                    qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+                        ctrl.as_mut().show_toast(
+                            QString::from(&msg),
+                            QString::from("error"),
+                        );
+                    }).unwrap();
+                }
+            }
+        });
+    }
+
+    pub fn refetch_poster(mut self: Pin<&mut Self>, id: i32) {
+        mark_activity();
+        let state = get_app_state();
+        let item = {
+            let conn = state.db.lock().unwrap();
+            db::queries::get_item_by_id(&conn, id as i64)
+        };
+        let item = match item {
+            Ok(Some(item)) => item,
+            Ok(None) => {
+                self.as_mut().show_toast(QString::from("Item not found"), QString::from("error"));
+                return;
+            }
+            Err(e) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Error: {}", e)),
+                    QString::from("error"),
+                );
+                return;
+            }
+        };
+
+        let remote_url = item
+            .poster_url
+            .clone()
+            .filter(|u| u.starts_with("http://") || u.starts_with("https://"));
+        if remote_url.is_none() && item.tmdb_id.is_none() && item.anilist_id.is_none() {
+            self.as_mut().show_toast(
+                QString::from("No provider ID or poster URL for this item"),
+                QString::from("warning"),
+            );
+            return;
+        }
+
+        let (api_key, poster_size) = {
+            let cfg = state.config.lock().unwrap();
+            (cfg.tmdb_api_key.clone(), cfg.poster_size.clone())
+        };
+        let cache_dir = state.data_dir.join("image_cache");
+        let qt_thread = self.qt_thread();
+
+        spawn_async(async move {
+            let client = http_client();
+
+            let url = match remote_url {
+                Some(url) => Ok(Some(url)),
+                None => resolve_provider_poster(&client, &api_key, &poster_size, &item).await,
+            };
+
+            let result = match url {
+                Ok(Some(url)) => images::cache::recache_poster(&client, &cache_dir, &url).await,
+                Ok(None) => Err(ApiError::NotFound("Provider has no poster for this item".to_string())),
+                Err(e) => Err(e),
+            };
+
+            let outcome = result.map_err(|e| api_error_message(&e)).and_then(|poster| {
+                replace_item_poster(id as i64, item.poster_url.as_deref(), &poster, false)
+            });
+
+            qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+                match outcome {
+                    Ok(()) => {
+                        ctrl.as_mut().show_toast(
+                            QString::from("Poster updated"),
+                            QString::from("success"),
+                        );
+                        ctrl.as_mut().reload_items();
+                    }
+                    Err(e) => {
+                        ctrl.as_mut().show_toast(
+                            QString::from(&format!("Poster refetch failed: {}", e)),
+                            QString::from("error"),
+                        );
+                    }
+                }
+            }).unwrap();
+        });
+    }
+
+    pub fn set_poster_url(mut self: Pin<&mut Self>, id: i32, url: &QString) {
+        mark_activity();
+        let url = url.to_string().trim().to_string();
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            self.as_mut().show_toast(
+                QString::from("Poster URL must start with http:// or https://"),
+                QString::from("error"),
+            );
+            return;
+        }
+
+        let state = get_app_state();
+        let old_poster = {
+            let conn = state.db.lock().unwrap();
+            db::queries::get_item_by_id(&conn, id as i64)
+        };
+        let old_poster = match old_poster {
+            Ok(Some(item)) => item.poster_url,
+            Ok(None) => {
+                self.as_mut().show_toast(QString::from("Item not found"), QString::from("error"));
+                return;
+            }
+            Err(e) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Error: {}", e)),
+                    QString::from("error"),
+                );
+                return;
+            }
+        };
+
+        let cache_dir = state.data_dir.join("image_cache");
+        let qt_thread = self.qt_thread();
+
+        spawn_async(async move {
+            let client = http_client();
+
+            let outcome = images::cache::cache_poster(&client, &cache_dir, &url)
+                .await
+                .map_err(|e| api_error_message(&e))
+                .and_then(|poster| replace_item_poster(id as i64, old_poster.as_deref(), &poster, true));
+
+            qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+                match outcome {
+                    Ok(()) => {
+                        ctrl.as_mut().show_toast(
+                            QString::from("Poster updated"),
+                            QString::from("success"),
+                        );
+                        ctrl.as_mut().reload_items();
+                    }
+                    Err(e) => {
+                        ctrl.as_mut().show_toast(
+                            QString::from(&format!("Could not set poster: {}", e)),
+                            QString::from("error"),
+                        );
+                    }
+                }
+            }).unwrap();
+        });
+    }
+
+    pub fn refetch_missing_posters(mut self: Pin<&mut Self>) {
+        mark_activity();
+        if *self.refetching_posters() {
+            return;
+        }
+
+        let state = get_app_state();
+        let items = {
+            let conn = state.db.lock().unwrap();
+            db::queries::get_all_items(&conn).unwrap_or_default()
+        };
+
+        // Missing = no poster at all, or a local path whose file is gone.
+        // Remote URLs are loaded directly by QML so they don't count.
+        let missing: Vec<MediaItem> = items
+            .into_iter()
+            .filter(|item| match item.poster_url.as_deref() {
+                None | Some("") => true,
+                Some(url) if url.starts_with("http://") || url.starts_with("https://") => false,
+                Some(url) => images::cache::find_cached_poster(url, &state.data_dir).is_none(),
+            })
+            .collect();
+        let (candidates, no_provider): (Vec<MediaItem>, Vec<MediaItem>) = missing
+            .into_iter()
+            .partition(|item| item.tmdb_id.is_some() || item.anilist_id.is_some());
+        let skipped = no_provider.len();
+
+        if candidates.is_empty() {
+            self.as_mut().show_toast(
+                QString::from(&format!("No missing posters to fetch ({} without provider ID)", skipped)),
+                QString::from("info"),
+            );
+            return;
+        }
+
+        state.poster_refetch_cancel.store(false, Ordering::SeqCst);
+        self.as_mut().set_refetching_posters(true);
+
+        let total = candidates.len() as i32;
+        self.as_mut().poster_progress(0, total);
+
+        let (api_key, poster_size) = {
+            let cfg = state.config.lock().unwrap();
+            (cfg.tmdb_api_key.clone(), cfg.poster_size.clone())
+        };
+        let cache_dir = state.data_dir.join("image_cache");
+        let qt_thread = self.qt_thread();
+
+        spawn_async(async move {
+            let client = http_client();
+
+            let state = get_app_state();
+            let mut done = 0;
+            let mut fetched = 0;
+            let mut failed = 0;
+            let mut cancelled = false;
+
+            for chunk in candidates.chunks(POSTER_CHUNK_SIZE) {
+                if state.poster_refetch_cancel.load(Ordering::SeqCst) {
+                    cancelled = true;
+                    break;
+                }
+
+                let mut tasks = JoinSet::new();
+                for item in chunk {
+                    let client = client.clone();
+                    let api_key = api_key.clone();
+                    let poster_size = poster_size.clone();
+                    let cache_dir = cache_dir.clone();
+                    let item = item.clone();
+                    tasks.spawn(async move {
+                        let path = match resolve_provider_poster(&client, &api_key, &poster_size, &item).await {
+                            Ok(Some(url)) => images::cache::recache_poster(&client, &cache_dir, &url).await,
+                            Ok(None) => Err(ApiError::NotFound("No poster available".to_string())),
+                            Err(e) => Err(e),
+                        };
+                        (item.id, path)
+                    });
+                }
+
+                let mut updates = Vec::new();
+                while let Some(joined) = tasks.join_next().await {
+                    match joined {
+                        Ok((Some(id), Ok(poster))) => updates.push((
+                            id,
+                            images::cache::stored_poster_path(&poster.path, &state.data_dir),
+                            poster.width,
+                            poster.height,
+                        )),
+                        _ => failed += 1,
+                    }
+                }
+
+                let conn = state.db.lock().unwrap();
+                match db::queries::update_poster_urls_batch(&conn, &updates) {
+                    Ok(()) => fetched += updates.len(),
+                    Err(_) => failed += updates.len(),
+                }
+                drop(conn);
+
+                done += chunk.len() as i32;
+                qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+                    ctrl.as_mut().poster_progress(done, total);
+                }).unwrap();
+            }
+
+            let mut msg = format!(
+                "Fetched {} poster(s), {} failed, {} skipped without provider ID",
+                fetched, failed, skipped
+            );
+            if cancelled {
+                msg.push_str(" (cancelled)");
+            }
+            qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+                ctrl.as_mut().set_refetching_posters(false);
+                ctrl.as_mut().show_toast(
+                    QString::from(&msg),
+                    QString::from(if failed > 0 || cancelled { "warning" } else { "success" }),
+                );
+                ctrl.as_mut().reload_items();
+            }).unwrap();
+        });
+    }
+
+    pub fn cancel_poster_refetch(self: Pin<&mut Self>) {
+        if *self.refetching_posters() {
+            get_app_state().poster_refetch_cancel.store(true, Ordering::SeqCst);
+        }
+    }
+
+    pub fn get_log_file_path(&self) -> QString {
+        let state = get_app_state();
+        let path = crate::logging::current_log_file(&state.data_dir);
+        QString::from(&path.map(|p| p.display().to_string()).unwrap_or_default())
+    }
+
+    pub fn get_cache_stats(self: Pin<&mut Self>) {
+        let qt_thread = self.qt_thread();
+        std::thread::spawn(move || {
+            let state = get_app_state();
+            let referenced = {
+                let conn = state.db.lock().unwrap();
+                db::queries::get_all_poster_urls(&conn).unwrap_or_default()
+            };
+            let stats = images::cache::cache_stats(
+                &state.data_dir.join("image_cache"),
+                &referenced,
+                &state.data_dir,
+            );
+            let json = serde_json::to_string(&stats).unwrap_or_else(|_| "{}".into());
+            qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+                ctrl.as_mut().cache_stats_ready(QString::from(&json));
+            }).unwrap();
+        });
+    }
+
+    pub fn clear_image_cache(self: Pin<&mut Self>, orphans_only: bool) {
+        mark_activity();
+        let qt_thread = self.qt_thread();
+        std::thread::spawn(move || {
+            let state = get_app_state();
+            let cache_dir = state.data_dir.join("image_cache");
+            let referenced = {
+                let conn = state.db.lock().unwrap();
+                db::queries::get_all_poster_urls(&conn).unwrap_or_default()
+            };
+            let removed = images::cache::clear_cache(&cache_dir, &referenced, &state.data_dir, orphans_only);
+
+            let cleared_rows = if orphans_only {
+                Ok(0)
+            } else {
+                let conn = state.db.lock().unwrap();
+                db::queries::clear_local_poster_urls(&conn)
+            };
+
+            let referenced = {
+                let conn = state.db.lock().unwrap();
+                db::queries::get_all_poster_urls(&conn).unwrap_or_default()
+            };
+            let stats = images::cache::cache_stats(&cache_dir, &referenced, &state.data_dir);
+            let json = serde_json::to_string(&stats).unwrap_or_else(|_| "{}".into());
+            qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+                match cleared_rows {
+                    Ok(_) => {
+                        ctrl.as_mut().show_toast(
+                            QString::from(&format!("Removed {} cached image(s)", removed)),
+                            QString::from("success"),
+                        );
+                    }
+                    Err(e) => {
+                        ctrl.as_mut().show_toast(
+                            QString::from(&format!("Cache cleared but poster paths not reset: {}", e)),
+                            QString::from("error"),
+                        );
+                    }
+                }
+                ctrl.as_mut().cache_stats_ready(QString::from(&json));
+                ctrl.as_mut().reload_items();
+            }).unwrap();
+        });
+    }
+
+    pub fn normalize_titles(mut self: Pin<&mut Self>, title_case: bool) {
+        mark_activity();
+        let state = get_app_state();
+        let conn = state.db.lock().unwrap();
+        let result = db::queries::normalize_titles(&conn, title_case);
+        drop(conn);
+        match result {
+            Ok(0) => {
+                self.as_mut().show_toast(QString::from("All titles are already tidy"), QString::from("success"));
+            }
+            Ok(changed) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Normalized {} title(s)", changed)),
+                    QString::from("success"),
+                );
+                self.as_mut().reload_items();
+            }
+            Err(e) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Normalize failed: {}", e)),
+                    QString::from("error"),
+                );
+            }
+        }
+    }
+
+    pub fn save_settings(mut self: Pin<&mut Self>, api_key: &QString, use_keyring: bool, include_adult: bool, quality_types: &QString) {
+        mark_activity();
+        let state = get_app_state();
+        let mut cfg = state.config.lock().unwrap();
+        cfg.tmdb_api_key = api_key.to_string();
+        cfg.use_keyring = use_keyring;
+        // Also moves a plaintext key over the first time the flag is on
+        let keyring_error = config::secrets::store_api_key(&mut cfg).err();
+        cfg.include_adult = include_adult;
+        cfg.row_height = *self.row_height();
+        let mut new_types: Vec<String> = quality_types
+            .to_string()
+            .split('\n')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        // Removing a type that items still carry would orphan them, so keep
+        // those and report them instead
+        let mut in_use = serde_json::Map::new();
+        {
+            let conn = state.db.lock().unwrap();
+            let removed: Vec<String> = cfg
+                .quality_types
+                .iter()
+                .filter(|t| !new_types.contains(t))
+                .cloned()
+                .collect();
+            for old in removed {
+                let count = db::queries::count_items_with_quality_type(&conn, &old).unwrap_or(0);
+                if count > 0 {
+                    in_use.insert(old.clone(), count.into());
+                    new_types.push(old);
+                }
+            }
+        }
+        new_types.sort_by_cached_key(|s| s.to_lowercase());
+        cfg.quality_types = new_types;
+
+        match config::manager::save_config(&cfg, &state.config_path) {
+            Ok(_) => {
+                drop(cfg);
+                self.as_mut().set_tmdb_api_key(api_key.clone());
+                self.as_mut().set_use_keyring(use_keyring);
+                self.as_mut().set_include_adult(include_adult);
+                match keyring_error {
+                    Some(e) => {
+                        tracing::warn!("Could not update the system keyring: {}", e);
+                        let message = if use_keyring {
+                            "Settings saved, but the system keyring is unavailable; the API key was kept in config.json"
+                        } else {
+                            "Settings saved, but the API key couldn't be removed from the system keyring"
+                        };
+                        self.as_mut().show_toast(QString::from(message), QString::from("warning"));
+                    }
+                    None => {
+                        self.as_mut().show_toast(
+                            QString::from("Settings saved"),
+                            QString::from("success"),
+                        );
+                    }
+                }
+                if !in_use.is_empty() {
+                    let kept: Vec<String> = in_use.iter().map(|(t, count)| format!("{} ({})", t, count)).collect();
+                    self.as_mut().show_toast(
+                        QString::from(&format!("Kept quality types still in use: {}", kept.join(", "))),
+                        QString::from("warning"),
+                    );
+                    let json = serde_json::Value::Object(in_use).to_string();
+                    self.as_mut().quality_types_in_use(QString::from(&json));
+                }
+            }
+            Err(e) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Save failed: {}", e)),
+                    QString::from("error"),
+                );
+            }
+        }
+    }
+
+    pub fn test_api_key(self: Pin<&mut Self>, api_key: &QString) {
+        mark_activity();
+        let api_key = api_key.to_string().trim().to_string();
+        let qt_thread = self.qt_thread();
+        spawn_async(async move {
+            let result = if api_key.is_empty() {
+                Err(ApiError::Unauthorized("No API key entered".to_string()))
+            } else {
+                api::tmdb::test_api_key(&http_client(), &api_key).await
+            };
+            qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+                let (valid, message) = match result {
+                    Ok(()) => (true, "API key works".to_string()),
+                    Err(e) => (false, e.to_string()),
+                };
+                ctrl.as_mut().api_key_tested(valid, QString::from(&message));
+            }).unwrap();
+        });
+    }
+
+    pub fn get_quality_types(&self) -> QString {
+        let state = get_app_state();
+        let cfg = state.config.lock().unwrap();
+        QString::from(&cfg.quality_types.join("\n"))
+    }
+
+    pub fn get_quality_types_with_counts(&self) -> QString {
+        let state = get_app_state();
+        let configured = state.config.lock().unwrap().quality_types.clone();
+        let mut counts = {
+            let conn = state.db.lock().unwrap();
+            db::queries::get_quality_type_counts(&conn).unwrap_or_default()
+        };
+
+        let mut entries: Vec<serde_json::Value> = configured
+            .iter()
+            .map(|name| {
+                let count = counts.remove(name).unwrap_or(0);
+                serde_json::json!({ "name": name, "count": count, "unlisted": false })
+            })
+            .collect();
+        let mut unlisted: Vec<(String, i64)> = counts.into_iter().collect();
+        unlisted.sort_by_cached_key(|(name, _)| name.to_lowercase());
+        entries.extend(unlisted.into_iter().map(|(name, count)| {
+            serde_json::json!({ "name": name, "count": count, "unlisted": true })
+        }));
+        QString::from(&serde_json::Value::Array(entries).to_string())
+    }
+
+    pub fn rename_quality_type(mut self: Pin<&mut Self>, old_name: &QString, new_name: &QString) -> bool {
+        mark_activity();
+        let old_name = old_name.to_string();
+        let new_name = new_name.to_string().trim().to_string();
+        if new_name.is_empty() || new_name == old_name {
+            return false;
+        }
 
-        // Cache posters synchronously (they're small images, and we only
-        // download for the items actually being added)
-        let cache_dir = state.data_dir.join("image_cache");
-        let qt_thread = self.qt_thread();
+        let state = get_app_state();
+        let mut cfg = state.config.lock().unwrap();
+        // Case-only renames are fine; anything else matching another type isn't
+        if cfg.quality_types.iter().any(|t| *t != old_name && t.eq_ignore_ascii_case(&new_name)) {
+            drop(cfg);
+            self.as_mut().show_toast(
+                QString::from(&format!("A quality type named \"{}\" already exists", new_name)),
+                QString::from("error"),
+            );
+            return false;
+        }
 
-        std::thread::spawn(move || {
-            let rt = tokio::runtime::Runtime::new().unwrap();
-            rt.block_on(async {
-                let client = reqwest::Client::builder()
-                    .timeout(std::time::Duration::from_secs(15))
-                    .build()
-                    .unwrap_or_default();
-
-                for (i, url_opt) in poster_urls.iter().enumerate() {
-                    if let Some(url) = url_opt {
-                        if !url.is_empty() {
-                            if let Ok(path) = images::cache::cache_poster(&client, &cache_dir, url).await {
-                                let stored_path = path
-                                    .strip_prefix(&state.data_dir)
-                                    .map(|p| p.to_string_lossy().to_string())
-                                    .unwrap_or_else(|_| path.to_string_lossy().to_string());
-                                items_to_add[i].poster_url = Some(stored_path);
-                            }
-                        }
-                    }
-                }
+        let result = {
+            let conn = state.db.lock().unwrap();
+            retag_quality_type(&conn, &mut cfg, &state.config_path, &old_name, &new_name)
+        };
+        drop(cfg);
 
-                let state = get_app_state();
-                let conn = state.db.lock().unwrap();
-                match db::queries::add_items_batch(&conn, &items_to_add, true) {
-                    Ok(result) => {
-                        drop(conn);
-                        let msg = format!(
-                            "Added {}, skipped {} duplicates",
-                            result.added, result.skipped
-                        );
-                        qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
-                            ctrl.as_mut().toast_message(QString::from(&msg), QString::from("success"));
-                            ctrl.as_mut().reload_items();
-                            ctrl.as_mut().reload_counts();
-                        }).unwrap();
-                    }
-                    Err(e) => {
-                        drop(conn);
-                        let msg = format!("Error: {}", e);
-                        qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
-                            ctrl.as_mut().toast_message(
-                                QString::from(&msg),
-                                QString::from("error"),
-                            );
-                        }).unwrap();
-                    }
-                }
-            });
-        });
+        match result {
+            Ok(moved) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!(
+                        "Renamed \"{}\" to \"{}\" ({} item(s) updated)",
+                        old_name, new_name, moved
+                    )),
+                    QString::from("success"),
+                );
+                self.as_mut().reload_items();
+                true
+            }
+            Err(e) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Rename failed: {}", e)),
+                    QString::from("error"),
+                );
+                false
+            }
+        }
+    }
+
+    /// Configured statuses in display order. QML reads this so tab order and
+    /// the number hotkeys always match the Rust side.
+    pub fn get_status_options(&self) -> QString {
+        let state = get_app_state();
+        let cfg = state.config.lock().unwrap();
+        QString::from(&cfg.statuses.join("\n"))
     }
 
-    pub fn save_settings(mut self: Pin<&mut Self>, api_key: &QString, include_adult: bool, quality_types: &QString) {
+    pub fn get_status_counts_json(&self, media_type: &QString) -> QString {
         let state = get_app_state();
-        let mut cfg = state.config.lock().unwrap();
-        cfg.tmdb_api_key = api_key.to_string();
-        cfg.include_adult = include_adult;
-        cfg.row_height = *self.row_height();
-        cfg.quality_types = quality_types
+        let conn = state.db.lock().unwrap();
+        let media_type = media_type.to_string();
+        let filter = db::queries::ItemFilter { media_type: Some(&media_type), ..Default::default() };
+        let counts = db::queries::get_status_counts(&conn, &filter, None).unwrap_or_default();
+        QString::from(&serde_json::to_string(&counts).unwrap_or_else(|_| "{}".into()))
+    }
+
+    pub fn save_statuses(mut self: Pin<&mut Self>, csv: &QString) {
+        mark_activity();
+        let new_statuses: Vec<String> = csv
             .to_string()
-            .split('\n')
+            .split(',')
             .map(|s| s.trim().to_string())
             .filter(|s| !s.is_empty())
             .collect();
-        cfg.quality_types.sort_by_cached_key(|s| s.to_lowercase());
 
-        match config::manager::save_config(&cfg, &state.config_path) {
+        if new_statuses.is_empty() {
+            self.as_mut().show_toast(
+                QString::from("At least one status is required"),
+                QString::from("error"),
+            );
+            return;
+        }
+        let mut seen = std::collections::HashSet::new();
+        if let Some(dup) = new_statuses.iter().find(|s| !seen.insert(s.to_lowercase())) {
+            self.as_mut().show_toast(
+                QString::from(&format!("Duplicate status: {}", dup)),
+                QString::from("error"),
+            );
+            return;
+        }
+
+        let state = get_app_state();
+        let mut cfg = state.config.lock().unwrap();
+
+        // A missing name is a removal, never a rename: renames go through
+        // renameStatus so items are only moved when the user asked for it.
+        let in_use = {
+            let conn = state.db.lock().unwrap();
+            cfg.statuses
+                .iter()
+                .filter(|s| !new_statuses.contains(s))
+                .map(|s| (s.clone(), db::queries::count_items_with_status(&conn, s).unwrap_or(0)))
+                .find(|(_, count)| *count > 0)
+        };
+        if let Some((status, count)) = in_use {
+            drop(cfg);
+            self.as_mut().show_toast(
+                QString::from(&format!("Can't remove \"{}\": {} item(s) still use it", status, count)),
+                QString::from("error"),
+            );
+            return;
+        }
+
+        cfg.statuses = new_statuses.clone();
+        let saved = config::manager::save_config(&cfg, &state.config_path);
+        drop(cfg);
+
+        // Keep the active tab pointing at a status that still exists
+        let active = self.active_status().to_string();
+        if !new_statuses.contains(&active) {
+            self.as_mut().set_active_status(QString::from(&new_statuses[0]));
+        }
+
+        match saved {
             Ok(_) => {
-                self.as_mut().set_tmdb_api_key(api_key.clone());
-                self.as_mut().set_include_adult(include_adult);
-                self.as_mut().toast_message(
-                    QString::from("Settings saved"),
-                    QString::from("success"),
-                );
+                self.as_mut().show_toast(QString::from("Statuses saved"), QString::from("success"));
             }
             Err(e) => {
-                self.as_mut().toast_message(
+                self.as_mut().show_toast(
                     QString::from(&format!("Save failed: {}", e)),
                     QString::from("error"),
                 );
             }
         }
+        self.as_mut().statuses_changed();
+        self.as_mut().reload_items();
+        self.as_mut().reload_counts();
     }
 
-    pub fn get_quality_types(&self) -> QString {
+    pub fn rename_status(mut self: Pin<&mut Self>, old_name: &QString, new_name: &QString) {
+        mark_activity();
+        let old_name = old_name.to_string();
+        // Commas would break the comma-separated status lists
+        let new_name = new_name.to_string().replace(',', "").trim().to_string();
+        if new_name.is_empty() || new_name == old_name {
+            return;
+        }
+
         let state = get_app_state();
-        let cfg = state.config.lock().unwrap();
-        QString::from(&cfg.quality_types.join("\n"))
-    }
+        let mut cfg = state.config.lock().unwrap();
+        let Some(pos) = cfg.statuses.iter().position(|s| *s == old_name) else {
+            drop(cfg);
+            self.as_mut().show_toast(
+                QString::from(&format!("Unknown status: {}", old_name)),
+                QString::from("error"),
+            );
+            return;
+        };
+        // Case-only renames are fine; anything else matching another status isn't
+        if cfg.statuses.iter().enumerate().any(|(i, s)| i != pos && s.eq_ignore_ascii_case(&new_name)) {
+            drop(cfg);
+            self.as_mut().show_toast(
+                QString::from(&format!("A status named \"{}\" already exists", new_name)),
+                QString::from("error"),
+            );
+            return;
+        }
 
-    pub fn get_status_options(&self) -> QString {
-        QString::from("On Drive\nTo Download\nTo Work On")
+        let conn = state.db.lock().unwrap();
+        let migrated = move_status(&conn, &mut cfg, &state.config_path, pos, &new_name);
+        drop(conn);
+        drop(cfg);
+        let moved = match migrated {
+            Ok(moved) => moved,
+            Err(e) => {
+                self.as_mut().show_toast(
+                    QString::from(&format!("Status rename failed: {}", e)),
+                    QString::from("error"),
+                );
+                return;
+            }
+        };
+        invalidate_counts();
+
+        if self.active_status().to_string() == old_name {
+            self.as_mut().set_active_status(QString::from(&new_name));
+        }
+
+        self.as_mut().show_toast(
+            QString::from(&format!("Renamed \"{}\" to \"{}\" ({} item(s) moved)", old_name, new_name, moved)),
+            QString::from("success"),
+        );
+        self.as_mut().statuses_changed();
+        self.as_mut().reload_items();
+        self.as_mut().reload_counts();
     }
 
     pub fn load_config(mut self: Pin<&mut Self>) {
         let state = get_app_state();
+        *state.config_listener.lock().unwrap() = Some(self.qt_thread());
         let cfg = state.config.lock().unwrap();
         self.as_mut().set_view_mode(QString::from(&cfg.view_mode));
         self.as_mut().set_tmdb_api_key(QString::from(&cfg.tmdb_api_key));
+        self.as_mut().set_use_keyring(cfg.use_keyring);
         self.as_mut().set_include_adult(cfg.include_adult);
+        self.as_mut().set_poster_size(QString::from(&cfg.poster_size));
+        self.as_mut().set_tmdb_language(QString::from(&cfg.tmdb_language));
+        self.as_mut().set_tmdb_region(QString::from(&cfg.tmdb_region));
+        self.as_mut().set_tmdb_result_pages(cfg.tmdb_result_pages as i32);
+        self.as_mut().set_idle_maintenance(cfg.idle_maintenance);
+        self.as_mut().set_ignore_articles(cfg.ignore_articles);
+        self.as_mut().set_prefetch_search_posters(cfg.prefetch_search_posters);
+        self.as_mut().set_uniform_posters(cfg.uniform_posters);
         self.as_mut().set_row_height(if cfg.row_height > 0 { cfg.row_height } else { 44 });
-        self.as_mut().set_sort_field(QString::from("title"));
-        self.as_mut().set_sort_dir(QString::from("ASC"));
+        self.as_mut().set_sort_field(QString::from(&cfg.sort_field));
+        self.as_mut().set_sort_dir(QString::from(&cfg.sort_dir));
+        self.as_mut().set_sort_field2(QString::from(&cfg.sort_field2));
+        self.as_mut().set_sort_dir2(QString::from(&cfg.sort_dir2));
+        // Assume we're online until a search says otherwise
+        self.as_mut().set_online(true);
+        drop(cfg);
+
+        let warning = state.config_warning.lock().unwrap().take();
+        if let Some(warning) = warning {
+            self.as_mut().show_toast(QString::from(&warning), QString::from("warning"));
+        }
     }
 
     pub fn set_sort_order(mut self: Pin<&mut Self>, field: &QString, dir: &QString) {
+        mark_activity();
         self.as_mut().set_sort_field(field.clone());
         self.as_mut().set_sort_dir(dir.clone());
         self.as_mut().reload_items();
+        let state = get_app_state();
+        let mut cfg = state.config.lock().unwrap();
+        cfg.sort_field = field.to_string();
+        cfg.sort_dir = dir.to_string();
+        save_config_logged(&cfg, &state.config_path);
+    }
+
+    /// Secondary sort for rows that tie on the primary field (shift-click)
+    pub fn set_sort_order2(mut self: Pin<&mut Self>, field: &QString, dir: &QString) {
+        mark_activity();
+        self.as_mut().set_sort_field2(field.clone());
+        self.as_mut().set_sort_dir2(dir.clone());
+        self.as_mut().reload_items();
+        let state = get_app_state();
+        let mut cfg = state.config.lock().unwrap();
+        cfg.sort_field2 = field.to_string();
+        cfg.sort_dir2 = dir.to_string();
+        save_config_logged(&cfg, &state.config_path);
     }
 
     pub fn set_row_height_pref(mut self: Pin<&mut Self>, height: i32) {
+        mark_activity();
         let h = height.clamp(30, 200);
         self.as_mut().set_row_height(h);
         let state = get_app_state();
         let mut cfg = state.config.lock().unwrap();
         cfg.row_height = h;
-        let _ = config::manager::save_config(&cfg, &state.config_path);
+        save_config_logged(&cfg, &state.config_path);
     }
 
-    // ---- Internal helpers ----
+    pub fn set_poster_size_pref(mut self: Pin<&mut Self>, size: &QString) {
+        mark_activity();
+        let size = size.to_string();
+        if size.is_empty() {
+            return;
+        }
+        self.as_mut().set_poster_size(QString::from(&size));
+        let state = get_app_state();
+        let mut cfg = state.config.lock().unwrap();
+        cfg.poster_size = size;
+        save_config_logged(&cfg, &state.config_path);
+    }
 
-    fn reload_items(mut self: Pin<&mut Self>) {
-        let page = self.active_page().to_string();
-        let status = self.active_status().to_string();
-        let search = self.search_term().to_string();
+    pub fn set_tmdb_result_pages_pref(mut self: Pin<&mut Self>, pages: i32) {
+        mark_activity();
+        let pages = pages.clamp(1, api::tmdb::MAX_RESULT_PAGES as i32);
+        self.as_mut().set_tmdb_result_pages(pages);
+        let state = get_app_state();
+        let mut cfg = state.config.lock().unwrap();
+        cfg.tmdb_result_pages = pages as u32;
+        save_config_logged(&cfg, &state.config_path);
+    }
+
+    pub fn set_tmdb_locale_pref(mut self: Pin<&mut Self>, language: &QString, region: &QString) {
+        mark_activity();
+        let language = match language.to_string().trim() {
+            "" => "en-US".to_string(),
+            lang => lang.to_string(),
+        };
+        let region = region.to_string().trim().to_uppercase();
+        if !is_valid_language_tag(&language) || !(region.is_empty() || is_valid_region(&region)) {
+            self.as_mut().show_toast(
+                QString::from("Language must look like \"en\" or \"en-US\", region like \"US\""),
+                QString::from("error"),
+            );
+            return;
+        }
 
+        self.as_mut().set_tmdb_language(QString::from(&language));
+        self.as_mut().set_tmdb_region(QString::from(&region));
         let state = get_app_state();
-        let conn = state.db.lock().unwrap();
+        let mut cfg = state.config.lock().unwrap();
+        cfg.tmdb_language = language;
+        cfg.tmdb_region = region;
+        save_config_logged(&cfg, &state.config_path);
+    }
 
-        let search_opt = if search.is_empty() { None } else { Some(search.as_str()) };
-        let count = db::queries::count_filtered_items(
-            &conn, Some(&page), Some(&status), search_opt,
-        ).unwrap_or(0);
+    pub fn get_poster_sizes(&self) -> QString {
+        QString::from(&api::tmdb::cached_image_config().poster_sizes.join("\n"))
+    }
 
-        self.as_mut().set_item_count(count as i32);
+    pub fn set_idle_maintenance_pref(mut self: Pin<&mut Self>, enabled: bool) {
+        mark_activity();
+        self.as_mut().set_idle_maintenance(enabled);
+        let state = get_app_state();
+        let mut cfg = state.config.lock().unwrap();
+        cfg.idle_maintenance = enabled;
+        save_config_logged(&cfg, &state.config_path);
+    }
+
+    pub fn set_ignore_articles_pref(mut self: Pin<&mut Self>, enabled: bool) {
+        mark_activity();
+        if *self.ignore_articles() == enabled {
+            return;
+        }
+        self.as_mut().set_ignore_articles(enabled);
+        {
+            let state = get_app_state();
+            let mut cfg = state.config.lock().unwrap();
+            cfg.ignore_articles = enabled;
+            save_config_logged(&cfg, &state.config_path);
+        }
+        self.as_mut().reload_items();
+    }
+
+    pub fn set_uniform_posters_pref(mut self: Pin<&mut Self>, enabled: bool) {
+        mark_activity();
+        if *self.uniform_posters() == enabled {
+            return;
+        }
+        self.as_mut().set_uniform_posters(enabled);
+        {
+            let state = get_app_state();
+            let mut cfg = state.config.lock().unwrap();
+            cfg.uniform_posters = enabled;
+            save_config_logged(&cfg, &state.config_path);
+        }
+        self.as_mut().reload_items();
+    }
+
+    pub fn set_prefetch_search_posters_pref(mut self: Pin<&mut Self>, enabled: bool) {
+        mark_activity();
+        self.as_mut().set_prefetch_search_posters(enabled);
+        let state = get_app_state();
+        let mut cfg = state.config.lock().unwrap();
+        cfg.prefetch_search_posters = enabled;
+        save_config_logged(&cfg, &state.config_path);
+        drop(cfg);
+        if !enabled {
+            images::cache::clear_search_cache(&state.data_dir.join("image_cache"));
+        }
+    }
+
+    pub fn get_default_statuses(&self) -> QString {
+        let state = get_app_state();
+        let cfg = state.config.lock().unwrap();
+        let defaults: serde_json::Map<String, serde_json::Value> = MEDIA_TYPES
+            .iter()
+            .map(|media_type| (media_type.to_string(), serde_json::json!(cfg.default_status_for(media_type))))
+            .collect();
+        QString::from(&serde_json::Value::Object(defaults).to_string())
+    }
 
-        if let Ok(status_counts) = db::queries::get_status_counts(&conn, &page, search_opt) {
-            self.as_mut().set_on_drive_count(*status_counts.get("On Drive").unwrap_or(&0) as i32);
-            self.as_mut().set_to_download_count(*status_counts.get("To Download").unwrap_or(&0) as i32);
-            self.as_mut().set_to_work_on_count(*status_counts.get("To Work On").unwrap_or(&0) as i32);
+    pub fn set_default_status(self: Pin<&mut Self>, media_type: &QString, status: &QString) {
+        mark_activity();
+        let media_type = media_type.to_string();
+        let status = status.to_string();
+        if !MEDIA_TYPES.contains(&media_type.as_str()) {
+            return;
+        }
+        let state = get_app_state();
+        let mut cfg = state.config.lock().unwrap();
+        if status.is_empty() {
+            cfg.default_status_per_type.remove(&media_type);
+        } else if cfg.statuses.contains(&status) {
+            cfg.default_status_per_type.insert(media_type, status);
         } else {
-            self.as_mut().set_on_drive_count(0);
-            self.as_mut().set_to_download_count(0);
-            self.as_mut().set_to_work_on_count(0);
+            return;
         }
+        save_config_logged(&cfg, &state.config_path);
+    }
 
-        drop(conn);
+    pub fn get_maintenance_queue(&self) -> QString {
+        let state = get_app_state();
+        let names = state.maintenance.queued_names();
+        QString::from(&serde_json::to_string(&names).unwrap_or_else(|_| "[]".into()))
+    }
+
+    // ---- Internal helpers ----
+
+    /// Show a toast and keep it in the notification history
+    fn show_toast(self: Pin<&mut Self>, message: QString, toast_type: QString) {
+        self.show_toast_with_details(message, toast_type, String::new());
+    }
+
+    fn show_toast_with_details(mut self: Pin<&mut Self>, message: QString, toast_type: QString, details: String) {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as i64);
+        {
+            let state = get_app_state();
+            let mut notifications = state.notifications.lock().unwrap();
+            notifications.push_front(Notification {
+                timestamp,
+                toast_type: toast_type.to_string(),
+                message: message.to_string(),
+                details,
+                read: false,
+            });
+            notifications.truncate(NOTIFICATION_LIMIT);
+        }
+        self.as_mut().toast_message(message, toast_type);
+        self.as_mut().notifications_changed();
+    }
+
+    fn reload_items(mut self: Pin<&mut Self>) {
+        self.as_mut().refresh_filtered_counts();
 
         // Signal QML to reload MediaModel (which does its own query for the actual rows)
         self.as_mut().items_changed();
     }
 
-    fn reload_counts(mut self: Pin<&mut Self>) {
+    /// Like reload_items, but for a change limited to `ids` so the view can
+    /// keep its scroll position and delegate state
+    fn patch_items(mut self: Pin<&mut Self>, ids: &[i64]) {
+        self.as_mut().refresh_filtered_counts();
+        let csv: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        self.as_mut().items_patched(QString::from(&csv.join(",")));
+    }
+
+    /// Item count and per-status counts for the current page/search
+    fn refresh_filtered_counts(mut self: Pin<&mut Self>) {
+        if !self.recent_view().is_empty() {
+            // Every item qualifies; the view just caps how many it shows
+            let total: i64 = {
+                let state = get_app_state();
+                let conn = state.db.lock().unwrap();
+                db::queries::get_counts(&conn).map(|c| c.values().sum()).unwrap_or(0)
+            };
+            let shown = total.min(*self.recent_limit() as i64) as i32;
+            self.as_mut().set_item_count(shown);
+            self.as_mut().set_status_counts(QString::from("{}"));
+            return;
+        }
+
+        // The same view MediaModel::reload gets, so the counts can't drift
+        let view = self.current_view();
+        let filter = view.filter();
+        let search_opt = if view.search.is_empty() { None } else { Some(view.search.as_str()) };
+
         let state = get_app_state();
         let conn = state.db.lock().unwrap();
-        if let Ok(counts) = db::queries::get_counts(&conn) {
+        let (count, status_counts) = if let Some(has_poster) = filter.has_poster {
+            // Same disk check MediaModel applies after its query
+            let matching_statuses = |filter: &db::queries::ItemFilter| -> Vec<String> {
+                db::queries::get_item_posters(&conn, filter, search_opt)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|(_, url)| images::cache::poster_missing(url.as_deref(), &state.data_dir) != has_poster)
+                    .map(|(status, _)| status)
+                    .collect()
+            };
+            let count = matching_statuses(&filter).len() as i64;
+            // Like get_status_counts: tab counts ignore the status and favorites filters
+            let all_statuses = db::queries::ItemFilter { status: None, favorites_only: false, ..filter };
+            let mut status_counts = HashMap::new();
+            for status in matching_statuses(&all_statuses) {
+                *status_counts.entry(status).or_insert(0i64) += 1;
+            }
+            (count, status_counts)
+        } else {
+            (
+                db::queries::count_filtered_items(&conn, &filter, search_opt).unwrap_or(0),
+                db::queries::get_status_counts(&conn, &filter, search_opt).unwrap_or_default(),
+            )
+        };
+
+        self.as_mut().set_item_count(count as i32);
+
+        self.as_mut().set_status_counts(QString::from(
+            &serde_json::to_string(&status_counts).unwrap_or_else(|_| "{}".into()),
+        ));
+    }
+
+    /// Push per-type counts to QML, querying only when the cache was invalidated.
+    fn reload_counts(mut self: Pin<&mut Self>) {
+        let state = get_app_state();
+        let mut cache = state.counts_cache.lock().unwrap();
+        if cache.is_none() {
+            let conn = state.db.lock().unwrap();
+            *cache = db::queries::get_counts(&conn).ok();
+        }
+        if let Some(counts) = cache.as_ref() {
             self.as_mut().set_movie_count(*counts.get("Movie").unwrap_or(&0) as i32);
             self.as_mut().set_tv_count(*counts.get("TV").unwrap_or(&0) as i32);
             self.as_mut().set_anime_count(*counts.get("Anime").unwrap_or(&0) as i32);
+            self.as_mut().set_manga_count(*counts.get("Manga").unwrap_or(&0) as i32);
         }
+        drop(cache);
         self.as_mut().counts_changed();
     }
 }
 
+/// Drop cached per-type counts after rows are added, removed or retyped.
+fn invalidate_counts() {
+    *get_app_state().counts_cache.lock().unwrap() = None;
+}
+
+/// Record user activity so idle maintenance backs off.
+fn mark_activity() {
+    get_app_state().maintenance.touch();
+}
+
+/// "Title (Year)", plus any romaji/native titles that differ from the title
+/// when `alt_titles` is set and the item comes from AniList.
+fn item_text_line(item: &MediaItem, alt_titles: bool) -> String {
+    let mut line = match item.year {
+        Some(year) if year > 0 => format!("{} ({})", item.title, year),
+        _ => item.title.clone(),
+    };
+    if alt_titles && is_anilist_type(&item.media_type) {
+        let alts: Vec<&str> = [item.romaji_title.as_deref(), item.native_title.as_deref()]
+            .into_iter()
+            .flatten()
+            .map(str::trim)
+            .filter(|t| !t.is_empty() && *t != item.title)
+            .collect();
+        if !alts.is_empty() {
+            line.push_str(&format!(" — {}", alts.join(" / ")));
+        }
+    }
+    line
+}
+
+/// Rename a quality type in both the config and the items using it. The
+/// item update is only committed once the config is saved, so the two can't
+/// disagree. Returns the number of items retagged.
+fn retag_quality_type(
+    conn: &rusqlite::Connection,
+    cfg: &mut AppConfig,
+    config_path: &std::path::Path,
+    old_name: &str,
+    new_name: &str,
+) -> Result<usize, String> {
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let moved = db::queries::rename_quality_type(&tx, old_name, new_name).map_err(|e| e.to_string())?;
+
+    let previous = cfg.quality_types.clone();
+    cfg.quality_types.retain(|t| t != old_name);
+    cfg.quality_types.push(new_name.to_string());
+    cfg.quality_types.sort_by_cached_key(|s| s.to_lowercase());
+    if let Err(e) = config::manager::save_config(cfg, config_path) {
+        cfg.quality_types = previous;
+        return Err(e.to_string());
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(moved)
+}
+
+/// Rename the status at `pos` in both the config and the items using it,
+/// committing the item update only once the config is saved, like
+/// `retag_quality_type`. Returns the number of items moved.
+fn move_status(
+    conn: &rusqlite::Connection,
+    cfg: &mut AppConfig,
+    config_path: &std::path::Path,
+    pos: usize,
+    new_name: &str,
+) -> Result<usize, String> {
+    let old_name = cfg.statuses[pos].clone();
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let moved = db::queries::rename_status(&tx, &old_name, new_name).map_err(|e| e.to_string())?;
+
+    let previous = (cfg.statuses.clone(), cfg.default_status_per_type.clone());
+    cfg.statuses[pos] = new_name.to_string();
+    for status in cfg.default_status_per_type.values_mut() {
+        if *status == old_name {
+            *status = new_name.to_string();
+        }
+    }
+    if let Err(e) = config::manager::save_config(cfg, config_path) {
+        (cfg.statuses, cfg.default_status_per_type) = previous;
+        return Err(e.to_string());
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(moved)
+}
+
+/// TMDB's path segment ("movie"/"tv") for a media type it covers.
+fn tmdb_kind(media_type: &str) -> Option<&'static str> {
+    match media_type {
+        "Movie" => Some("movie"),
+        "TV" => Some("tv"),
+        _ => None,
+    }
+}
+
+/// Negative years mean "no bound" (0); a reversed range is swapped.
+fn year_range(min_year: i32, max_year: i32) -> (i32, i32) {
+    let (min_year, max_year) = (min_year.max(0), max_year.max(0));
+    if min_year > 0 && max_year > 0 && min_year > max_year {
+        (max_year, min_year)
+    } else {
+        (min_year, max_year)
+    }
+}
+
+/// Check a TMDB language tag: ISO 639-1, optionally with a region ("en", "pt-BR").
+fn is_valid_language_tag(s: &str) -> bool {
+    let (lang, region) = match s.split_once('-') {
+        Some((lang, region)) => (lang, Some(region)),
+        None => (s, None),
+    };
+    lang.len() == 2 && lang.bytes().all(|b| b.is_ascii_lowercase()) && region.into_iter().all(is_valid_region)
+}
+
+/// Check an ISO 3166-1 alpha-2 region code ("US").
+fn is_valid_region(s: &str) -> bool {
+    s.len() == 2 && s.bytes().all(|b| b.is_ascii_uppercase())
+}
+
+/// Check a `YYYY-MM-DD` date, including days per month.
+fn is_valid_date(s: &str) -> bool {
+    let parts: Vec<&str> = s.split('-').collect();
+    if parts.len() != 3
+        || parts[0].len() != 4
+        || parts[1].len() != 2
+        || parts[2].len() != 2
+        || !parts.iter().all(|p| p.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return false;
+    }
+    let (Ok(year), Ok(month), Ok(day)) = (
+        parts[0].parse::<u32>(),
+        parts[1].parse::<u32>(),
+        parts[2].parse::<u32>(),
+    ) else {
+        return false;
+    };
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days_in_month).contains(&day)
+}
+
+/// Replace a new item's fields with those given in an
+/// `addSearchResultWithDetails` object. A blank title is ignored; blank or
+/// null text fields and a null year clear the value.
+fn apply_result_overrides(item: &mut MediaItem, overrides: &serde_json::Value) {
+    if let Some(title) = overrides["title"].as_str().map(str::trim).filter(|t| !t.is_empty()) {
+        item.title = title.to_string();
+    }
+    if let Some(year) = overrides.get("year") {
+        item.year = year
+            .as_i64()
+            .or_else(|| year.as_str()?.trim().parse().ok())
+            .map(|y| y as i32);
+    }
+    let text = |key: &str| {
+        overrides[key]
+            .as_str()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    };
+    if overrides.get("quality_type").is_some() {
+        item.quality_type = text("quality_type");
+    }
+    if overrides.get("source").is_some() {
+        item.source = text("source");
+    }
+    if overrides.get("notes").is_some() {
+        item.notes = text("notes");
+    }
+}
+
 fn opt_string(s: &QString) -> Option<String> {
     let st = s.to_string();
     if st.is_empty() {
@@ -672,6 +3253,152 @@ fn opt_string(s: &QString) -> Option<String> {
 }
 
 
+/// Download search-result posters into `search_cache` so the results dialog
+/// shows local thumbnails. Results from a superseded search are dropped.
+async fn prefetch_search_posters(
+    client: &reqwest::Client,
+    urls: Vec<Option<String>>,
+    generation: usize,
+    qt_thread: &cxx_qt::CxxQtThread<qobject::AppController>,
+) {
+    let state = get_app_state();
+    let search_dir = state
+        .data_dir
+        .join("image_cache")
+        .join(images::cache::SEARCH_CACHE_DIR);
+
+    let semaphore = Arc::new(Semaphore::new(POSTER_DOWNLOAD_LIMIT));
+    let mut downloads = JoinSet::new();
+    for (i, url_opt) in urls.into_iter().enumerate() {
+        let Some(url) = url_opt.filter(|u| !u.is_empty()) else {
+            continue;
+        };
+        let client = client.clone();
+        let search_dir = search_dir.clone();
+        let semaphore = semaphore.clone();
+        downloads.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            if get_app_state().search_generation.load(Ordering::SeqCst) != generation {
+                return (i, None);
+            }
+            let cached = images::cache::cache_poster(&client, &search_dir, &url).await;
+            // A hit from an earlier search counts as a fresh use for eviction
+            let path = cached.ok().map(|poster| poster.path);
+            if let Some(path) = &path {
+                images::cache::mark_used(path);
+            }
+            (i, path)
+        });
+    }
+
+    while let Some(joined) = downloads.join_next().await {
+        let Ok((i, Some(path))) = joined else {
+            continue;
+        };
+        if !show_search_poster(i, path, generation, qt_thread) {
+            return;
+        }
+    }
+}
+
+/// Point search result `index` at a local poster (its thumbnail when there is
+/// one) and tell the results dialog. Returns false once `generation` has been
+/// superseded by a newer search, leaving the new results alone.
+fn show_search_poster(
+    index: usize,
+    path: PathBuf,
+    generation: usize,
+    qt_thread: &cxx_qt::CxxQtThread<qobject::AppController>,
+) -> bool {
+    let state = get_app_state();
+    let display = images::cache::thumbnail_path(&path)
+        .filter(|thumb| thumb.exists())
+        .unwrap_or(path);
+    {
+        let mut cached = state.cached_poster_paths.lock().unwrap();
+        if state.search_generation.load(Ordering::SeqCst) != generation {
+            return false;
+        }
+        if let Some(slot) = cached.get_mut(index) {
+            *slot = Some(images::cache::stored_poster_path(&display, &state.data_dir));
+        }
+    }
+    let index = index as i32;
+    qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+        ctrl.as_mut().search_posters_updated(index);
+    }).unwrap();
+    true
+}
+
+/// Emit `batchProgress` from a worker thread
+fn queue_batch_progress(
+    qt_thread: &cxx_qt::CxxQtThread<qobject::AppController>,
+    done: i32,
+    total: i32,
+    phase: &'static str,
+) {
+    qt_thread.queue(move |mut ctrl: Pin<&mut qobject::AppController>| {
+        ctrl.as_mut().batch_progress(done, total, QString::from(phase));
+    }).unwrap();
+}
+
+/// Toast text for an API error, with a hint when there's something the user
+/// can do about it
+fn api_error_message(e: &ApiError) -> String {
+    match e {
+        ApiError::Unauthorized(_) => format!("{}. Check your API key in Settings.", e),
+        ApiError::RateLimited(_) => format!("{}. Try again in a minute.", e),
+        ApiError::Network(_) => format!("{}. Check your internet connection.", e),
+        _ => e.to_string(),
+    }
+}
+
+/// Ask the item's provider for its current poster URL.
+async fn resolve_provider_poster(
+    client: &reqwest::Client,
+    api_key: &str,
+    poster_size: &str,
+    item: &MediaItem,
+) -> Result<Option<String>, ApiError> {
+    if is_anilist_type(&item.media_type) {
+        return match item.anilist_id {
+            Some(anilist_id) => api::anilist::get_cover_url(client, anilist_id).await,
+            None => Ok(None),
+        };
+    }
+
+    let Some(tmdb_id) = item.tmdb_id else {
+        return Ok(None);
+    };
+    if api_key.is_empty() {
+        return Err(ApiError::Unauthorized("TMDB API key not set".to_string()));
+    }
+    let kind = if item.media_type == "TV" { "tv" } else { "movie" };
+    api::tmdb::get_poster_url(client, api_key, kind, tmdb_id, poster_size).await
+}
+
+/// Point an item at a freshly cached poster, then drop its previous file
+/// unless that's the one just written or another row still points at it.
+fn replace_item_poster(
+    id: i64,
+    old: Option<&str>,
+    poster: &images::cache::CachedPoster,
+    manual: bool,
+) -> Result<(), String> {
+    let state = get_app_state();
+    let stored = images::cache::stored_poster_path(&poster.path, &state.data_dir);
+    let conn = state.db.lock().unwrap();
+    db::queries::update_poster_url(&conn, id, Some(&stored), poster.width, poster.height, manual)
+        .map_err(|e| e.to_string())?;
+
+    if let Some(old) = old {
+        if old != stored && db::queries::count_items_with_poster(&conn, old).unwrap_or(1) == 0 {
+            images::cache::delete_cached_poster(old, &state.data_dir);
+        }
+    }
+    Ok(())
+}
+
 fn normalize_poster_url_for_storage(value: &str, data_dir: &std::path::Path) -> String {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -683,8 +3410,5 @@ fn normalize_poster_url_for_storage(value: &str, data_dir: &std::path::Path) ->
     }
 
     let resolved = images::cache::resolve_cached_poster_path(trimmed, data_dir);
-    match resolved.strip_prefix(data_dir) {
-        Ok(rel) => rel.to_string_lossy().to_string(),
-        Err(_) => resolved.to_string_lossy().to_string(),
-    }
+    images::cache::stored_poster_path(&resolved, data_dir)
 }
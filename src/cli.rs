@@ -0,0 +1,209 @@
+//! Headless `--exec '<json>'` commands for scripting the library (e.g. from
+//! cron). Runs straight against the database and exits before Qt starts.
+//!
+//! Commands:
+//! - `{"cmd":"list","media_type":"Movie","status":"On Drive","quality":"Remux","source":"Disc","min_year":1950,"max_year":1969,"incomplete":true,"watched":false,"has_poster":false,"sort":"year","dir":"DESC","sort2":"title","dir2":"ASC"}`
+//! - `{"cmd":"search","term":"alien","media_type":"Movie","sort":"year","dir":"DESC"}`
+//! - `{"cmd":"get","id":12}`
+//! - `{"cmd":"counts"}`
+//! - `{"cmd":"move","ids":[1,2,3],"status":"To Work On"}`
+//! - `{"cmd":"import_json","path":"items.json","skip_duplicates":true}`
+//! - `{"cmd":"import_text","path":"wishlist.txt","media_type":"Movie","status":"To Download"}`
+//! - `{"cmd":"seed_demo","count":500}` (debug builds only)
+//!
+//! Prints `{"ok":true,"result":...}` or `{"ok":false,"error":"..."}`.
+
+use rusqlite::Connection;
+use serde_json::{json, Value};
+
+use crate::bridge::{get_data_dir, MEDIA_TYPES};
+use crate::db;
+use crate::db::queries::{ItemFilter, ItemSort};
+use crate::models::MediaItem;
+
+/// If `--exec <json>` was passed, run that command, print the JSON result and
+/// return the process exit code. Returns None when the GUI should start.
+pub fn run_from_args() -> Option<i32> {
+    let args: Vec<String> = std::env::args().collect();
+    let pos = args.iter().position(|a| a == "--exec")?;
+
+    let result = match args.get(pos + 1) {
+        Some(raw) => execute(raw),
+        None => Err("--exec requires a JSON command".to_string()),
+    };
+    let (output, code) = match result {
+        Ok(value) => (json!({ "ok": true, "result": value }), 0),
+        Err(e) => (json!({ "ok": false, "error": e }), 1),
+    };
+    println!("{}", output);
+    Some(code)
+}
+
+fn execute(raw: &str) -> Result<Value, String> {
+    let cmd: Value = serde_json::from_str(raw).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let name = cmd["cmd"].as_str().ok_or("Missing \"cmd\"")?;
+    let conn = db::connection::init_db(&get_data_dir()).map_err(|e| e.to_string())?;
+
+    match name {
+        "list" => list(&conn, &cmd),
+        "search" => search(&conn, &cmd),
+        "get" => get(&conn, &cmd),
+        "counts" => counts(&conn),
+        "move" => move_items(&conn, &cmd),
+        "import_json" => import_json(&conn, &cmd),
+        "import_text" => import_text(&conn, &cmd),
+        #[cfg(debug_assertions)]
+        "seed_demo" => seed_demo(&conn, &cmd),
+        other => Err(format!(
+            "Unknown command \"{}\" (expected list, search, get, counts, move, import_json or import_text)",
+            other
+        )),
+    }
+}
+
+fn media_type_arg(cmd: &Value) -> Result<Option<&str>, String> {
+    match cmd["media_type"].as_str() {
+        Some(mt) if !MEDIA_TYPES.contains(&mt) => Err(format!("Unknown media type: {}", mt)),
+        other => Ok(other),
+    }
+}
+
+/// Optional filter keys shared by `list` and `search`
+fn filter_arg(cmd: &Value) -> Result<ItemFilter<'_>, String> {
+    Ok(ItemFilter {
+        media_type: media_type_arg(cmd)?,
+        status: cmd["status"].as_str(),
+        favorites_only: cmd["favorites_only"].as_bool().unwrap_or(false),
+        quality: cmd["quality"].as_str(),
+        source: cmd["source"].as_str(),
+        min_year: cmd["min_year"].as_i64().map(|y| y as i32),
+        max_year: cmd["max_year"].as_i64().map(|y| y as i32),
+        has_poster: cmd["has_poster"].as_bool(),
+        incomplete: cmd["incomplete"].as_bool().unwrap_or(false),
+        watched: cmd["watched"].as_bool(),
+    })
+}
+
+/// Row order from the optional "sort"/"dir"/"sort2"/"dir2" keys; title
+/// ascending by default.
+fn sort_arg(cmd: &Value) -> Result<ItemSort<'_>, String> {
+    let loaded = crate::config::manager::load_config(&get_data_dir()).map_err(|e| e.to_string())?;
+    Ok(ItemSort {
+        field: cmd["sort"].as_str().unwrap_or("title"),
+        dir: cmd["dir"].as_str().unwrap_or("ASC"),
+        then_by: cmd["sort2"].as_str().map(|field| (field, cmd["dir2"].as_str().unwrap_or("ASC"))),
+        ignore_articles: loaded.config.ignore_articles,
+    })
+}
+
+fn list(conn: &Connection, cmd: &Value) -> Result<Value, String> {
+    let sort = sort_arg(cmd)?;
+    let filter = filter_arg(cmd)?;
+    let items = db::queries::query_items(conn, &filter, &sort).map_err(|e| e.to_string())?;
+    serde_json::to_value(with_poster_check(items, &filter)).map_err(|e| e.to_string())
+}
+
+/// Finish a `has_poster` filter with the file check SQL can't do.
+fn with_poster_check(mut items: Vec<MediaItem>, filter: &ItemFilter) -> Vec<MediaItem> {
+    if let Some(has_poster) = filter.has_poster {
+        let data_dir = get_data_dir();
+        items.retain(|item| {
+            crate::images::cache::poster_missing(item.poster_url.as_deref(), &data_dir) != has_poster
+        });
+    }
+    items
+}
+
+fn search(conn: &Connection, cmd: &Value) -> Result<Value, String> {
+    let term = cmd["term"].as_str().ok_or("Missing \"term\"")?;
+    let filter = filter_arg(cmd)?;
+    let sort = sort_arg(cmd)?;
+    let items = db::queries::search_items(conn, term, &filter, &sort).map_err(|e| e.to_string())?;
+    serde_json::to_value(with_poster_check(items, &filter)).map_err(|e| e.to_string())
+}
+
+fn get(conn: &Connection, cmd: &Value) -> Result<Value, String> {
+    let id = cmd["id"].as_i64().ok_or("Missing \"id\"")?;
+    match db::queries::get_item_by_id(conn, id).map_err(|e| e.to_string())? {
+        Some(item) => serde_json::to_value(item).map_err(|e| e.to_string()),
+        None => Err(format!("No item with id {}", id)),
+    }
+}
+
+fn counts(conn: &Connection) -> Result<Value, String> {
+    let mut by_type = serde_json::Map::new();
+    for media_type in MEDIA_TYPES {
+        let filter = ItemFilter { media_type: Some(media_type), ..Default::default() };
+        let counts = db::queries::get_status_counts(conn, &filter, None).map_err(|e| e.to_string())?;
+        by_type.insert(media_type.to_string(), json!(counts));
+    }
+    Ok(Value::Object(by_type))
+}
+
+fn move_items(conn: &Connection, cmd: &Value) -> Result<Value, String> {
+    let status = cmd["status"].as_str().ok_or("Missing \"status\"")?;
+    let loaded = crate::config::manager::load_config(&get_data_dir()).map_err(|e| e.to_string())?;
+    if !loaded.config.statuses.iter().any(|s| s == status) {
+        return Err(format!("Unknown status: {}", status));
+    }
+    let ids: Vec<i64> = cmd["ids"]
+        .as_array()
+        .ok_or("Missing \"ids\"")?
+        .iter()
+        .filter_map(|v| v.as_i64())
+        .collect();
+    let moved = db::queries::move_items(conn, &ids, status).map_err(|e| e.to_string())?;
+    Ok(json!({ "moved": moved, "not_found": ids.len().saturating_sub(moved) }))
+}
+
+#[cfg(debug_assertions)]
+fn seed_demo(conn: &Connection, cmd: &Value) -> Result<Value, String> {
+    let count = cmd["count"].as_u64().unwrap_or(200) as usize;
+    let loaded = crate::config::manager::load_config(&get_data_dir()).map_err(|e| e.to_string())?;
+    let result = db::seed::seed_demo_data(conn, count, &loaded.config.statuses).map_err(|e| e.to_string())?;
+    Ok(json!({ "added": result.added, "errors": result.errors }))
+}
+
+/// Import a JSON array of items (same shape `list` prints; `id` and the
+/// timestamps are ignored).
+fn import_json(conn: &Connection, cmd: &Value) -> Result<Value, String> {
+    let path = cmd["path"].as_str().ok_or("Missing \"path\"")?;
+    let data = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let items: Vec<MediaItem> =
+        serde_json::from_str(&data).map_err(|e| format!("Invalid item list: {}", e))?;
+    if let Some(bad) = items.iter().find(|i| !MEDIA_TYPES.contains(&i.media_type.as_str())) {
+        return Err(format!("{}: unknown media type {}", bad.title, bad.media_type));
+    }
+
+    let skip_duplicates = cmd["skip_duplicates"].as_bool().unwrap_or(true);
+    let result = db::queries::add_items_batch(conn, &items, skip_duplicates).map_err(|e| e.to_string())?;
+    serde_json::to_value(result).map_err(|e| e.to_string())
+}
+
+/// Import a `Title (Year)` per line wishlist, looking each title up online.
+fn import_text(conn: &Connection, cmd: &Value) -> Result<Value, String> {
+    let path = cmd["path"].as_str().ok_or("Missing \"path\"")?;
+    let media_type = media_type_arg(cmd)?.ok_or("Missing \"media_type\"")?;
+    let loaded = crate::config::manager::load_config(&get_data_dir()).map_err(|e| e.to_string())?;
+    let status = match cmd["status"].as_str() {
+        Some(status) if !loaded.config.statuses.iter().any(|s| s == status) => {
+            return Err(format!("Unknown status: {}", status));
+        }
+        Some(status) => status.to_string(),
+        None if loaded.config.statuses.is_empty() => return Err("No statuses configured".to_string()),
+        None => loaded.config.default_status_for(media_type),
+    };
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| e.to_string())?;
+    let client = crate::api::new_client();
+    let result = rt.block_on(crate::export::import_text_list(
+        conn,
+        &client,
+        std::path::Path::new(path),
+        media_type,
+        &status,
+    ))?;
+    serde_json::to_value(result).map_err(|e| e.to_string())
+}
@@ -1,23 +1,129 @@
+use crate::config::secrets;
 use crate::models::AppConfig;
-use std::path::Path;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
-pub fn load_config(data_dir: &Path) -> Result<(AppConfig, std::path::PathBuf), Box<dyn std::error::Error>> {
+/// Settings read by `load_config`
+pub struct LoadedConfig {
+    pub config: AppConfig,
+    pub path: PathBuf,
+    /// Why config.json couldn't be parsed. The file is left as is; the
+    /// settings then come from config.json.bak, or the defaults.
+    pub parse_error: Option<String>,
+    /// Settings came from config.json.bak (only set with `parse_error`)
+    pub recovered_from_backup: bool,
+    /// Why the API key couldn't be read from the system keyring
+    pub keyring_error: Option<String>,
+}
+
+/// Hash of the config.json contents last read or written here, so the
+/// watcher can tell our own saves from outside edits. `save_config` holds
+/// the lock for its whole write-and-rename, so the watcher never reads the
+/// file halfway through one of our saves.
+static KNOWN_CONTENT: Mutex<Option<u64>> = Mutex::new(None);
+
+fn content_hash(data: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Record `data` as the file's current contents; false if it already was
+fn remember_content(data: &str) -> bool {
+    let hash = content_hash(data);
+    KNOWN_CONTENT.lock().unwrap().replace(hash) != Some(hash)
+}
+
+fn backup_path(config_path: &Path) -> PathBuf {
+    config_path.with_extension("json.bak")
+}
+
+fn read_config(path: &Path) -> Option<AppConfig> {
+    let data = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+pub fn load_config(data_dir: &Path) -> Result<LoadedConfig, Box<dyn std::error::Error>> {
     let config_path = data_dir.join("config.json");
     if config_path.exists() {
         let data = std::fs::read_to_string(&config_path)?;
-        let config: AppConfig = serde_json::from_str(&data).unwrap_or_default();
-        Ok((config, config_path))
+        remember_content(&data);
+        let (mut config, parse_error, recovered_from_backup) = match serde_json::from_str(&data) {
+            Ok(config) => (config, None, false),
+            Err(e) => match read_config(&backup_path(&config_path)) {
+                Some(config) => (config, Some(e.to_string()), true),
+                None => (AppConfig::default(), Some(e.to_string()), false),
+            },
+        };
+        let keyring_error = secrets::load_api_key(&mut config);
+        Ok(LoadedConfig { config, path: config_path, parse_error, recovered_from_backup, keyring_error })
     } else {
         let config = AppConfig::default();
         std::fs::create_dir_all(data_dir)?;
         let data = serde_json::to_string_pretty(&config)?;
+        remember_content(&data);
         std::fs::write(&config_path, data)?;
-        Ok((config, config_path))
+        Ok(LoadedConfig {
+            config,
+            path: config_path,
+            parse_error: None,
+            recovered_from_backup: false,
+            keyring_error: None,
+        })
     }
 }
 
+/// Write to a temp file and rename it over config.json, so an interrupted
+/// save never leaves a half-written file. The previous version is kept as
+/// config.json.bak, but only if it still parses: a corrupt file never
+/// replaces a good backup and is moved to config.json.invalid instead, so
+/// it can still be inspected. A key kept in the keyring is written as
+/// `secrets::KEYRING_PLACEHOLDER`.
 pub fn save_config(config: &AppConfig, config_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    let data = serde_json::to_string_pretty(config)?;
-    std::fs::write(config_path, data)?;
+    let data = if config.api_key_in_keyring {
+        let mut on_disk = config.clone();
+        on_disk.tmdb_api_key = secrets::KEYRING_PLACEHOLDER.to_string();
+        serde_json::to_string_pretty(&on_disk)?
+    } else {
+        serde_json::to_string_pretty(config)?
+    };
+    let mut known = KNOWN_CONTENT.lock().unwrap();
+    let tmp_path = config_path.with_extension("json.tmp");
+    {
+        let mut file = std::fs::File::create(&tmp_path)?;
+        file.write_all(data.as_bytes())?;
+        file.sync_all()?;
+    }
+
+    if read_config(config_path).is_some() {
+        std::fs::copy(config_path, backup_path(config_path))?;
+    } else if config_path.exists() {
+        std::fs::rename(config_path, config_path.with_extension("json.invalid"))?;
+    }
+    std::fs::rename(&tmp_path, config_path)?;
+    *known = Some(content_hash(&data));
     Ok(())
 }
+
+/// Settings from config.json if it changed since we last read or wrote it.
+/// None when it didn't, or when it doesn't parse (likely an edit still in
+/// progress; the next save of it is picked up).
+pub fn read_external_change(config_path: &Path) -> Option<AppConfig> {
+    let data = {
+        let mut known = KNOWN_CONTENT.lock().unwrap();
+        let data = std::fs::read_to_string(config_path).ok()?;
+        let hash = content_hash(&data);
+        if known.replace(hash) == Some(hash) {
+            return None;
+        }
+        data
+    };
+    let mut config: AppConfig = serde_json::from_str(&data).ok()?;
+    if let Some(e) = secrets::load_api_key(&mut config) {
+        tracing::warn!("Could not read the API key from the system keyring: {}", e);
+    }
+    Some(config)
+}
@@ -1 +1,3 @@
 pub mod manager;
+pub mod secrets;
+pub mod watcher;
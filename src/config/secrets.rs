@@ -0,0 +1,74 @@
+//! The TMDB API key in the system keyring (Secret Service, Windows
+//! Credential Manager or the macOS Keychain), for `AppConfig::use_keyring`.
+
+use crate::models::AppConfig;
+
+const SERVICE: &str = "media-tracker";
+const USER: &str = "tmdb_api_key";
+
+/// What config.json holds in place of a key kept in the keyring
+pub const KEYRING_PLACEHOLDER: &str = "<stored in system keyring>";
+
+fn entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(SERVICE, USER).map_err(|e| e.to_string())
+}
+
+/// The stored key, or None if the keyring works but has no entry
+pub fn read_api_key() -> Result<Option<String>, String> {
+    match entry()?.get_password() {
+        Ok(key) => Ok(Some(key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Store the key; an empty key removes the entry
+fn write_api_key(key: &str) -> Result<(), String> {
+    let entry = entry()?;
+    let result = if key.is_empty() { entry.delete_credential() } else { entry.set_password(key) };
+    match result {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// With `use_keyring`, swap the key read from config.json for the keyring's.
+/// A plaintext key stays in use when the keyring has no entry yet (it moves
+/// over on the next settings save) or can't be reached; the latter is
+/// returned as an error message.
+pub fn load_api_key(config: &mut AppConfig) -> Option<String> {
+    if !config.use_keyring {
+        return None;
+    }
+    let stored_in_keyring = config.tmdb_api_key == KEYRING_PLACEHOLDER;
+    if stored_in_keyring {
+        // Never hand the placeholder out as a key, and keep it on disk
+        config.tmdb_api_key.clear();
+        config.api_key_in_keyring = true;
+    }
+    match read_api_key() {
+        Ok(Some(key)) => {
+            config.tmdb_api_key = key;
+            config.api_key_in_keyring = true;
+            None
+        }
+        Ok(None) => None,
+        Err(e) => Some(e),
+    }
+}
+
+/// Bring the keyring in line with `config` before it's saved: with
+/// `use_keyring` the key goes into the keyring and config.json only gets the
+/// placeholder; without it a key stored earlier is removed. On failure the
+/// key is kept in config.json instead.
+pub fn store_api_key(config: &mut AppConfig) -> Result<(), String> {
+    if config.use_keyring {
+        let result = write_api_key(&config.tmdb_api_key);
+        config.api_key_in_keyring = result.is_ok();
+        result
+    } else if std::mem::take(&mut config.api_key_in_keyring) {
+        write_api_key("")
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+//! Picks up edits made to config.json while the app is running.
+
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::config::manager;
+use crate::models::AppConfig;
+
+/// Editors often save in several steps (truncate, write, rename); wait for
+/// them to finish before reading the file
+const SETTLE_DELAY: Duration = Duration::from_millis(300);
+
+/// Watch `config_path` on a background thread and call `on_change` with the
+/// new settings whenever the file is changed by something other than
+/// `save_config`. Problems starting the watcher are logged; the app works
+/// the same without it.
+pub fn start(config_path: PathBuf, on_change: impl Fn(AppConfig) + Send + 'static) {
+    std::thread::spawn(move || {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                tracing::warn!("Config watcher unavailable: {}", e);
+                return;
+            }
+        };
+        // Saves replace the file by renaming over it, so watch the directory
+        let Some(dir) = config_path.parent() else {
+            return;
+        };
+        if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+            tracing::warn!("Could not watch {}: {}", dir.display(), e);
+            return;
+        }
+
+        while let Ok(event) = rx.recv() {
+            let Ok(event) = event else {
+                continue;
+            };
+            let touches_config = event.paths.iter().any(|p| p.file_name() == config_path.file_name());
+            if !touches_config || !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                continue;
+            }
+            std::thread::sleep(SETTLE_DELAY);
+            while rx.try_recv().is_ok() {}
+
+            if let Some(config) = manager::read_external_change(&config_path) {
+                tracing::info!("Reloading {} after an outside edit", config_path.display());
+                on_change(config);
+            }
+        }
+    });
+}
@@ -1,10 +1,15 @@
-use rusqlite::Connection;
+use rusqlite::{params, Connection};
+
+/// Stored in `PRAGMA user_version`; bump when adding a one-time data migration.
+const SCHEMA_VERSION: i32 = 2;
 
 pub fn init_db(data_dir: &std::path::Path) -> Result<Connection, Box<dyn std::error::Error>> {
     std::fs::create_dir_all(data_dir)?;
     let db_path = data_dir.join("media_tracker.db");
+    tracing::info!("Opening database {}", db_path.display());
     let conn = Connection::open(db_path)?;
     conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA busy_timeout=5000;")?;
+    super::fold::register(&conn)?;
     run_migrations(&conn)?;
     Ok(conn)
 }
@@ -29,7 +34,125 @@ fn run_migrations(conn: &Connection) -> Result<(), rusqlite::Error> {
             updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
         );
         CREATE INDEX IF NOT EXISTS idx_media_type_status ON media_items(media_type, status);
-        CREATE INDEX IF NOT EXISTS idx_title ON media_items(title);",
+        CREATE INDEX IF NOT EXISTS idx_title_nocase ON media_items(title COLLATE NOCASE);",
+    )?;
+
+    // Columns added after the initial schema
+    add_column_if_missing(conn, "number_of_seasons", "INTEGER")?;
+    add_column_if_missing(conn, "number_of_episodes", "INTEGER")?;
+    add_column_if_missing(conn, "progress", "INTEGER")?;
+    add_column_if_missing(conn, "total_episodes", "INTEGER")?;
+    add_column_if_missing(conn, "favorite", "BOOLEAN NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "acquired_date", "TEXT")?;
+    add_column_if_missing(conn, "watched_date", "TEXT")?;
+    add_column_if_missing(conn, "poster_width", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "poster_height", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "sort_title", "TEXT")?;
+    add_column_if_missing(conn, "imdb_id", "TEXT")?;
+    add_column_if_missing(conn, "anime_format", "TEXT")?;
+    add_column_if_missing(conn, "season", "TEXT")?;
+    add_column_if_missing(conn, "watched", "BOOLEAN NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "poster_manual", "BOOLEAN NOT NULL DEFAULT 0")?;
+
+    // query_items always filters on (media_type, status) and orders by one
+    // whitelisted column, so each sortable column gets a composite index with the
+    // filter prefix. SQLite can then walk the index in order instead of scanning
+    // the table and sorting. idx_media_type_status stays for the count queries.
+    // Titles sort case-insensitively, so their indexes use NOCASE to match the
+    // ORDER BY; the BINARY ones they replace are dropped. The timestamp indexes
+    // back the cross-type "recently added/updated" view.
+    conn.execute_batch(
+        "DROP INDEX IF EXISTS idx_title;
+        DROP INDEX IF EXISTS idx_type_status_title;
+        CREATE INDEX IF NOT EXISTS idx_type_status_title_nocase
+            ON media_items(media_type, status, title COLLATE NOCASE);
+        CREATE INDEX IF NOT EXISTS idx_type_status_sort_title
+            ON media_items(media_type, status, sort_title COLLATE NOCASE);
+        CREATE INDEX IF NOT EXISTS idx_type_status_year ON media_items(media_type, status, year);
+        CREATE INDEX IF NOT EXISTS idx_type_status_quality ON media_items(media_type, status, quality_type);
+        CREATE INDEX IF NOT EXISTS idx_type_status_source ON media_items(media_type, status, source);
+        CREATE INDEX IF NOT EXISTS idx_type_status_seasons ON media_items(media_type, status, number_of_seasons);
+        CREATE INDEX IF NOT EXISTS idx_type_status_acquired ON media_items(media_type, status, acquired_date);
+        CREATE INDEX IF NOT EXISTS idx_created_at ON media_items(created_at);
+        CREATE INDEX IF NOT EXISTS idx_updated_at ON media_items(updated_at);",
     )?;
+
+    let version: i32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    tracing::info!("Database schema version {} (current {})", version, SCHEMA_VERSION);
+    if version < 1 {
+        tracing::info!("Migrating poster paths to relative paths");
+        relativize_poster_paths(conn)?;
+    }
+    if version < 2 {
+        tracing::info!("Backfilling sort titles");
+        backfill_sort_titles(conn)?;
+    }
+    if version < SCHEMA_VERSION {
+        conn.execute_batch(&format!("PRAGMA user_version = {}", SCHEMA_VERSION))?;
+    }
     Ok(())
 }
+
+/// Older versions stored absolute poster paths, which broke every poster once
+/// the app or data folder moved. Rewrite anything inside an `image_cache`
+/// folder (either separator) to `image_cache/...`, which resolves against
+/// whatever the data dir is at display time.
+fn relativize_poster_paths(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let rows: Vec<(i64, String)> = conn
+        .prepare(
+            "SELECT id, poster_url FROM media_items
+             WHERE poster_url IS NOT NULL
+               AND poster_url NOT LIKE 'http://%' AND poster_url NOT LIKE 'https://%'",
+        )?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+
+    let tx = conn.unchecked_transaction()?;
+    for (id, path) in rows {
+        let segments: Vec<&str> = path.split(['/', '\\']).collect();
+        let Some(start) = segments.iter().rposition(|s| *s == "image_cache") else {
+            continue;
+        };
+        let relative = segments[start..].join("/");
+        if relative != path {
+            tx.execute("UPDATE media_items SET poster_url = ?1 WHERE id = ?2", params![relative, id])?;
+        }
+    }
+    tx.commit()
+}
+
+/// Fill `sort_title` for rows added before the column existed.
+fn backfill_sort_titles(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let rows: Vec<(i64, String)> = conn
+        .prepare("SELECT id, title FROM media_items")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+
+    let tx = conn.unchecked_transaction()?;
+    for (id, title) in rows {
+        tx.execute(
+            "UPDATE media_items SET sort_title = ?1 WHERE id = ?2",
+            params![crate::db::queries::sort_title(&title), id],
+        )?;
+    }
+    tx.commit()
+}
+
+fn add_column_if_missing(conn: &Connection, column: &str, decl: &str) -> Result<(), rusqlite::Error> {
+    let exists = conn
+        .prepare("SELECT 1 FROM pragma_table_info('media_items') WHERE name = ?1")?
+        .exists([column])?;
+    if !exists {
+        conn.execute_batch(&format!("ALTER TABLE media_items ADD COLUMN {} {}", column, decl))?;
+    }
+    Ok(())
+}
+
+/// Fresh in-memory database with the full schema, for tests.
+#[cfg(test)]
+pub fn open_in_memory() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    super::fold::register(&conn).unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
@@ -0,0 +1,124 @@
+//! Accent-, case- and width-insensitive text for local search. Registered as
+//! the SQL function `fold()` so queries can compare `fold(title) LIKE ?` with
+//! a pattern folded the same way.
+
+use rusqlite::functions::FunctionFlags;
+use rusqlite::Connection;
+
+/// Accented lowercase Latin letters and the plain letter each folds to
+const LATIN_FOLDS: [(&str, char); 19] = [
+    ("àáâãäåāăąǎ", 'a'),
+    ("çćĉċč", 'c'),
+    ("ďđ", 'd'),
+    ("èéêëēĕėęěẽ", 'e'),
+    ("ĝğġģ", 'g'),
+    ("ĥħ", 'h'),
+    ("ìíîïĩīĭįıǐ", 'i'),
+    ("ĵ", 'j'),
+    ("ķ", 'k'),
+    ("ĺļľŀł", 'l'),
+    ("ñńņňŉ", 'n'),
+    ("òóôõöøōŏőǒ", 'o'),
+    ("ŕŗř", 'r'),
+    ("śŝşšș", 's'),
+    ("ţťŧț", 't'),
+    ("ùúûüũūŭůűųǔ", 'u'),
+    ("ŵ", 'w'),
+    ("ýÿŷ", 'y'),
+    ("źżž", 'z'),
+];
+
+/// Lowercase `text`, strip Latin diacritics and turn full-width ASCII into
+/// plain ASCII, so "Pokémon", "POKEMON" and "Ｐｏｋｅｍｏｎ" all fold to "pokemon".
+pub fn fold(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match c {
+            c if c.is_ascii() => out.push(c),
+            // Full-width forms (common in Japanese titles) and the ideographic space
+            '\u{FF01}'..='\u{FF5E}' => out.extend(char::from_u32(c as u32 - 0xFEE0)),
+            '\u{3000}' => out.push(' '),
+            // Combining accents from already-decomposed input
+            '\u{0300}'..='\u{036F}' => {}
+            'ß' => out.push_str("ss"),
+            'æ' => out.push_str("ae"),
+            'œ' => out.push_str("oe"),
+            'þ' => out.push_str("th"),
+            c => match LATIN_FOLDS.iter().find(|(accented, _)| accented.contains(c)) {
+                Some((_, plain)) => out.push(*plain),
+                None => out.push(c),
+            },
+        }
+    }
+    out
+}
+
+/// Byte range of the part of `text` whose folded form contains
+/// `folded_term` (already passed through `fold`), for highlighting a hit.
+pub fn find_folded(text: &str, folded_term: &str) -> Option<std::ops::Range<usize>> {
+    if folded_term.is_empty() {
+        return None;
+    }
+    // Folding can change lengths ("ß" -> "ss"), so remember which original
+    // char every folded byte came from
+    let mut folded = String::with_capacity(text.len());
+    let mut origin = Vec::with_capacity(text.len());
+    for (pos, c) in text.char_indices() {
+        let piece = fold(c.encode_utf8(&mut [0; 4]));
+        origin.extend(std::iter::repeat_n(pos, piece.len()));
+        folded.push_str(&piece);
+    }
+    let start = folded.find(folded_term)?;
+    let last = origin[start + folded_term.len() - 1];
+    let last_len = text[last..].chars().next().map_or(0, char::len_utf8);
+    Some(origin[start]..last + last_len)
+}
+
+/// Make `fold(text)` callable from SQL on `conn`. NULL stays NULL.
+pub fn register(conn: &Connection) -> rusqlite::Result<()> {
+    conn.create_scalar_function(
+        "fold",
+        1,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |ctx| Ok(ctx.get::<Option<String>>(0)?.map(|s| fold(&s))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_case_accents_and_width() {
+        assert_eq!(fold("Pokémon"), "pokemon");
+        assert_eq!(fold("POKÉMON"), "pokemon");
+        assert_eq!(fold("Ｐｏｋｅｍｏｎ"), "pokemon");
+        // Decomposed "é" (e + combining acute)
+        assert_eq!(fold("Poke\u{301}mon"), "pokemon");
+        assert_eq!(fold("Straße"), "strasse");
+        assert_eq!(fold("進撃の巨人"), "進撃の巨人");
+    }
+
+    #[test]
+    fn find_folded_maps_back_to_original_bytes() {
+        let text = "Pokémon: The Movie";
+        let range = find_folded(text, &fold("pokemon")).unwrap();
+        assert_eq!(&text[range], "Pokémon");
+
+        let text = "Ｐｏｋｅｍｏｎ";
+        let range = find_folded(text, "kemon").unwrap();
+        assert_eq!(&text[range], "ｋｅｍｏｎ");
+
+        // "ß" folds to two bytes; a hit on either half covers the whole char
+        let text = "Die Straße";
+        let range = find_folded(text, "strasse").unwrap();
+        assert_eq!(&text[range], "Straße");
+        let range = find_folded(text, "s").unwrap();
+        assert_eq!(&text[range], "S");
+        let range = find_folded(text, "sse").unwrap();
+        assert_eq!(&text[range], "ße");
+
+        assert!(find_folded(text, "").is_none());
+        assert!(find_folded(text, "strase").is_none());
+    }
+}
@@ -1,2 +1,5 @@
 pub mod connection;
+pub mod fold;
 pub mod queries;
+#[cfg(debug_assertions)]
+pub mod seed;
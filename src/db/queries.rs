@@ -1,5 +1,20 @@
-use crate::models::{BatchAddResult, MediaItem};
-use rusqlite::{params, Connection};
+use crate::db::fold::fold;
+use crate::models::{is_anilist_type, BatchAddResult, MediaItem};
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Max ids bound into a single `IN (...)` query
+const IDS_PER_QUERY: usize = 500;
+
+/// Items inserted between progress callbacks in `add_items_batch_with_progress`
+const INSERT_PROGRESS_CHUNK: usize = 10;
+
+/// Column list matching the field order expected by `row_to_item`.
+const ITEM_COLUMNS: &str = "id, title, native_title, romaji_title, year, media_type, status,
+                quality_type, source, notes, tmdb_id, anilist_id, poster_url,
+                created_at, updated_at, number_of_seasons, number_of_episodes,
+                progress, total_episodes, favorite, acquired_date, watched_date,
+                poster_width, poster_height, imdb_id, anime_format, season, watched,
+                poster_manual";
 
 fn row_to_item(row: &rusqlite::Row) -> rusqlite::Result<MediaItem> {
     Ok(MediaItem {
@@ -18,41 +33,188 @@ fn row_to_item(row: &rusqlite::Row) -> rusqlite::Result<MediaItem> {
         poster_url: row.get(12)?,
         created_at: row.get(13)?,
         updated_at: row.get(14)?,
+        number_of_seasons: row.get(15)?,
+        number_of_episodes: row.get(16)?,
+        progress: row.get(17)?,
+        total_episodes: row.get(18)?,
+        favorite: row.get(19)?,
+        acquired_date: row.get(20)?,
+        watched_date: row.get(21)?,
+        poster_width: row.get(22)?,
+        poster_height: row.get(23)?,
+        imdb_id: row.get(24)?,
+        anime_format: row.get(25)?,
+        season: row.get(26)?,
+        watched: row.get(27)?,
+        poster_manual: row.get(28)?,
     })
 }
 
-pub fn get_items_sorted(
-    conn: &Connection,
-    media_type: Option<&str>,
-    status: Option<&str>,
-    sort_field: &str,
-    sort_dir: &str,
-) -> Result<Vec<MediaItem>, rusqlite::Error> {
-    let mut sql = String::from(
-        "SELECT id, title, native_title, romaji_title, year, media_type, status,
-                quality_type, source, notes, tmdb_id, anilist_id, poster_url,
-                created_at, updated_at FROM media_items WHERE 1=1",
-    );
-    let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+/// Articles skipped at the start of a title when sorting
+const LEADING_ARTICLES: [&str; 3] = ["the ", "a ", "an "];
 
-    if let Some(mt) = media_type {
-        sql.push_str(" AND media_type = ?");
-        param_values.push(Box::new(mt.to_string()));
+/// Leading numbers are zero-padded to this width so "2 Fast 2 Furious" sorts
+/// before "22 Jump Street"
+const SORT_NUMBER_WIDTH: usize = 10;
+
+/// Value stored in the `sort_title` column: the title without a leading
+/// "The"/"A"/"An" and with any leading number zero-padded.
+pub fn sort_title(title: &str) -> String {
+    let title = title.trim();
+    let rest = LEADING_ARTICLES
+        .iter()
+        .find(|article| title.get(..article.len()).is_some_and(|p| p.eq_ignore_ascii_case(article)))
+        .map(|article| title[article.len()..].trim_start())
+        .filter(|rest| !rest.is_empty())
+        .unwrap_or(title);
+    let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+    if digits == 0 {
+        return rest.to_string();
     }
-    if let Some(s) = status {
-        sql.push_str(" AND status = ?");
-        param_values.push(Box::new(s.to_string()));
+    format!("{:0>width$}{}", &rest[..digits], &rest[digits..], width = SORT_NUMBER_WIDTH)
+}
+
+/// Restrictions shared by the library list, search and count queries.
+/// `None`/empty fields don't restrict anything.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ItemFilter<'a> {
+    pub media_type: Option<&'a str>,
+    pub status: Option<&'a str>,
+    pub favorites_only: bool,
+    pub quality: Option<&'a str>,
+    pub source: Option<&'a str>,
+    /// Inclusive year bounds. Items without a year only match when both are None.
+    pub min_year: Option<i32>,
+    pub max_year: Option<i32>,
+    /// Some(false): items with no poster or a local one; Some(true): items
+    /// with any poster. Whether a local file still exists can't be checked in
+    /// SQL, so callers finish the job with `images::cache::poster_missing`.
+    pub has_poster: Option<bool>,
+    /// Items missing a year, quality type or source
+    pub incomplete: bool,
+    /// Some(true): watched items only; Some(false): unwatched only
+    pub watched: Option<bool>,
+}
+
+impl ItemFilter<'_> {
+    /// Append ` AND ...` clauses (with `?` placeholders) for every set field.
+    fn push_clauses(&self, sql: &mut String, param_values: &mut Vec<Box<dyn rusqlite::types::ToSql>>) {
+        let columns = [
+            ("media_type", self.media_type),
+            ("status", self.status),
+            ("quality_type", self.quality),
+            ("source", self.source),
+        ];
+        for (column, value) in columns {
+            if let Some(v) = value.filter(|v| !v.is_empty()) {
+                sql.push_str(&format!(" AND {} = ?", column));
+                param_values.push(Box::new(v.to_string()));
+            }
+        }
+        if self.favorites_only {
+            sql.push_str(" AND favorite = 1");
+        }
+        match self.watched {
+            Some(true) => sql.push_str(" AND watched = 1"),
+            Some(false) => sql.push_str(" AND watched = 0"),
+            None => {}
+        }
+        match self.has_poster {
+            Some(false) => sql.push_str(
+                " AND (poster_url IS NULL OR TRIM(poster_url) = ''
+                   OR (poster_url NOT LIKE 'http://%' AND poster_url NOT LIKE 'https://%'))",
+            ),
+            Some(true) => sql.push_str(" AND poster_url IS NOT NULL AND TRIM(poster_url) != ''"),
+            None => {}
+        }
+        if self.incomplete {
+            sql.push_str(
+                " AND (year IS NULL OR quality_type IS NULL OR TRIM(quality_type) = ''
+                   OR source IS NULL OR TRIM(source) = '')",
+            );
+        }
+        // NULL years fail both comparisons, so they drop out once a bound is set
+        if let Some(min) = self.min_year {
+            sql.push_str(" AND year >= ?");
+            param_values.push(Box::new(min));
+        }
+        if let Some(max) = self.max_year {
+            sql.push_str(" AND year <= ?");
+            param_values.push(Box::new(max));
+        }
     }
+}
 
-    // Whitelist sort columns to prevent SQL injection
-    let col = match sort_field {
+/// Whitelist sort columns to prevent SQL injection. Every column here has a
+/// matching (media_type, status, col) index in run_migrations; keep them in sync.
+fn sort_column(field: &str, ignore_articles: bool) -> &'static str {
+    match field {
         "year" => "year",
         "quality_type" => "quality_type",
         "source" => "source",
-        _ => "title",
-    };
-    let dir = if sort_dir == "DESC" { "DESC" } else { "ASC" };
-    sql.push_str(&format!(" ORDER BY {} {} NULLS LAST", col, dir));
+        "number_of_seasons" => "number_of_seasons",
+        "acquired_date" => "acquired_date",
+        // "avatar" and "Avatar" belong together
+        _ if ignore_articles => "sort_title COLLATE NOCASE",
+        _ => "title COLLATE NOCASE",
+    }
+}
+
+fn sort_direction(dir: &str) -> &'static str {
+    if dir == "DESC" { "DESC" } else { "ASC" }
+}
+
+/// Row order for `query_items`: `field`/`dir`, then `then_by` (field,
+/// direction) for ties; None means title ascending.
+#[derive(Debug, Clone, Copy)]
+pub struct ItemSort<'a> {
+    pub field: &'a str,
+    pub dir: &'a str,
+    pub then_by: Option<(&'a str, &'a str)>,
+    /// Order titles by `sort_title` rather than the title as typed
+    pub ignore_articles: bool,
+}
+
+impl Default for ItemSort<'_> {
+    fn default() -> Self {
+        Self { field: "title", dir: "ASC", then_by: None, ignore_articles: true }
+    }
+}
+
+impl ItemSort<'_> {
+    fn push_order_by(&self, sql: &mut String) {
+        let col = sort_column(self.field, self.ignore_articles);
+        sql.push_str(&format!(" ORDER BY {} {} NULLS LAST", col, sort_direction(self.dir)));
+        let (field2, dir2) = self.then_by.unwrap_or(("title", "ASC"));
+        let col2 = sort_column(field2, self.ignore_articles);
+        if col2 != col {
+            sql.push_str(&format!(", {} {}", col2, sort_direction(dir2)));
+        }
+    }
+}
+
+/// `SELECT <columns> FROM media_items` restricted by `filter` and, when
+/// given, the `search_items` substring match. Returns the SQL and its params.
+fn filtered_select(
+    columns: &str,
+    filter: &ItemFilter,
+    search: Option<&str>,
+) -> (String, Vec<Box<dyn rusqlite::types::ToSql>>) {
+    let mut sql = format!("SELECT {} FROM media_items WHERE 1=1", columns);
+    let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+    filter.push_clauses(&mut sql, &mut param_values);
+    push_search_clause(&mut sql, &mut param_values, search);
+    (sql, param_values)
+}
+
+/// Items matching every criterion in `filter`, in `sort` order.
+pub fn query_items(
+    conn: &Connection,
+    filter: &ItemFilter,
+    sort: &ItemSort,
+) -> Result<Vec<MediaItem>, rusqlite::Error> {
+    let (mut sql, param_values) = filtered_select(ITEM_COLUMNS, filter, None);
+    sort.push_order_by(&mut sql);
 
     let params_refs: Vec<&dyn rusqlite::types::ToSql> =
         param_values.iter().map(|p| p.as_ref()).collect();
@@ -66,8 +228,12 @@ pub fn get_items_sorted(
 pub fn add_item(conn: &Connection, item: &MediaItem) -> Result<i64, rusqlite::Error> {
     conn.execute(
         "INSERT INTO media_items (title, native_title, romaji_title, year, media_type, status,
-         quality_type, source, notes, tmdb_id, anilist_id, poster_url)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+         quality_type, source, notes, tmdb_id, anilist_id, poster_url,
+         number_of_seasons, number_of_episodes, progress, total_episodes,
+         acquired_date, watched_date, poster_width, poster_height, sort_title, imdb_id,
+         anime_format, season, watched)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18,
+                 ?19, ?20, ?21, ?22, ?23, ?24, ?25)",
         params![
             item.title,
             item.native_title,
@@ -81,6 +247,19 @@ pub fn add_item(conn: &Connection, item: &MediaItem) -> Result<i64, rusqlite::Er
             item.tmdb_id,
             item.anilist_id,
             item.poster_url,
+            item.number_of_seasons,
+            item.number_of_episodes,
+            item.progress,
+            item.total_episodes,
+            item.acquired_date,
+            item.watched_date,
+            item.poster_width,
+            item.poster_height,
+            sort_title(&item.title),
+            item.imdb_id,
+            item.anime_format,
+            item.season,
+            item.watched,
         ],
     )?;
     Ok(conn.last_insert_rowid())
@@ -90,6 +269,18 @@ pub fn add_items_batch(
     conn: &Connection,
     items: &[MediaItem],
     skip_duplicates: bool,
+) -> Result<BatchAddResult, rusqlite::Error> {
+    add_items_batch_with_progress(conn, items, skip_duplicates, |_| {})
+}
+
+/// `add_items_batch` that calls `on_progress` with the number of items
+/// handled so far after every `INSERT_PROGRESS_CHUNK` items and at the end,
+/// all in the one transaction
+pub fn add_items_batch_with_progress(
+    conn: &Connection,
+    items: &[MediaItem],
+    skip_duplicates: bool,
+    mut on_progress: impl FnMut(usize),
 ) -> Result<BatchAddResult, rusqlite::Error> {
     let mut result = BatchAddResult {
         added: 0,
@@ -97,39 +288,43 @@ pub fn add_items_batch(
         errors: 0,
         added_items: Vec::new(),
         skipped_items: Vec::new(),
+        skipped_matches: Vec::new(),
+        forced_items: Vec::new(),
         error_items: Vec::new(),
     };
 
+    // Rows inserted earlier in this batch are visible to check_duplicate_by_id,
+    // but its title/year match never fires without a year; catch those repeats here
+    let mut seen = std::collections::HashSet::new();
     let tx = conn.unchecked_transaction()?;
-    for item in items {
-        if skip_duplicates && check_duplicate_by_id(&tx, item)? {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 && i % INSERT_PROGRESS_CHUNK == 0 {
+            on_progress(i);
+        }
+        let key = (item.title.trim().to_lowercase(), item.year, item.media_type.as_str());
+        let duplicate = if !seen.insert(key) {
+            Some(("repeated in this batch".to_string(), None))
+        } else {
+            check_duplicate_by_id(&tx, item)?.map(|existing| {
+                let reason = format!("matches existing '{}' ({})", existing.title, existing.status);
+                (reason, Some(existing.id))
+            })
+        };
+        if let Some((reason, existing_id)) = duplicate.as_ref().filter(|_| skip_duplicates) {
             result.skipped += 1;
-            result.skipped_items.push(item.title.clone());
+            result.skipped_items.push(format!("{} → {}", item.title, reason));
+            result.skipped_matches.push(*existing_id);
             continue;
         }
 
-        match tx.execute(
-            "INSERT INTO media_items (title, native_title, romaji_title, year, media_type, status,
-             quality_type, source, notes, tmdb_id, anilist_id, poster_url)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
-            params![
-                item.title,
-                item.native_title,
-                item.romaji_title,
-                item.year,
-                item.media_type,
-                item.status,
-                item.quality_type,
-                item.source,
-                item.notes,
-                item.tmdb_id,
-                item.anilist_id,
-                item.poster_url,
-            ],
-        ) {
+        match add_item(&tx, item) {
             Ok(_) => {
                 result.added += 1;
                 result.added_items.push(item.title.clone());
+                // Duplicates only get here when skipping is off
+                if let Some((reason, _)) = &duplicate {
+                    result.forced_items.push(format!("{} → {}", item.title, reason));
+                }
             }
             Err(e) => {
                 result.errors += 1;
@@ -140,17 +335,25 @@ pub fn add_items_batch(
         }
     }
     tx.commit()?;
+    on_progress(items.len());
     Ok(result)
 }
 
 pub fn update_item(conn: &Connection, item: &MediaItem) -> Result<(), rusqlite::Error> {
-    // Don't overwrite tmdb_id/anilist_id — they're set on initial add from search
-    // and the edit dialog doesn't expose them, so they'd be wiped to NULL.
+    // Don't overwrite the provider ids and metadata or the TV season/episode counts — they're
+    // set on initial add from search and the edit dialog doesn't expose them, so
+    // they'd be wiped to NULL. Poster dimensions are kept unless the poster
+    // itself changed (the right-hand side sees the old poster_url).
     conn.execute(
         "UPDATE media_items SET title=?1, native_title=?2, romaji_title=?3, year=?4,
          media_type=?5, status=?6, quality_type=?7, source=?8, notes=?9,
-         poster_url=?10, updated_at=CURRENT_TIMESTAMP
-         WHERE id=?11",
+         poster_url=?10, progress=?11, total_episodes=?12, acquired_date=?13,
+         watched_date=?14, sort_title=?16,
+         poster_width = CASE WHEN poster_url IS ?10 THEN poster_width ELSE 0 END,
+         poster_height = CASE WHEN poster_url IS ?10 THEN poster_height ELSE 0 END,
+         poster_manual = CASE WHEN poster_url IS ?10 THEN poster_manual ELSE 0 END,
+         updated_at=CURRENT_TIMESTAMP
+         WHERE id=?15",
         params![
             item.title,
             item.native_title,
@@ -162,12 +365,215 @@ pub fn update_item(conn: &Connection, item: &MediaItem) -> Result<(), rusqlite::
             item.source,
             item.notes,
             item.poster_url,
+            item.progress,
+            item.total_episodes,
+            item.acquired_date,
+            item.watched_date,
             item.id,
+            sort_title(&item.title),
         ],
     )?;
     Ok(())
 }
 
+/// Flip an item's favorite flag. Leaves `updated_at` alone so starring doesn't
+/// reorder a "recently updated" sort.
+pub fn toggle_favorite(conn: &Connection, id: i64) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "UPDATE media_items SET favorite = NOT favorite WHERE id = ?1",
+        params![id],
+    )?;
+    Ok(())
+}
+
+/// Mark items watched or unwatched. Like favorites, this leaves `updated_at`
+/// alone. Returns the number of rows changed.
+pub fn bulk_set_watched(conn: &Connection, ids: &[i64], watched: bool) -> Result<usize, rusqlite::Error> {
+    let tx = conn.unchecked_transaction()?;
+    let mut changed = 0;
+    for chunk in ids.chunks(IDS_PER_QUERY) {
+        let placeholders: Vec<String> = chunk.iter().enumerate().map(|(i, _)| format!("?{}", i + 2)).collect();
+        let sql = format!(
+            "UPDATE media_items SET watched = ?1 WHERE watched != ?1 AND id IN ({})",
+            placeholders.join(", ")
+        );
+        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = vec![Box::new(watched)];
+        for id in chunk {
+            param_values.push(Box::new(*id));
+        }
+        let params_refs: Vec<&dyn rusqlite::types::ToSql> =
+            param_values.iter().map(|p| p.as_ref()).collect();
+        changed += tx.execute(&sql, params_refs.as_slice())?;
+    }
+    tx.commit()?;
+    Ok(changed)
+}
+
+/// Insert a copy of an item (say, a second copy at another quality) and
+/// return its id, or None if the item doesn't exist. The copy gets fresh
+/// timestamps and shares the original's cached poster file.
+pub fn duplicate_item(conn: &Connection, id: i64) -> Result<Option<i64>, rusqlite::Error> {
+    let Some(item) = get_item_by_id(conn, id)? else {
+        return Ok(None);
+    };
+    let tx = conn.unchecked_transaction()?;
+    let new_id = add_item(&tx, &item)?;
+    // add_item leaves favorite at its default
+    tx.execute(
+        "UPDATE media_items SET favorite = ?1 WHERE id = ?2",
+        params![item.favorite, new_id],
+    )?;
+    tx.commit()?;
+    Ok(Some(new_id))
+}
+
+/// Column holding a provider's id ("tmdb" or "anilist")
+fn external_id_column(provider: &str) -> Option<&'static str> {
+    match provider {
+        "tmdb" => Some("tmdb_id"),
+        "anilist" => Some("anilist_id"),
+        _ => None,
+    }
+}
+
+/// The item carrying a provider's id, if any. For an unknown provider this
+/// is always None.
+pub fn get_by_external_id(
+    conn: &Connection,
+    provider: &str,
+    external_id: i64,
+) -> Result<Option<MediaItem>, rusqlite::Error> {
+    let Some(column) = external_id_column(provider) else {
+        return Ok(None);
+    };
+    let sql = format!("SELECT {} FROM media_items WHERE {} = ?1 LIMIT 1", ITEM_COLUMNS, column);
+    conn.query_row(&sql, params![external_id], row_to_item).optional()
+}
+
+/// Set a provider's id on an item that doesn't have one yet, leaving the
+/// other provider's id alone. Returns false if the item already had an id
+/// for that provider (or doesn't exist). Leaves `updated_at` alone.
+pub fn link_external_id(
+    conn: &Connection,
+    id: i64,
+    provider: &str,
+    external_id: i64,
+) -> Result<bool, rusqlite::Error> {
+    let Some(column) = external_id_column(provider) else {
+        return Ok(false);
+    };
+    let sql = format!("UPDATE media_items SET {0} = ?1 WHERE id = ?2 AND {0} IS NULL", column);
+    let changed = conn.execute(&sql, params![external_id, id])?;
+    Ok(changed > 0)
+}
+
+pub fn get_all_items(conn: &Connection) -> Result<Vec<MediaItem>, rusqlite::Error> {
+    let sql = format!("SELECT {} FROM media_items ORDER BY title COLLATE NOCASE ASC", ITEM_COLUMNS);
+    let mut stmt = conn.prepare(&sql)?;
+    let items = stmt
+        .query_map([], row_to_item)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(items)
+}
+
+pub fn get_item_by_id(conn: &Connection, id: i64) -> Result<Option<MediaItem>, rusqlite::Error> {
+    let sql = format!("SELECT {} FROM media_items WHERE id = ?1", ITEM_COLUMNS);
+    conn.query_row(&sql, params![id], row_to_item).optional()
+}
+
+/// Fetch several items by id, returned in the order of `ids`. Ids with no
+/// matching row are skipped; a repeated id yields the item each time.
+/// Large id lists are queried in chunks to stay under SQLite's parameter limit.
+pub fn get_items_by_ids(conn: &Connection, ids: &[i64]) -> Result<Vec<MediaItem>, rusqlite::Error> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut by_id = std::collections::HashMap::with_capacity(ids.len());
+    for chunk in ids.chunks(IDS_PER_QUERY) {
+        let placeholders: Vec<String> = chunk.iter().enumerate().map(|(i, _)| format!("?{}", i + 1)).collect();
+        let sql = format!(
+            "SELECT {} FROM media_items WHERE id IN ({})",
+            ITEM_COLUMNS,
+            placeholders.join(", ")
+        );
+        let params: Vec<Box<dyn rusqlite::types::ToSql>> =
+            chunk.iter().map(|id| Box::new(*id) as Box<dyn rusqlite::types::ToSql>).collect();
+        let params_refs: Vec<&dyn rusqlite::types::ToSql> =
+            params.iter().map(|p| p.as_ref()).collect();
+        let mut stmt = conn.prepare(&sql)?;
+        for item in stmt.query_map(params_refs.as_slice(), row_to_item)? {
+            let item = item?;
+            if let Some(id) = item.id {
+                by_id.insert(id, item);
+            }
+        }
+    }
+
+    Ok(ids.iter().filter_map(|id| by_id.get(id).cloned()).collect())
+}
+
+/// The `limit` most recently added items across all media types, or the most
+/// recently updated when `by_updated` is set. Newest first.
+pub fn get_recent_items(
+    conn: &Connection,
+    limit: usize,
+    by_updated: bool,
+) -> Result<Vec<MediaItem>, rusqlite::Error> {
+    let column = if by_updated { "updated_at" } else { "created_at" };
+    // id breaks ties between rows written in the same second
+    let sql = format!(
+        "SELECT {} FROM media_items ORDER BY {} DESC, id DESC LIMIT ?1",
+        ITEM_COLUMNS, column
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params![limit as i64], row_to_item)?;
+    rows.collect()
+}
+
+/// Set an item's poster along with its pixel dimensions (0x0 when unknown).
+/// `manual` marks a poster the user picked instead of the provider's.
+pub fn update_poster_url(
+    conn: &Connection,
+    id: i64,
+    poster_url: Option<&str>,
+    width: i32,
+    height: i32,
+    manual: bool,
+) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "UPDATE media_items SET poster_url = ?1, poster_width = ?2, poster_height = ?3,
+         poster_manual = ?5, updated_at = CURRENT_TIMESTAMP WHERE id = ?4",
+        params![poster_url, width, height, id, manual],
+    )?;
+    Ok(())
+}
+
+/// Set poster paths and dimensions for several rows in a single transaction.
+/// Each update is `(id, poster_url, width, height)`.
+pub fn update_poster_urls_batch(
+    conn: &Connection,
+    updates: &[(i64, String, i32, i32)],
+) -> Result<(), rusqlite::Error> {
+    let tx = conn.unchecked_transaction()?;
+    for (id, poster_url, width, height) in updates {
+        tx.execute(
+            "UPDATE media_items SET poster_url = ?1, poster_width = ?2, poster_height = ?3,
+             poster_manual = 0, updated_at = CURRENT_TIMESTAMP WHERE id = ?4",
+            params![poster_url, width, height, id],
+        )?;
+    }
+    tx.commit()
+}
+
+pub fn count_items_with_poster(conn: &Connection, path: &str) -> Result<i64, rusqlite::Error> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM media_items WHERE poster_url = ?1",
+        params![path],
+        |row| row.get(0),
+    )
+}
+
 pub fn get_poster_urls(conn: &Connection, ids: &[i64]) -> Result<Vec<String>, rusqlite::Error> {
     if ids.is_empty() {
         return Ok(Vec::new());
@@ -188,10 +594,32 @@ pub fn get_poster_urls(conn: &Connection, ids: &[i64]) -> Result<Vec<String>, ru
     Ok(urls)
 }
 
-pub fn delete_items_batch(conn: &Connection, ids: &[i64]) -> Result<(), rusqlite::Error> {
+pub fn get_all_poster_urls(conn: &Connection) -> Result<Vec<String>, rusqlite::Error> {
+    let mut stmt = conn.prepare("SELECT poster_url FROM media_items WHERE poster_url IS NOT NULL")?;
+    let urls = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(urls)
+}
+
+/// Null out poster_url on rows pointing at a local cached file (remote URLs are kept).
+pub fn clear_local_poster_urls(conn: &Connection) -> Result<usize, rusqlite::Error> {
+    conn.execute(
+        "UPDATE media_items SET poster_url = NULL, poster_width = 0, poster_height = 0, poster_manual = 0
+         WHERE poster_url IS NOT NULL
+           AND poster_url NOT LIKE 'http://%' AND poster_url NOT LIKE 'https://%'",
+        [],
+    )
+}
+
+/// Delete `ids` and return the poster paths they used that no remaining row
+/// shares, i.e. the cached files that are now safe to remove. Rows can share
+/// a poster file (same URL hash), and those files are left out.
+pub fn delete_items_batch(conn: &Connection, ids: &[i64]) -> Result<Vec<String>, rusqlite::Error> {
     if ids.is_empty() {
-        return Ok(());
+        return Ok(Vec::new());
     }
+    let poster_paths = get_poster_urls(conn, ids)?;
     let placeholders: Vec<String> = ids.iter().enumerate().map(|(i, _)| format!("?{}", i + 1)).collect();
     let sql = format!(
         "DELETE FROM media_items WHERE id IN ({})",
@@ -202,60 +630,167 @@ pub fn delete_items_batch(conn: &Connection, ids: &[i64]) -> Result<(), rusqlite
     let params_refs: Vec<&dyn rusqlite::types::ToSql> =
         params.iter().map(|p| p.as_ref()).collect();
     conn.execute(&sql, params_refs.as_slice())?;
-    Ok(())
+
+    let mut unshared = Vec::new();
+    for path in poster_paths {
+        if !unshared.contains(&path) && count_items_with_poster(conn, &path)? == 0 {
+            unshared.push(path);
+        }
+    }
+    Ok(unshared)
 }
 
+/// Set the status of every item in `ids` in one transaction. Returns how many
+/// rows actually changed; ids that no longer exist (a stale selection) don't count.
 pub fn move_items(
     conn: &Connection,
     ids: &[i64],
     new_status: &str,
-) -> Result<(), rusqlite::Error> {
+) -> Result<usize, rusqlite::Error> {
     if ids.is_empty() {
-        return Ok(());
+        return Ok(0);
     }
-    let placeholders: Vec<String> = ids.iter().enumerate().map(|(i, _)| format!("?{}", i + 2)).collect();
-    let sql = format!(
-        "UPDATE media_items SET status = ?1, updated_at = CURRENT_TIMESTAMP WHERE id IN ({})",
-        placeholders.join(", ")
-    );
-    let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
-    param_values.push(Box::new(new_status.to_string()));
-    for id in ids {
-        param_values.push(Box::new(*id));
+    let tx = conn.unchecked_transaction()?;
+    let mut changed = 0;
+    for chunk in ids.chunks(IDS_PER_QUERY) {
+        let placeholders: Vec<String> = chunk.iter().enumerate().map(|(i, _)| format!("?{}", i + 2)).collect();
+        let sql = format!(
+            "UPDATE media_items SET status = ?1, updated_at = CURRENT_TIMESTAMP WHERE id IN ({})",
+            placeholders.join(", ")
+        );
+        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+        param_values.push(Box::new(new_status.to_string()));
+        for id in chunk {
+            param_values.push(Box::new(*id));
+        }
+        let params_refs: Vec<&dyn rusqlite::types::ToSql> =
+            param_values.iter().map(|p| p.as_ref()).collect();
+        tx.execute(&sql, params_refs.as_slice())?;
+        changed += tx.changes() as usize;
     }
-    let params_refs: Vec<&dyn rusqlite::types::ToSql> =
-        param_values.iter().map(|p| p.as_ref()).collect();
-    conn.execute(&sql, params_refs.as_slice())?;
-    Ok(())
+    tx.commit()?;
+    Ok(changed)
+}
+
+/// Move every item from one status name to another (used when a status is renamed).
+pub fn rename_status(conn: &Connection, old: &str, new: &str) -> Result<usize, rusqlite::Error> {
+    conn.execute(
+        "UPDATE media_items SET status = ?1 WHERE status = ?2",
+        params![new, old],
+    )
+}
+
+pub fn rename_quality_type(conn: &Connection, old: &str, new: &str) -> Result<usize, rusqlite::Error> {
+    conn.execute(
+        "UPDATE media_items SET quality_type = ?1 WHERE quality_type = ?2",
+        params![new, old],
+    )
+}
+
+/// Item count for every quality type in use, whether or not it's configured
+pub fn get_quality_type_counts(
+    conn: &Connection,
+) -> Result<std::collections::HashMap<String, i64>, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "SELECT quality_type, COUNT(*) FROM media_items
+         WHERE quality_type IS NOT NULL AND TRIM(quality_type) != ''
+         GROUP BY quality_type",
+    )?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+    rows.collect()
+}
+
+pub fn count_items_with_quality_type(conn: &Connection, quality_type: &str) -> Result<i64, rusqlite::Error> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM media_items WHERE quality_type = ?1",
+        params![quality_type],
+        |row| row.get(0),
+    )
+}
+
+pub fn count_items_with_status(conn: &Connection, status: &str) -> Result<i64, rusqlite::Error> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM media_items WHERE status = ?1",
+        params![status],
+        |row| row.get(0),
+    )
+}
+
+/// Words kept lowercase inside a title-cased title
+const TITLE_CASE_SMALL_WORDS: [&str; 13] =
+    ["a", "an", "and", "as", "at", "but", "by", "for", "in", "of", "on", "or", "the"];
+
+/// Title-case a title. Only all-lowercase words are touched, so acronyms
+/// ("DVD"), numerals ("II") and names like "McQueen" keep their casing.
+fn title_case(title: &str) -> String {
+    let words: Vec<&str> = title.split(' ').collect();
+    let last = words.len().saturating_sub(1);
+    words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            let is_lower = word.chars().any(char::is_alphabetic) && !word.chars().any(char::is_uppercase);
+            if !is_lower || (i != 0 && i != last && TITLE_CASE_SMALL_WORDS.contains(word)) {
+                return word.to_string();
+            }
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Trim titles and collapse runs of whitespace, optionally title-casing them
+/// too. Native and romaji titles are left alone. Returns the number of rows
+/// changed; running it again changes nothing.
+pub fn normalize_titles(conn: &Connection, apply_title_case: bool) -> Result<usize, rusqlite::Error> {
+    let rows: Vec<(i64, String)> = conn
+        .prepare("SELECT id, title FROM media_items")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+
+    let tx = conn.unchecked_transaction()?;
+    let mut changed = 0;
+    for (id, title) in rows {
+        let mut normalized = title.split_whitespace().collect::<Vec<_>>().join(" ");
+        if apply_title_case {
+            normalized = title_case(&normalized);
+        }
+        // Never blank out a title that was only whitespace
+        if normalized == title || normalized.is_empty() {
+            continue;
+        }
+        changed += tx.execute(
+            "UPDATE media_items SET title = ?1, sort_title = ?2, updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?3",
+            params![normalized, sort_title(&normalized), id],
+        )?;
+    }
+    tx.commit()?;
+    Ok(changed)
 }
 
 pub fn search_items(
     conn: &Connection,
     term: &str,
-    media_type: Option<&str>,
-    status: Option<&str>,
+    filter: &ItemFilter,
+    sort: &ItemSort,
 ) -> Result<Vec<MediaItem>, rusqlite::Error> {
-    let search_pattern = format!("%{}%", term);
-    let mut sql = String::from(
-        "SELECT id, title, native_title, romaji_title, year, media_type, status,
-                quality_type, source, notes, tmdb_id, anilist_id, poster_url,
-                created_at, updated_at FROM media_items
-         WHERE (title LIKE ?1 OR notes LIKE ?1 OR native_title LIKE ?1 OR romaji_title LIKE ?1)",
+    // fold() ignores case, accents and full-width forms on both sides
+    let search_pattern = format!("%{}%", fold(term));
+    let mut sql = format!(
+        "SELECT {} FROM media_items
+         WHERE (fold(title) LIKE ?1 OR fold(notes) LIKE ?1
+                OR fold(native_title) LIKE ?1 OR fold(romaji_title) LIKE ?1)",
+        ITEM_COLUMNS
     );
     let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
     param_values.push(Box::new(search_pattern));
-
-    if let Some(mt) = media_type {
-        sql.push_str(" AND media_type = ?");
-        param_values.push(Box::new(mt.to_string()));
-    }
-
-    if let Some(s) = status {
-        sql.push_str(" AND status = ?");
-        param_values.push(Box::new(s.to_string()));
-    }
-
-    sql.push_str(" ORDER BY title ASC");
+    filter.push_clauses(&mut sql, &mut param_values);
+    sort.push_order_by(&mut sql);
 
     let params_refs: Vec<&dyn rusqlite::types::ToSql> =
         param_values.iter().map(|p| p.as_ref()).collect();
@@ -266,102 +801,338 @@ pub fn search_items(
     Ok(items)
 }
 
+/// The library row a new item was found to duplicate
+#[derive(Debug, Clone)]
+pub struct DuplicateMatch {
+    pub id: i64,
+    pub title: String,
+    pub status: String,
+}
+
+fn row_to_duplicate(row: &rusqlite::Row) -> rusqlite::Result<DuplicateMatch> {
+    Ok(DuplicateMatch {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        status: row.get(2)?,
+    })
+}
+
+/// The existing row `item` duplicates, if any: same provider id, or same
+/// year and one of its titles.
 pub fn check_duplicate_by_id(
     conn: &Connection,
     item: &MediaItem,
-) -> Result<bool, rusqlite::Error> {
-    // Check by API ID first
-    if item.media_type == "Anime" {
+) -> Result<Option<DuplicateMatch>, rusqlite::Error> {
+    // Check by API ID first (AniList IDs are unique across anime and manga)
+    if is_anilist_type(&item.media_type) {
         if let Some(anilist_id) = item.anilist_id {
-            let count: i64 = conn.query_row(
-                "SELECT COUNT(*) FROM media_items WHERE anilist_id = ?1",
-                params![anilist_id],
-                |row| row.get(0),
-            )?;
-            if count > 0 {
-                return Ok(true);
+            let found = conn
+                .query_row(
+                    "SELECT id, title, status FROM media_items WHERE anilist_id = ?1 LIMIT 1",
+                    params![anilist_id],
+                    row_to_duplicate,
+                )
+                .optional()?;
+            if found.is_some() {
+                return Ok(found);
             }
         }
     } else {
         if let Some(tmdb_id) = item.tmdb_id {
-            let count: i64 = conn.query_row(
-                "SELECT COUNT(*) FROM media_items WHERE tmdb_id = ?1 AND media_type = ?2",
-                params![tmdb_id, item.media_type],
-                |row| row.get(0),
-            )?;
-            if count > 0 {
-                return Ok(true);
+            let found = conn
+                .query_row(
+                    "SELECT id, title, status FROM media_items WHERE tmdb_id = ?1 AND media_type = ?2 LIMIT 1",
+                    params![tmdb_id, item.media_type],
+                    row_to_duplicate,
+                )
+                .optional()?;
+            if found.is_some() {
+                return Ok(found);
             }
         }
     }
 
     // Fall back to title + year check
-    let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM media_items WHERE title = ?1 AND year = ?2 AND media_type = ?3",
-        params![item.title, item.year, item.media_type],
-        |row| row.get(0),
-    )?;
-    Ok(count > 0)
+    let found = conn
+        .query_row(
+            "SELECT id, title, status FROM media_items WHERE title = ?1 AND year = ?2 AND media_type = ?3 LIMIT 1",
+            params![item.title, item.year, item.media_type],
+            row_to_duplicate,
+        )
+        .optional()?;
+    if found.is_some() {
+        return Ok(found);
+    }
+
+    // Same year, and any of the item's title variants (display/native/romaji)
+    // matches any variant of an existing row, ignoring case. Catches the same
+    // anime saved once under its English name and once under its romaji name.
+    let variants: Vec<&str> = [
+        Some(item.title.as_str()),
+        item.native_title.as_deref(),
+        item.romaji_title.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(str::trim)
+    .filter(|t| !t.is_empty())
+    .collect();
+    if variants.is_empty() {
+        return Ok(None);
+    }
+
+    let placeholders: Vec<String> = (0..variants.len()).map(|i| format!("LOWER(?{})", i + 3)).collect();
+    let list = placeholders.join(", ");
+    let sql = format!(
+        "SELECT id, title, status FROM media_items WHERE media_type = ?1 AND year = ?2
+         AND (LOWER(title) IN ({list}) OR LOWER(native_title) IN ({list})
+              OR LOWER(romaji_title) IN ({list}))
+         LIMIT 1",
+    );
+    let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+    param_values.push(Box::new(item.media_type.clone()));
+    param_values.push(Box::new(item.year));
+    for v in &variants {
+        param_values.push(Box::new(v.to_string()));
+    }
+    let params_refs: Vec<&dyn rusqlite::types::ToSql> =
+        param_values.iter().map(|p| p.as_ref()).collect();
+    conn.query_row(&sql, params_refs.as_slice(), row_to_duplicate).optional()
 }
 
-pub fn count_filtered_items(
+/// For each `(api_id, title variants, year)` candidate, the status of a
+/// library item of `media_type` it matches, if any. Matches on TMDB/AniList
+/// id first, then like `check_duplicate_by_id`: same year and any title
+/// variant equal to any of the item's, ignoring case. All in a single query.
+pub fn find_existing_by_api_ids(
     conn: &Connection,
-    media_type: Option<&str>,
-    status: Option<&str>,
-    search: Option<&str>,
-) -> Result<i64, rusqlite::Error> {
-    let mut sql = String::from("SELECT COUNT(*) FROM media_items WHERE 1=1");
-    let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+    media_type: &str,
+    candidates: &[(Option<i64>, Vec<&str>, Option<i32>)],
+) -> Result<Vec<Option<String>>, rusqlite::Error> {
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
 
-    if let Some(mt) = media_type {
-        sql.push_str(" AND media_type = ?");
-        param_values.push(Box::new(mt.to_string()));
+    let anilist = is_anilist_type(media_type);
+    let id_col = if anilist { "anilist_id" } else { "tmdb_id" };
+    let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = vec![Box::new(media_type.to_string())];
+
+    let ids: Vec<i64> = candidates.iter().filter_map(|(id, _, _)| *id).collect();
+    let mut id_placeholders = Vec::new();
+    for id in &ids {
+        param_values.push(Box::new(*id));
+        id_placeholders.push(format!("?{}", param_values.len()));
     }
+    let mut title_placeholders = Vec::new();
+    for title in candidates.iter().flat_map(|(_, titles, _)| titles) {
+        param_values.push(Box::new(title.trim().to_string()));
+        title_placeholders.push(format!("LOWER(?{})", param_values.len()));
+    }
+    let titles = title_placeholders.join(", ");
+
+    // AniList ids are unique across anime and manga; TMDB ids are per type
+    let id_clause = match (id_placeholders.is_empty(), anilist) {
+        (true, _) => "0".to_string(),
+        (false, true) => format!("{} IN ({})", id_col, id_placeholders.join(", ")),
+        (false, false) => format!("(media_type = ?1 AND {} IN ({}))", id_col, id_placeholders.join(", ")),
+    };
+    let title_clause = if titles.is_empty() {
+        "0".to_string()
+    } else {
+        format!(
+            "(media_type = ?1 AND (LOWER(title) IN ({titles}) OR LOWER(native_title) IN ({titles})
+              OR LOWER(romaji_title) IN ({titles})))"
+        )
+    };
+    let sql = format!(
+        "SELECT {}, title, native_title, romaji_title, year, media_type, status FROM media_items
+         WHERE {} OR {}",
+        id_col, id_clause, title_clause
+    );
 
-    if let Some(term) = search {
-        if !term.is_empty() {
-            let pattern = format!("%{}%", term);
-            sql.push_str(" AND (title LIKE ? OR notes LIKE ? OR native_title LIKE ? OR romaji_title LIKE ?)");
-            param_values.push(Box::new(pattern.clone()));
-            param_values.push(Box::new(pattern.clone()));
-            param_values.push(Box::new(pattern.clone()));
-            param_values.push(Box::new(pattern));
+    let params_refs: Vec<&dyn rusqlite::types::ToSql> =
+        param_values.iter().map(|p| p.as_ref()).collect();
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            Ok((
+                row.get::<_, Option<i64>>(0)?,
+                [row.get::<_, Option<String>>(1)?, row.get(2)?, row.get(3)?],
+                row.get::<_, Option<i32>>(4)?,
+                row.get::<_, String>(5)?,
+                row.get::<_, String>(6)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // SQLite's LOWER only folds ASCII, so match it here
+    let mut by_id = std::collections::HashMap::new();
+    let mut by_title = std::collections::HashMap::new();
+    for (api_id, titles, year, row_type, status) in rows {
+        if let Some(api_id) = api_id {
+            by_id.entry(api_id).or_insert_with(|| status.clone());
+        }
+        let Some(year) = year.filter(|_| row_type == media_type) else {
+            continue;
+        };
+        for title in titles.iter().flatten() {
+            by_title.entry((title.trim().to_ascii_lowercase(), year)).or_insert_with(|| status.clone());
         }
     }
 
-    if let Some(s) = status {
-        sql.push_str(" AND status = ?");
-        param_values.push(Box::new(s.to_string()));
-    }
+    Ok(candidates
+        .iter()
+        .map(|(id, titles, year)| {
+            id.and_then(|id| by_id.get(&id))
+                .or_else(|| {
+                    let year = (*year)?;
+                    titles.iter().find_map(|t| by_title.get(&(t.trim().to_ascii_lowercase(), year)))
+                })
+                .cloned()
+        })
+        .collect())
+}
+
+pub fn count_filtered_items(
+    conn: &Connection,
+    filter: &ItemFilter,
+    search: Option<&str>,
+) -> Result<i64, rusqlite::Error> {
+    let (sql, param_values) = filtered_select("COUNT(*)", filter, search);
 
     let params_refs: Vec<&dyn rusqlite::types::ToSql> =
         param_values.iter().map(|p| p.as_ref()).collect();
     conn.query_row(&sql, params_refs.as_slice(), |row| row.get(0))
 }
 
+/// `(status, poster_url)` for every matching item, for counts that need a
+/// check SQL can't do (see `ItemFilter::has_poster`).
+pub fn get_item_posters(
+    conn: &Connection,
+    filter: &ItemFilter,
+    search: Option<&str>,
+) -> Result<Vec<(String, Option<String>)>, rusqlite::Error> {
+    let (sql, param_values) = filtered_select("status, poster_url", filter, search);
 
-pub fn get_status_counts(
+    let params_refs: Vec<&dyn rusqlite::types::ToSql> =
+        param_values.iter().map(|p| p.as_ref()).collect();
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map(params_refs.as_slice(), |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Folded title/notes substring match, as in `search_items`; None or empty matches everything.
+fn push_search_clause(
+    sql: &mut String,
+    param_values: &mut Vec<Box<dyn rusqlite::types::ToSql>>,
+    search: Option<&str>,
+) {
+    if let Some(term) = search.filter(|t| !t.is_empty()) {
+        let pattern = format!("%{}%", fold(term));
+        sql.push_str(
+            " AND (fold(title) LIKE ? OR fold(notes) LIKE ? OR fold(native_title) LIKE ? OR fold(romaji_title) LIKE ?)",
+        );
+        param_values.push(Box::new(pattern.clone()));
+        param_values.push(Box::new(pattern.clone()));
+        param_values.push(Box::new(pattern.clone()));
+        param_values.push(Box::new(pattern));
+    }
+}
+
+/// Distinct non-empty sources across the library, case-insensitively sorted.
+pub fn get_distinct_sources(conn: &Connection) -> Result<Vec<String>, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT source FROM media_items
+         WHERE source IS NOT NULL AND source != ''
+         ORDER BY source COLLATE NOCASE",
+    )?;
+    let rows = stmt.query_map([], |row| row.get(0))?;
+    rows.collect()
+}
+
+/// Distinct non-empty quality types used by one media type, with item
+/// counts, in case-insensitive alphabetical order.
+pub fn get_quality_types_in_use(
     conn: &Connection,
     media_type: &str,
-    search: Option<&str>,
-) -> Result<std::collections::HashMap<String, i64>, rusqlite::Error> {
-    let mut sql = String::from(
-        "SELECT status, COUNT(*) FROM media_items WHERE media_type = ?",
+) -> Result<Vec<(String, i64)>, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "SELECT quality_type, COUNT(*) FROM media_items
+         WHERE media_type = ?1 AND quality_type IS NOT NULL AND quality_type != ''
+         GROUP BY quality_type ORDER BY quality_type COLLATE NOCASE",
+    )?;
+    let rows = stmt.query_map(params![media_type], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+    })?;
+    rows.collect()
+}
+
+/// Item counts grouped by one column ("quality_type", "source", "year",
+/// "status", "media_type" or "watched"), optionally within one media type. Empty or
+/// NULL values are counted under an empty label. Years come newest first,
+/// everything else by count. An unknown column yields no rows.
+pub fn count_by_column(
+    conn: &Connection,
+    column: &str,
+    media_type: Option<&str>,
+) -> Result<Vec<(String, i64)>, rusqlite::Error> {
+    // Whitelisted. All but "watched" are covered by an index starting with
+    // media_type; its CASE label can't use one, so that grouping scans the rows.
+    let (column, order) = match column {
+        "quality_type" => ("quality_type", "COUNT(*) DESC, quality_type COLLATE NOCASE"),
+        "source" => ("source", "COUNT(*) DESC, source COLLATE NOCASE"),
+        "year" => ("year", "year DESC"),
+        "status" => ("status", "COUNT(*) DESC, status"),
+        "media_type" => ("media_type", "COUNT(*) DESC, media_type"),
+        "watched" => ("CASE WHEN watched THEN 'Watched' ELSE 'Unwatched' END", "COUNT(*) DESC"),
+        _ => return Ok(Vec::new()),
+    };
+    // A plain equality (not "?1 IS NULL OR ...") so SQLite can use the index
+    let filter = if media_type.is_some() { "WHERE media_type = ?1" } else { "" };
+    let sql = format!(
+        "SELECT {col}, COUNT(*) FROM media_items {filter} GROUP BY {col} ORDER BY {order}",
+        col = column,
+        filter = filter,
+        order = order
     );
-    let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
-    param_values.push(Box::new(media_type.to_string()));
-
-    if let Some(term) = search {
-        if !term.is_empty() {
-            let pattern = format!("%{}%", term);
-            sql.push_str(" AND (title LIKE ? OR notes LIKE ? OR native_title LIKE ? OR romaji_title LIKE ?)");
-            param_values.push(Box::new(pattern.clone()));
-            param_values.push(Box::new(pattern.clone()));
-            param_values.push(Box::new(pattern.clone()));
-            param_values.push(Box::new(pattern));
+
+    let mut stmt = conn.prepare(&sql)?;
+    let params: Vec<&dyn rusqlite::types::ToSql> = match &media_type {
+        Some(media_type) => vec![media_type],
+        None => Vec::new(),
+    };
+    let rows = stmt.query_map(params.as_slice(), |row| {
+        let label = match row.get::<_, rusqlite::types::Value>(0)? {
+            rusqlite::types::Value::Integer(n) if n > 0 => n.to_string(),
+            rusqlite::types::Value::Text(s) => s,
+            _ => String::new(),
+        };
+        Ok((label, row.get::<_, i64>(1)?))
+    })?;
+
+    // NULL and '' group separately; fold them into one "unknown" row
+    let mut counts: Vec<(String, i64)> = Vec::new();
+    for row in rows {
+        let (label, count) = row?;
+        match counts.iter_mut().find(|(l, _)| label.is_empty() && l.is_empty()) {
+            Some(existing) => existing.1 += count,
+            None => counts.push((label, count)),
         }
     }
+    Ok(counts)
+}
 
+/// Item count per status for `filter`, ignoring its status and favorites
+/// restrictions (the counts label the status tabs themselves).
+pub fn get_status_counts(
+    conn: &Connection,
+    filter: &ItemFilter,
+    search: Option<&str>,
+) -> Result<std::collections::HashMap<String, i64>, rusqlite::Error> {
+    let filter = ItemFilter { status: None, favorites_only: false, ..*filter };
+    let (mut sql, param_values) = filtered_select("status, COUNT(*)", &filter, search);
     sql.push_str(" GROUP BY status");
 
     let params_refs: Vec<&dyn rusqlite::types::ToSql> =
@@ -396,3 +1167,166 @@ pub fn get_counts(
     }
     Ok(counts)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::open_in_memory;
+
+    fn item(title: &str, media_type: &str) -> MediaItem {
+        MediaItem {
+            title: title.to_string(),
+            media_type: media_type.to_string(),
+            status: "Watched".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// EXPLAIN QUERY PLAN details for `sql`, one step per line.
+    fn query_plan(conn: &Connection, sql: &str, param_values: &[Box<dyn rusqlite::types::ToSql>]) -> String {
+        let params_refs: Vec<&dyn rusqlite::types::ToSql> = param_values.iter().map(|p| p.as_ref()).collect();
+        let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {}", sql)).unwrap();
+        let steps = stmt
+            .query_map(params_refs.as_slice(), |row| row.get::<_, String>(3))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        steps.join("\n")
+    }
+
+    #[test]
+    fn shared_poster_survives_deleting_one_row() {
+        let conn = open_in_memory();
+        let data_dir = std::env::temp_dir().join(format!("media-tracker-shared-poster-{}", std::process::id()));
+        std::fs::create_dir_all(data_dir.join("image_cache")).unwrap();
+        let stored = "image_cache/0123456789abcdef.jpg";
+        let file = data_dir.join(stored);
+        std::fs::write(&file, b"poster").unwrap();
+
+        let mut movie = item("Blade Runner", "Movie");
+        movie.poster_url = Some(stored.to_string());
+        let mut cut = item("Blade Runner (Final Cut)", "Movie");
+        cut.poster_url = Some(stored.to_string());
+        let movie_id = add_item(&conn, &movie).unwrap();
+        let cut_id = add_item(&conn, &cut).unwrap();
+        assert_eq!(count_items_with_poster(&conn, stored).unwrap(), 2);
+
+        let unshared = delete_items_batch(&conn, &[movie_id]).unwrap();
+        assert!(unshared.is_empty());
+        for path in &unshared {
+            crate::images::cache::delete_cached_poster(path, &data_dir);
+        }
+        assert!(file.exists());
+
+        // Once the last row goes, so can the file
+        let unshared = delete_items_batch(&conn, &[cut_id]).unwrap();
+        assert_eq!(unshared, vec![stored.to_string()]);
+        for path in &unshared {
+            crate::images::cache::delete_cached_poster(path, &data_dir);
+        }
+        assert!(!file.exists());
+        let _ = std::fs::remove_dir_all(data_dir);
+    }
+
+    #[test]
+    fn duplicate_fallback_matches_title_variants() {
+        let conn = open_in_memory();
+        let mut existing = item("Attack on Titan", "Anime");
+        existing.romaji_title = Some("Shingeki no Kyojin".to_string());
+        existing.native_title = Some("進撃の巨人".to_string());
+        existing.year = Some(2013);
+        existing.anilist_id = Some(16498);
+        let id = add_item(&conn, &existing).unwrap();
+
+        // Typed under the romaji title instead of the English one
+        let mut romaji = item("Shingeki no Kyojin", "Anime");
+        romaji.year = Some(2013);
+        assert_eq!(check_duplicate_by_id(&conn, &romaji).unwrap().map(|m| m.id), Some(id));
+
+        // Case doesn't matter
+        let mut lower = item("attack on titan", "Anime");
+        lower.year = Some(2013);
+        assert_eq!(check_duplicate_by_id(&conn, &lower).unwrap().map(|m| m.id), Some(id));
+
+        // A different year is a different show (e.g. a later season)
+        let mut sequel = item("Shingeki no Kyojin", "Anime");
+        sequel.year = Some(2017);
+        assert!(check_duplicate_by_id(&conn, &sequel).unwrap().is_none());
+
+        // The API id still matches whatever the title
+        let mut by_id = item("Something Else", "Anime");
+        by_id.anilist_id = Some(16498);
+        assert_eq!(check_duplicate_by_id(&conn, &by_id).unwrap().map(|m| m.id), Some(id));
+
+        // The search list flags the same results the add would skip
+        let candidates = vec![
+            (Some(1), vec!["Shingeki no Kyojin"], Some(2013)),
+            (Some(2), vec!["ATTACK ON TITAN"], Some(2013)),
+            (Some(3), vec!["Attack on Titan: The Movie", "進撃の巨人"], Some(2013)),
+            (Some(4), vec!["Shingeki no Kyojin"], Some(2017)),
+            (Some(16498), vec!["Something Else"], None),
+        ];
+        let owned = find_existing_by_api_ids(&conn, "Anime", &candidates).unwrap();
+        let watched = Some("Watched".to_string());
+        assert_eq!(owned, vec![watched.clone(), watched.clone(), watched.clone(), None, watched]);
+    }
+
+    #[test]
+    fn mixed_case_titles_sort_together() {
+        let conn = open_in_memory();
+        for title in ["Batman", "avatar", "Avatar"] {
+            add_item(&conn, &item(title, "Movie")).unwrap();
+        }
+        let filter = ItemFilter { media_type: Some("Movie"), status: Some("Watched"), ..Default::default() };
+        for ignore_articles in [false, true] {
+            let sort = ItemSort { ignore_articles, ..Default::default() };
+            let titles: Vec<String> =
+                query_items(&conn, &filter, &sort).unwrap().into_iter().map(|i| i.title).collect();
+            assert_eq!(titles[0].to_lowercase(), "avatar");
+            assert_eq!(titles[1].to_lowercase(), "avatar");
+            assert_eq!(titles[2], "Batman");
+
+            let sort = ItemSort { dir: "DESC", ..sort };
+            let titles: Vec<String> =
+                query_items(&conn, &filter, &sort).unwrap().into_iter().map(|i| i.title).collect();
+            assert_eq!(titles[0], "Batman");
+        }
+    }
+
+    #[test]
+    fn search_matches_every_title_variant() {
+        let conn = open_in_memory();
+        let mut anime = item("Pokémon: The First Movie", "Anime");
+        anime.romaji_title = Some("Gekijōban Poketto Monsutā".to_string());
+        anime.native_title = Some("劇場版ポケットモンスター".to_string());
+        let id = add_item(&conn, &anime).unwrap();
+        add_item(&conn, &item("Digimon: The Movie", "Anime")).unwrap();
+
+        let filter = ItemFilter::default();
+        let sort = ItemSort::default();
+        for term in ["pokemon", "ＰＯＫＥＭＯＮ", "gekijoban", "poketto", "ポケット"] {
+            let hits: Vec<Option<i64>> =
+                search_items(&conn, term, &filter, &sort).unwrap().into_iter().map(|i| i.id).collect();
+            assert_eq!(hits, vec![Some(id)], "{}", term);
+        }
+        assert_eq!(search_items(&conn, "movie", &filter, &sort).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn sort_columns_use_their_index() {
+        let conn = open_in_memory();
+        let filter = ItemFilter { media_type: Some("Movie"), status: Some("Watched"), ..Default::default() };
+        for field in ["title", "year", "quality_type", "source", "number_of_seasons", "acquired_date"] {
+            for ignore_articles in [false, true] {
+                for dir in ["ASC", "DESC"] {
+                    let sort = ItemSort { field, dir, then_by: None, ignore_articles };
+                    let (mut sql, param_values) = filtered_select(ITEM_COLUMNS, &filter, None);
+                    sort.push_order_by(&mut sql);
+                    let plan = query_plan(&conn, &sql, &param_values);
+                    assert!(plan.contains("USING INDEX idx_type_status_"), "{} {}: {}", field, dir, plan);
+                    assert!(!plan.contains("TEMP B-TREE FOR ORDER BY"), "{} {}: {}", field, dir, plan);
+                }
+            }
+        }
+    }
+}
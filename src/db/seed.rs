@@ -0,0 +1,106 @@
+//! Demo data for trying out layouts, sorting and search on a realistic-size
+//! library. Debug builds only; run via `--exec '{"cmd":"seed_demo","count":500}'`.
+
+use rusqlite::Connection;
+
+use crate::bridge::MEDIA_TYPES;
+use crate::db::queries;
+use crate::models::media_item::default_statuses;
+use crate::models::{is_anilist_type, BatchAddResult, MediaItem};
+
+const QUALITY_TYPES: [&str; 6] = ["BluRay", "BluRay 1080p", "BluRay 2160p", "Remux", "WEB-DL", "DVD"];
+const SOURCES: [&str; 5] = ["Disc", "Web", "Backup", "Library", "Gift"];
+
+const ADJECTIVES: [&str; 16] = [
+    "Silent", "Crimson", "Last", "Hidden", "Broken", "Golden", "Endless", "Northern",
+    "Lost", "Electric", "Midnight", "Wandering", "Iron", "Paper", "Frozen", "Distant",
+];
+const NOUNS: [&str; 16] = [
+    "Harbor", "Kingdom", "Signal", "Garden", "Frontier", "Orchestra", "Machine", "River",
+    "Empire", "Lantern", "Horizon", "Witness", "Voyage", "Station", "Tide", "Archive",
+];
+const SUFFIXES: [&str; 5] = ["", "", " II", ": Reborn", " Part 2"];
+const NATIVE: [&str; 8] = ["静かな", "紅の", "最後の", "黄金の", "港", "王国", "信号", "庭"];
+
+/// Tiny xorshift PRNG so seeding doesn't need an extra dependency.
+struct Rng(u64);
+
+impl Rng {
+    fn new() -> Self {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0x2545_f491_4f6c_dd1d);
+        Rng(seed | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+
+    fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+}
+
+/// Insert `count` random items spread across every media type and `statuses`
+/// (the default statuses if empty).
+pub fn seed_demo_data(
+    conn: &Connection,
+    count: usize,
+    statuses: &[String],
+) -> Result<BatchAddResult, rusqlite::Error> {
+    let defaults = default_statuses();
+    let statuses = if statuses.is_empty() { &defaults[..] } else { statuses };
+    let mut rng = Rng::new();
+    let items: Vec<MediaItem> = (0..count)
+        .map(|n| {
+            let media_type = MEDIA_TYPES[n % MEDIA_TYPES.len()].to_string();
+            let anilist = is_anilist_type(&media_type);
+            let title = format!(
+                "{}{} {}{}",
+                if rng.chance(40) { "The " } else { "" },
+                rng.pick(&ADJECTIVES),
+                rng.pick(&NOUNS),
+                rng.pick(&SUFFIXES)
+            );
+            let total = if media_type == "Movie" { None } else { Some(1 + rng.below(150) as i32) };
+            let progress = total.filter(|_| rng.chance(60)).map(|t| rng.below(t as usize + 1) as i32);
+            let acquired_date = rng.chance(70).then(|| {
+                format!("20{:02}-{:02}-{:02}", 10 + rng.below(16), 1 + rng.below(12), 1 + rng.below(28))
+            });
+
+            MediaItem {
+                title,
+                native_title: (anilist && rng.chance(80))
+                    .then(|| format!("{}{}", rng.pick(&NATIVE), rng.pick(&NATIVE))),
+                year: rng.chance(95).then(|| 1950 + rng.below(76) as i32),
+                media_type: media_type.clone(),
+                status: statuses[rng.below(statuses.len())].clone(),
+                quality_type: rng.chance(75).then(|| rng.pick(&QUALITY_TYPES).to_string()),
+                source: rng.chance(50).then(|| rng.pick(&SOURCES).to_string()),
+                notes: rng.chance(20).then(|| "Seeded demo item".to_string()),
+                number_of_seasons: (media_type == "TV").then(|| 1 + rng.below(8) as i32),
+                number_of_episodes: if media_type == "TV" { total } else { None },
+                progress,
+                total_episodes: total,
+                acquired_date,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    // Random titles can collide; duplicates are fine for demo data
+    queries::add_items_batch(conn, &items, false)
+}
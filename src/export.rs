@@ -0,0 +1,233 @@
+//! Importing plain lists of titles from outside the app, and exporting
+//! library items as CSV.
+
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+use rusqlite::Connection;
+use serde::Serialize;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::api;
+use crate::bridge::get_data_dir;
+use crate::db;
+use crate::images;
+use crate::models::{is_anilist_type, AppConfig, BatchAddResult, MediaItem, SearchResult};
+
+/// Max simultaneous title lookups (and their poster downloads) per import
+const LOOKUP_LIMIT: usize = 4;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TextImportResult {
+    /// Lines that found a match online (and so have ids and a poster)
+    pub matched: i32,
+    /// Lines imported as title-only
+    pub unmatched: i32,
+    pub unmatched_items: Vec<String>,
+    #[serde(flatten)]
+    pub batch: BatchAddResult,
+}
+
+/// Import a text file with one `Title (Year)` per line (the year is
+/// optional; blank lines and `#` comments are skipped). Each title is looked
+/// up online for its provider id and poster; lines without a match are still
+/// imported with just the title and year. The lookups all finish before
+/// anything is written to `conn`.
+pub async fn import_text_list(
+    conn: &Connection,
+    client: &reqwest::Client,
+    path: &Path,
+    media_type: &str,
+    status: &str,
+) -> Result<TextImportResult, String> {
+    let data = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let entries: Vec<(String, Option<i32>)> = data
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_line)
+        .collect();
+
+    let data_dir = Arc::new(get_data_dir());
+    let cfg = Arc::new(
+        crate::config::manager::load_config(&data_dir)
+            .map_err(|e| e.to_string())?
+            .config,
+    );
+
+    let semaphore = Arc::new(Semaphore::new(LOOKUP_LIMIT));
+    let mut lookups = JoinSet::new();
+    for (i, (title, year)) in entries.into_iter().enumerate() {
+        let client = client.clone();
+        let cfg = cfg.clone();
+        let data_dir = data_dir.clone();
+        let semaphore = semaphore.clone();
+        let mut item = title_only_item(&title, year, media_type, status);
+        let media_type = media_type.to_string();
+        lookups.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            // A failed lookup is treated the same as no match
+            let Some(result) = lookup(&client, &cfg, &media_type, &title, year).await else {
+                return (i, item, false);
+            };
+            apply_result(&mut item, &result, &media_type);
+            if let Some(url) = result.poster_url.filter(|u| !u.is_empty()) {
+                let cache_dir = data_dir.join("image_cache");
+                if let Ok(poster) = images::cache::cache_poster(&client, &cache_dir, &url).await {
+                    item.poster_url = Some(images::cache::stored_poster_path(&poster.path, &data_dir));
+                    item.poster_width = poster.width;
+                    item.poster_height = poster.height;
+                }
+            }
+            (i, item, true)
+        });
+    }
+
+    // Back in file order
+    let mut looked_up = Vec::new();
+    while let Some(joined) = lookups.join_next().await {
+        looked_up.push(joined.map_err(|e| e.to_string())?);
+    }
+    looked_up.sort_by_key(|(i, _, _)| *i);
+
+    let matched = looked_up.iter().filter(|(_, _, found)| *found).count() as i32;
+    let unmatched_items = looked_up
+        .iter()
+        .filter(|(_, _, found)| !found)
+        .map(|(_, item, _)| item.title.clone())
+        .collect::<Vec<_>>();
+    let items: Vec<MediaItem> = looked_up.into_iter().map(|(_, item, _)| item).collect();
+
+    let batch = db::queries::add_items_batch(conn, &items, true).map_err(|e| e.to_string())?;
+    Ok(TextImportResult {
+        matched,
+        unmatched: unmatched_items.len() as i32,
+        unmatched_items,
+        batch,
+    })
+}
+
+/// Split `Title (1999)` into title and year. Anything else is all title.
+fn parse_line(line: &str) -> (String, Option<i32>) {
+    let parsed = line.strip_suffix(')').and_then(|rest| {
+        let (title, year) = rest.rsplit_once('(')?;
+        let title = title.trim();
+        if year.len() != 4 || !year.bytes().all(|b| b.is_ascii_digit()) || title.is_empty() {
+            return None;
+        }
+        Some((title.to_string(), year.parse().ok()))
+    });
+    parsed.unwrap_or_else(|| (line.to_string(), None))
+}
+
+/// Best search match for one title, if the provider is reachable and has one
+async fn lookup(
+    client: &reqwest::Client,
+    cfg: &AppConfig,
+    media_type: &str,
+    title: &str,
+    year: Option<i32>,
+) -> Option<SearchResult> {
+    let locale = api::tmdb::Locale {
+        language: cfg.tmdb_language.clone(),
+        region: cfg.tmdb_region.clone(),
+    };
+    let key = &cfg.tmdb_api_key;
+    let results = match media_type {
+        "Movie" if !key.is_empty() => {
+            api::tmdb::search_movie(client, key, title, year, cfg.include_adult, &cfg.poster_size, &locale, cfg.tmdb_result_pages).await
+        }
+        "TV" if !key.is_empty() => {
+            api::tmdb::search_tv(client, key, title, year, cfg.include_adult, &cfg.poster_size, &locale, cfg.tmdb_result_pages).await
+        }
+        "Anime" => api::anilist::search_anime(client, title, year, cfg.include_adult).await,
+        "Manga" => api::anilist::search_manga(client, title, year, cfg.include_adult).await,
+        _ => return None,
+    };
+    results.ok()?.into_iter().next()
+}
+
+fn title_only_item(title: &str, year: Option<i32>, media_type: &str, status: &str) -> MediaItem {
+    MediaItem {
+        title: title.to_string(),
+        year,
+        media_type: media_type.to_string(),
+        status: status.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Take the provider's title and ids; the line's own year wins if it had one.
+fn apply_result(item: &mut MediaItem, result: &SearchResult, media_type: &str) {
+    item.title = result.title.clone();
+    item.native_title = result.native_title.clone();
+    item.romaji_title = result.romaji_title.clone();
+    item.year = item.year.or(result.year);
+    item.total_episodes = result.total_episodes;
+    item.anime_format = result.anime_format.clone();
+    item.season = result.season.clone();
+    if is_anilist_type(media_type) {
+        item.anilist_id = Some(result.api_id);
+    } else {
+        item.tmdb_id = Some(result.api_id);
+    }
+}
+
+/// Columns written by `export_items_csv`, in order
+const CSV_HEADER: [&str; 20] = [
+    "title", "native_title", "romaji_title", "year", "media_type", "status",
+    "quality_type", "source", "notes", "tmdb_id", "anilist_id", "imdb_id",
+    "progress", "total_episodes", "favorite", "watched", "acquired_date",
+    "watched_date", "created_at", "updated_at",
+];
+
+/// Write `items` to a CSV file in the given order, one row per item under a
+/// header row. Returns the number of items written.
+pub fn export_items_csv(items: &[MediaItem], path: &Path) -> Result<usize, String> {
+    let file = std::fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut out = std::io::BufWriter::new(file);
+    let write_err = |e: std::io::Error| format!("Failed to write {}: {}", path.display(), e);
+    writeln!(out, "{}", CSV_HEADER.join(",")).map_err(write_err)?;
+    for item in items {
+        let opt = |v: Option<String>| v.unwrap_or_default();
+        let fields = [
+            item.title.clone(),
+            opt(item.native_title.clone()),
+            opt(item.romaji_title.clone()),
+            opt(item.year.map(|y| y.to_string())),
+            item.media_type.clone(),
+            item.status.clone(),
+            opt(item.quality_type.clone()),
+            opt(item.source.clone()),
+            opt(item.notes.clone()),
+            opt(item.tmdb_id.map(|id| id.to_string())),
+            opt(item.anilist_id.map(|id| id.to_string())),
+            opt(item.imdb_id.clone()),
+            opt(item.progress.map(|p| p.to_string())),
+            opt(item.total_episodes.map(|n| n.to_string())),
+            item.favorite.to_string(),
+            item.watched.to_string(),
+            opt(item.acquired_date.clone()),
+            opt(item.watched_date.clone()),
+            opt(item.created_at.clone()),
+            opt(item.updated_at.clone()),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        writeln!(out, "{}", row.join(",")).map_err(write_err)?;
+    }
+    out.flush().map_err(write_err)?;
+    Ok(items.len())
+}
+
+/// Quote a field if it holds a comma, quote or line break, doubling any
+/// quotes inside (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
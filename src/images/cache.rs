@@ -1,72 +1,310 @@
+use crate::api::ApiError;
+use image::imageops::FilterType;
+use image::{ImageFormat, Rgb, RgbImage};
 use reqwest::Client;
+use serde::Serialize;
 use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 
-fn url_to_filename(url: &str) -> String {
+const MAX_ATTEMPTS: u32 = 3;
+const KNOWN_EXTENSIONS: [&str; 4] = ["jpg", "png", "webp", "jpeg"];
+/// Subdirectory of the cache holding grid-sized copies of each poster.
+const THUMB_DIR: &str = "thumbs";
+const THUMB_WIDTH: u32 = 200;
+/// Subdirectory holding grid-sized copies framed to a uniform 2:3, shown
+/// instead of the thumbnails when `uniform_posters` is on.
+const FRAMED_DIR: &str = "framed";
+const FRAMED_HEIGHT: u32 = THUMB_WIDTH * 3 / 2;
+/// Art wider than this multiple of 2:3 (banners, screenshots) is letterboxed
+/// rather than cropped to the middle, like the grid card does.
+const LETTERBOX_RATIO: f64 = 1.5;
+/// Theme surfaceDark, so the bars blend into the card
+const LETTERBOX_COLOR: [u8; 3] = [0x1a, 0x1d, 0x20];
+/// Subdirectory for posters prefetched for the search dialog. Nothing in the
+/// library references these, so they're always treated as orphans.
+pub const SEARCH_CACHE_DIR: &str = "search_cache";
+/// Prefetched posters kept around for repeated searches before the least
+/// recently used are evicted.
+pub const SEARCH_CACHE_MAX_FILES: usize = 300;
+
+/// Hash of the URL used as the cached file's stem.
+fn url_to_stem(url: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(url.as_bytes());
     let hash = hex::encode(hasher.finalize());
-    // Extract extension from URL
+    hash[..16].to_string()
+}
+
+/// File name older versions derived from the URL's last path segment.
+fn legacy_filename(url: &str) -> String {
     let ext = url
         .rsplit('/')
         .next()
         .and_then(|s| s.rsplit('.').next())
         .unwrap_or("jpg");
-    format!("{}.{}", &hash[..16], ext)
+    format!("{}.{}", url_to_stem(url), ext)
+}
+
+/// Find an existing cached file for `url` by stem, whatever its extension.
+fn find_by_stem(cache_dir: &Path, url: &str) -> Option<PathBuf> {
+    let stem = url_to_stem(url);
+    KNOWN_EXTENSIONS
+        .iter()
+        .map(|ext| cache_dir.join(format!("{}.{}", stem, ext)))
+        .chain(std::iter::once(cache_dir.join(legacy_filename(url))))
+        .find(|p| p.is_file())
+}
+
+/// Pick a file extension from the Content-Type header, sniffing magic bytes
+/// when the header is missing or generic. Returns None for anything that
+/// isn't a JPEG/PNG/WebP image (e.g. an HTML error page).
+fn image_extension(content_type: Option<&str>, bytes: &[u8]) -> Option<&'static str> {
+    let mime = content_type
+        .and_then(|ct| ct.split(';').next())
+        .map(|ct| ct.trim().to_ascii_lowercase());
+    match mime.as_deref() {
+        Some("image/jpeg") | Some("image/jpg") => return Some("jpg"),
+        Some("image/png") => return Some("png"),
+        Some("image/webp") => return Some("webp"),
+        Some(m) if m.starts_with("text/") || m.contains("html") || m.contains("json") => return None,
+        _ => {}
+    }
+
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("jpg")
+    } else if bytes.starts_with(&[0x89, b'P', b'N', b'G']) {
+        Some("png")
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("webp")
+    } else {
+        None
+    }
+}
+
+/// A poster file in the cache along with its pixel size (0x0 if it couldn't be read).
+#[derive(Debug, Clone)]
+pub struct CachedPoster {
+    pub path: PathBuf,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl CachedPoster {
+    fn new(path: PathBuf) -> Self {
+        let (width, height) = poster_dimensions(&path);
+        CachedPoster { path, width, height }
+    }
+}
+
+/// Read an image's size from its header without decoding the pixel data.
+pub fn poster_dimensions(path: &Path) -> (i32, i32) {
+    image::image_dimensions(path)
+        .map(|(w, h)| (w as i32, h as i32))
+        .unwrap_or((0, 0))
 }
 
 pub async fn cache_poster(
     client: &Client,
     cache_dir: &Path,
     url: &str,
-) -> Result<PathBuf, String> {
-    std::fs::create_dir_all(cache_dir).map_err(|e| format!("Failed to create cache dir: {}", e))?;
-
-    let filename = url_to_filename(url);
-    let file_path = cache_dir.join(&filename);
+) -> Result<CachedPoster, ApiError> {
+    std::fs::create_dir_all(cache_dir).map_err(|e| ApiError::Io(format!("Failed to create cache dir: {}", e)))?;
 
     // Return cached file if it exists
-    if file_path.exists() {
-        return Ok(file_path);
+    if let Some(existing) = find_by_stem(cache_dir, url) {
+        return Ok(CachedPoster::new(existing));
     }
 
-    // Download the image
-    let resp = client
-        .get(url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to download poster: {}", e))?;
+    let file_path = download_poster(client, url, cache_dir).await?;
+    generate_variants(&file_path).await;
+    Ok(CachedPoster::new(file_path))
+}
+
+/// Like `cache_poster`, but always re-downloads and overwrites any existing
+/// file. The old file is only replaced once the download succeeds.
+pub async fn recache_poster(
+    client: &Client,
+    cache_dir: &Path,
+    url: &str,
+) -> Result<CachedPoster, ApiError> {
+    std::fs::create_dir_all(cache_dir).map_err(|e| ApiError::Io(format!("Failed to create cache dir: {}", e)))?;
+
+    let previous = find_by_stem(cache_dir, url);
+    let file_path = download_poster(client, url, cache_dir).await?;
+    // The extension may have changed; drop the stale copy so it isn't orphaned
+    if let Some(old) = previous.filter(|old| *old != file_path) {
+        let _ = std::fs::remove_file(old);
+    }
+    remove_variants(&file_path);
+    generate_variants(&file_path).await;
+    Ok(CachedPoster::new(file_path))
+}
+
+/// Make the thumbnail and framed copy of a fresh download on a blocking
+/// thread, so decoding doesn't stall the runtime. Best effort; the model
+/// generates them lazily if this fails.
+async fn generate_variants(original: &Path) {
+    let original = original.to_path_buf();
+    let _ = tokio::task::spawn_blocking(move || {
+        ensure_thumbnail(&original);
+        ensure_framed(&original);
+    })
+    .await;
+}
+
+/// GET a poster, retrying failed connections, timeouts and 5xx/429 responses
+/// with exponential backoff. Anything else (a bad URL, a 404) fails at once.
+async fn fetch_with_retry(client: &Client, url: &str) -> Result<reqwest::Response, ApiError> {
+    for attempt in 0..MAX_ATTEMPTS {
+        let last_attempt = attempt + 1 == MAX_ATTEMPTS;
+        let delay = std::time::Duration::from_millis(500 * (1 << attempt)); // 0.5s, 1s
+
+        let resp = match client.get(url).send().await {
+            Ok(resp) => resp,
+            Err(e) if !last_attempt && (e.is_connect() || e.is_timeout()) => {
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+            Err(e) => return Err(ApiError::Network(format!("Failed to download poster: {}", e))),
+        };
 
-    if !resp.status().is_success() {
-        return Err(format!("Poster download failed: HTTP {}", resp.status()));
+        let status = resp.status();
+        if (status.is_server_error() || status.as_u16() == 429) && !last_attempt {
+            tokio::time::sleep(delay).await;
+            continue;
+        }
+        if !status.is_success() {
+            return Err(ApiError::from_status(status, format!("Poster download failed: HTTP {}", status)));
+        }
+        return Ok(resp);
     }
 
+    Err(ApiError::Network("Poster download: max attempts exceeded".into()))
+}
+
+async fn download_poster(client: &Client, url: &str, cache_dir: &Path) -> Result<PathBuf, ApiError> {
+    let resp = fetch_with_retry(client, url).await.inspect_err(|e| {
+        tracing::warn!("Poster {}: {}", url, e);
+    })?;
+
+    let content_type = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+
     let bytes = resp
         .bytes()
         .await
-        .map_err(|e| format!("Failed to read poster data: {}", e))?;
+        .map_err(|e| ApiError::Network(format!("Failed to read poster data: {}", e)))?;
 
+    let ext = image_extension(content_type.as_deref(), &bytes).ok_or_else(|| {
+        ApiError::Parse(format!(
+            "URL did not return a JPEG, PNG or WebP image (Content-Type: {})",
+            content_type.as_deref().unwrap_or("none")
+        ))
+    })?;
+    let file_path = cache_dir.join(format!("{}.{}", url_to_stem(url), ext));
     std::fs::write(&file_path, &bytes)
-        .map_err(|e| format!("Failed to save poster: {}", e))?;
+        .map_err(|e| ApiError::Io(format!("Failed to save poster: {}", e)))?;
 
     Ok(file_path)
 }
 
 
+/// `file://` URL for a local path. Backslashes become `/`, Windows drive paths
+/// get the `file:///C:/` form, and anything outside the URL-safe set
+/// (spaces, `#`, non-ASCII) is percent-encoded.
+pub fn file_url(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut url = String::from("file://");
+    if !path.starts_with('/') {
+        url.push('/');
+    }
+    for b in path.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' | b':' => {
+                url.push(b as char)
+            }
+            _ => url.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    url
+}
+
+/// Undo `file_url`: strip the scheme, decode percent escapes and drop the
+/// slash in front of a Windows drive letter. Anything else is returned as is.
+pub fn path_from_file_url(value: &str) -> String {
+    let Some(rest) = value.strip_prefix("file://") else {
+        return value.to_string();
+    };
+    let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+
+    let bytes = rest.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+        match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+            Some(b) if bytes[i] == b'%' => {
+                decoded.push(b);
+                i += 3;
+            }
+            _ => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    let path = String::from_utf8_lossy(&decoded).to_string();
+
+    // "/C:/Users/..." -> "C:/Users/..."
+    let b = path.as_bytes();
+    if b.len() >= 3 && b[0] == b'/' && b[1].is_ascii_alphabetic() && b[2] == b':' {
+        path[1..].to_string()
+    } else {
+        path
+    }
+}
+
+/// Path of a cached poster as stored in the DB: relative to `data_dir` with `/`
+/// separators when it lives there (so the DB survives moving the app folder
+/// or switching OS), otherwise absolute.
+pub fn stored_poster_path(path: &Path, data_dir: &Path) -> String {
+    match path.strip_prefix(data_dir) {
+        Ok(rel) => rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => path.to_string_lossy().to_string(),
+    }
+}
+
+/// `file://` URL for a stored poster path, joined against the current `data_dir`.
+pub fn stored_poster_url(path: &str, data_dir: &Path) -> String {
+    file_url(&resolve_cached_poster_path(path, data_dir))
+}
+
 /// Convert a stored poster path (absolute, relative, file://, or asset://localhost/) into
 /// an absolute filesystem path rooted at `data_dir` when needed.
 pub fn resolve_cached_poster_path(path: &str, data_dir: &Path) -> PathBuf {
-    let normalized = path
-        .strip_prefix("file://")
-        .or_else(|| path.strip_prefix("asset://localhost/"))
-        .unwrap_or(path)
-        .trim();
+    let path = path.trim();
+    let normalized = match path.strip_prefix("asset://localhost/") {
+        Some(rest) => rest.to_string(),
+        None => path_from_file_url(path),
+    };
 
-    let p = Path::new(normalized);
+    let p = Path::new(&normalized);
     if p.is_absolute() {
         return p.to_path_buf();
     }
 
+    // Relative paths written on Windows use backslashes
+    let normalized = normalized.replace('\\', "/");
+    let p = Path::new(&normalized);
     let rel = if normalized.starts_with("image_cache/") {
         p.to_path_buf()
     } else if p.file_name().is_some() {
@@ -78,10 +316,388 @@ pub fn resolve_cached_poster_path(path: &str, data_dir: &Path) -> PathBuf {
     data_dir.join(rel)
 }
 
+/// Locate the file for a stored poster path, falling back to a file of the
+/// same name in `image_cache` (covers absolute paths from another machine or
+/// an old install directory, whichever separator they use).
+pub fn find_cached_poster(path: &str, data_dir: &Path) -> Option<PathBuf> {
+    let direct = resolve_cached_poster_path(path, data_dir);
+    if direct.exists() {
+        return Some(direct);
+    }
+
+    let name = path.rsplit(['/', '\\']).next().filter(|n| !n.is_empty())?;
+    let fallback = data_dir.join("image_cache").join(name);
+    fallback.exists().then_some(fallback)
+}
+
+/// True when a stored poster_url has nothing to show: unset, or a local file
+/// that's no longer on disk. Remote URLs count as present.
+pub fn poster_missing(poster_url: Option<&str>, data_dir: &Path) -> bool {
+    match poster_url.map(str::trim).filter(|u| !u.is_empty()) {
+        None => true,
+        Some(url) if url.starts_with("http://") || url.starts_with("https://") => false,
+        Some(url) => find_cached_poster(url, data_dir).is_none(),
+    }
+}
+
 /// Delete a cached poster file by its stored path.
 pub fn delete_cached_poster(path: &str, data_dir: &Path) {
     let resolved = resolve_cached_poster_path(path, data_dir);
     if resolved.exists() && resolved.components().any(|c| c.as_os_str() == "image_cache") {
+        remove_variants(&resolved);
         let _ = std::fs::remove_file(resolved);
     }
 }
+
+/// Files directly in `cache_dir` plus those in its thumbnail, framed and
+/// search subdirectories.
+fn list_cache_files(cache_dir: &Path) -> Vec<PathBuf> {
+    let search_dir = cache_dir.join(SEARCH_CACHE_DIR);
+    [
+        cache_dir.to_path_buf(),
+        cache_dir.join(THUMB_DIR),
+        cache_dir.join(FRAMED_DIR),
+        search_dir.join(THUMB_DIR),
+        search_dir.join(FRAMED_DIR),
+        search_dir,
+    ]
+    .iter()
+    .filter_map(|dir| std::fs::read_dir(dir).ok())
+    .flat_map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()))
+    .filter(|p| p.is_file())
+    .collect()
+}
+
+/// File names and stems of the originals stored poster paths point at.
+struct ReferencedFiles {
+    names: HashSet<OsString>,
+    stems: HashSet<OsString>,
+}
+
+impl ReferencedFiles {
+    fn new(referenced: &[String], data_dir: &Path) -> Self {
+        let paths: Vec<PathBuf> = referenced
+            .iter()
+            .map(|p| resolve_cached_poster_path(p, data_dir))
+            .collect();
+        Self {
+            names: paths.iter().filter_map(|p| p.file_name().map(|n| n.to_os_string())).collect(),
+            stems: paths.iter().filter_map(|p| p.file_stem().map(|n| n.to_os_string())).collect(),
+        }
+    }
+
+    /// Originals match by file name, mirroring the fallback lookup in the
+    /// media model; thumbnails and framed copies match by stem against the
+    /// referenced originals.
+    fn is_orphan(&self, path: &Path, cache_dir: &Path) -> bool {
+        if path.starts_with(cache_dir.join(SEARCH_CACHE_DIR)) {
+            true
+        } else if path.parent() == Some(cache_dir.join(THUMB_DIR).as_path())
+            || path.parent() == Some(cache_dir.join(FRAMED_DIR).as_path())
+        {
+            path.file_stem().is_some_and(|n| !self.stems.contains(n))
+        } else {
+            path.file_name().is_some_and(|n| !self.names.contains(n))
+        }
+    }
+}
+
+/// List cached files whose name isn't used by any stored poster path.
+pub fn find_orphaned_files(cache_dir: &Path, referenced: &[String], data_dir: &Path) -> Vec<PathBuf> {
+    let refs = ReferencedFiles::new(referenced, data_dir);
+    list_cache_files(cache_dir)
+        .into_iter()
+        .filter(|p| refs.is_orphan(p, cache_dir))
+        .collect()
+}
+
+/// Whether one cached file is still unreferenced, for callers that found it
+/// earlier and re-check right before deleting it.
+pub fn is_orphaned(path: &Path, cache_dir: &Path, referenced: &[String], data_dir: &Path) -> bool {
+    ReferencedFiles::new(referenced, data_dir).is_orphan(path, cache_dir)
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct CacheStats {
+    pub file_count: usize,
+    pub total_bytes: u64,
+    pub orphaned_count: usize,
+}
+
+/// Count files and bytes in the cache directory, plus how many are orphaned.
+pub fn cache_stats(cache_dir: &Path, referenced: &[String], data_dir: &Path) -> CacheStats {
+    let mut stats = CacheStats::default();
+    for path in list_cache_files(cache_dir) {
+        if let Ok(meta) = std::fs::metadata(&path) {
+            stats.file_count += 1;
+            stats.total_bytes += meta.len();
+        }
+    }
+    stats.orphaned_count = find_orphaned_files(cache_dir, referenced, data_dir).len();
+    stats
+}
+
+/// Delete orphaned files, or every file when `orphans_only` is false.
+/// Returns the number of files removed.
+pub fn clear_cache(cache_dir: &Path, referenced: &[String], data_dir: &Path, orphans_only: bool) -> usize {
+    let targets: Vec<PathBuf> = if orphans_only {
+        find_orphaned_files(cache_dir, referenced, data_dir)
+    } else {
+        list_cache_files(cache_dir)
+    };
+    targets
+        .iter()
+        .filter(|p| std::fs::remove_file(p).is_ok())
+        .count()
+}
+
+/// Drop every poster prefetched for earlier search results.
+pub fn clear_search_cache(cache_dir: &Path) {
+    let _ = std::fs::remove_dir_all(cache_dir.join(SEARCH_CACHE_DIR));
+}
+
+/// Bump a cached file's modification time so LRU eviction sees it as used.
+pub fn mark_used(path: &Path) {
+    if let Ok(file) = std::fs::File::options().write(true).open(path) {
+        let _ = file.set_modified(std::time::SystemTime::now());
+    }
+}
+
+/// Trim the search cache to its `max_files` most recently used posters,
+/// removing each evicted poster's thumbnail and framed copy with it. Returns
+/// the number of posters removed.
+pub fn evict_search_cache(cache_dir: &Path, max_files: usize) -> usize {
+    let Ok(entries) = std::fs::read_dir(cache_dir.join(SEARCH_CACHE_DIR)) else {
+        return 0;
+    };
+    let mut posters: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .map(|e| {
+            let modified = e
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::UNIX_EPOCH);
+            (modified, e.path())
+        })
+        .collect();
+    if posters.len() <= max_files {
+        return 0;
+    }
+
+    // Newest first; everything past the limit goes
+    posters.sort_by_key(|p| std::cmp::Reverse(p.0));
+    posters
+        .drain(max_files..)
+        .filter(|(_, path)| {
+            remove_variants(path);
+            std::fs::remove_file(path).is_ok()
+        })
+        .count()
+}
+
+/// Where the grid thumbnail for a cached original lives: `thumbs/<stem>.jpg`
+/// next to it.
+pub fn thumbnail_path(original: &Path) -> Option<PathBuf> {
+    let stem = original.file_stem()?;
+    let mut path = original.parent()?.join(THUMB_DIR).join(stem);
+    path.set_extension("jpg");
+    Some(path)
+}
+
+/// Return the thumbnail for a cached original, generating it on first use
+/// (covers posters cached before thumbnails existed). Falls back to the
+/// original when it can't be decoded.
+pub fn ensure_thumbnail(original: &Path) -> PathBuf {
+    let Some(thumb) = thumbnail_path(original) else {
+        return original.to_path_buf();
+    };
+    if thumb.exists() {
+        return thumb;
+    }
+    match generate_thumbnail(original, &thumb) {
+        Ok(()) => thumb,
+        Err(_) => original.to_path_buf(),
+    }
+}
+
+fn generate_thumbnail(original: &Path, thumb: &Path) -> Result<(), String> {
+    let img = image::open(original).map_err(|e| format!("Failed to decode poster: {}", e))?;
+    let resized = if img.width() > THUMB_WIDTH {
+        img.resize(THUMB_WIDTH, u32::MAX, FilterType::Triangle)
+    } else {
+        img
+    };
+
+    if let Some(dir) = thumb.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create thumbnail dir: {}", e))?;
+    }
+    // JPEG has no alpha channel, so flatten first
+    resized
+        .to_rgb8()
+        .save_with_format(thumb, ImageFormat::Jpeg)
+        .map_err(|e| format!("Failed to save thumbnail: {}", e))
+}
+
+/// Where the 2:3 grid copy of a cached original lives: `framed/<stem>.jpg`
+/// next to it.
+pub fn framed_path(original: &Path) -> Option<PathBuf> {
+    let stem = original.file_stem()?;
+    let mut path = original.parent()?.join(FRAMED_DIR).join(stem);
+    path.set_extension("jpg");
+    Some(path)
+}
+
+/// Return the 2:3 copy of a cached original, generating it on first use.
+/// Falls back to the thumbnail when the original can't be decoded.
+pub fn ensure_framed(original: &Path) -> PathBuf {
+    let Some(framed) = framed_path(original) else {
+        return ensure_thumbnail(original);
+    };
+    if framed.exists() {
+        return framed;
+    }
+    match generate_framed(original, &framed) {
+        Ok(()) => framed,
+        Err(_) => ensure_thumbnail(original),
+    }
+}
+
+/// Crop to the middle 2:3 of the poster, or letterbox it when it's far wider
+/// than that.
+fn generate_framed(original: &Path, framed: &Path) -> Result<(), String> {
+    let img = image::open(original).map_err(|e| format!("Failed to decode poster: {}", e))?;
+    let aspect = img.width() as f64 / img.height().max(1) as f64;
+    let target = THUMB_WIDTH as f64 / FRAMED_HEIGHT as f64;
+    let out = if aspect > target * LETTERBOX_RATIO {
+        let fitted = img.resize(THUMB_WIDTH, FRAMED_HEIGHT, FilterType::Triangle).to_rgb8();
+        let mut canvas = RgbImage::from_pixel(THUMB_WIDTH, FRAMED_HEIGHT, Rgb(LETTERBOX_COLOR));
+        let x = (THUMB_WIDTH - fitted.width()) / 2;
+        let y = (FRAMED_HEIGHT - fitted.height()) / 2;
+        image::imageops::overlay(&mut canvas, &fitted, x as i64, y as i64);
+        canvas
+    } else {
+        img.resize_to_fill(THUMB_WIDTH, FRAMED_HEIGHT, FilterType::Triangle).to_rgb8()
+    };
+
+    if let Some(dir) = framed.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create framed poster dir: {}", e))?;
+    }
+    out.save_with_format(framed, ImageFormat::Jpeg)
+        .map_err(|e| format!("Failed to save framed poster: {}", e))
+}
+
+/// Delete the thumbnail and framed copy derived from a cached original.
+fn remove_variants(original: &Path) {
+    for variant in [thumbnail_path(original), framed_path(original)].into_iter().flatten() {
+        let _ = std::fs::remove_file(variant);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    const JPEG: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0, 0, 0];
+    const PNG: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    const WEBP: &[u8] = b"RIFF\0\0\0\0WEBPVP8 ";
+
+    /// Empty scratch directory unique to this test run.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("media-tracker-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Answer successive connections on a local port with `responses`
+    /// (status, Content-Type, body). Returns the base URL.
+    fn serve(responses: Vec<(u16, &'static str, &'static [u8])>) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for (status, content_type, body) in responses {
+                let Ok((mut stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut request = [0u8; 4096];
+                let _ = stream.read(&mut request);
+                let head = format!(
+                    "HTTP/1.1 {} X\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    content_type,
+                    body.len()
+                );
+                let _ = stream.write_all(head.as_bytes());
+                let _ = stream.write_all(body);
+            }
+        });
+        base
+    }
+
+    #[test]
+    fn extension_from_content_type_then_magic_bytes() {
+        // The header wins when it names an image type
+        assert_eq!(image_extension(Some("image/png; charset=binary"), JPEG), Some("png"));
+        assert_eq!(image_extension(Some("image/jpg"), PNG), Some("jpg"));
+        // Generic or missing headers fall back to sniffing
+        assert_eq!(image_extension(Some("application/octet-stream"), WEBP), Some("webp"));
+        assert_eq!(image_extension(None, PNG), Some("png"));
+        assert_eq!(image_extension(None, JPEG), Some("jpg"));
+        // Error pages and unknown data aren't cached
+        assert_eq!(image_extension(Some("text/html"), JPEG), None);
+        assert_eq!(image_extension(None, b"<html>"), None);
+    }
+
+    #[test]
+    fn existing_files_are_found_by_stem() {
+        let dir = temp_dir("find-by-stem");
+        let url = "https://img.anili.st/media/1234";
+        assert!(find_by_stem(&dir, url).is_none());
+
+        // Cached under a different extension than a new download would pick
+        let png = dir.join(format!("{}.png", url_to_stem(url)));
+        std::fs::write(&png, PNG).unwrap();
+        assert_eq!(find_by_stem(&dir, url), Some(png));
+
+        // Older versions took the extension from the URL
+        let legacy_url = "https://example.com/cover.php?id=1";
+        let legacy = dir.join(legacy_filename(legacy_url));
+        std::fs::write(&legacy, JPEG).unwrap();
+        assert_eq!(find_by_stem(&dir, legacy_url), Some(legacy));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_until_success() {
+        let base = serve(vec![(503, "text/plain", b""), (500, "text/plain", b""), (200, "image/jpeg", JPEG)]);
+        let resp = fetch_with_retry(&Client::new(), &format!("{}/poster.jpg", base)).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.bytes().await.unwrap().as_ref(), JPEG);
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        // A retry would find nothing listening and fail differently
+        let base = serve(vec![(404, "text/plain", b"")]);
+        let err = fetch_with_retry(&Client::new(), &format!("{}/poster.jpg", base)).await.unwrap_err();
+        assert!(matches!(err, ApiError::NotFound(_)), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn url_without_extension_is_cached_by_content() {
+        let dir = temp_dir("no-extension");
+        let base = serve(vec![(200, "application/octet-stream", PNG)]);
+        let url = format!("{}/covers/large/bx1234", base);
+        let client = Client::new();
+
+        let cached = cache_poster(&client, &dir, &url).await.unwrap();
+        assert_eq!(cached.path, dir.join(format!("{}.png", url_to_stem(&url))));
+
+        // The server only answers once, so this must come from the cache
+        let again = cache_poster(&client, &dir, &url).await.unwrap();
+        assert_eq!(again.path, cached.path);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
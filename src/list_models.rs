@@ -18,6 +18,9 @@ pub mod qobject {
 
         include!("cxx-qt-lib/qstring.h");
         type QString = cxx_qt_lib::QString;
+
+        include!("cxx-qt-lib/qvector.h");
+        type QVector_i32 = cxx_qt_lib::QVector<i32>;
     }
 
     // ── MediaModel ──────────────────────────────────────────────────────
@@ -25,6 +28,7 @@ pub mod qobject {
         #[qobject]
         #[base = QAbstractListModel]
         #[qml_element]
+        #[qproperty(i32, selected_count)]
         type MediaModel = super::MediaModelRust;
 
         #[qinvokable]
@@ -41,8 +45,22 @@ pub mod qobject {
         #[cxx_name = "rowCount"]
         fn row_count(self: &MediaModel, parent: &QModelIndex) -> i32;
 
+        /// Replace the rows with the view `query` describes
+        /// (AppController.viewQuery() JSON)
+        #[qinvokable]
+        fn reload(self: Pin<&mut MediaModel>, query: &QString);
+
+        /// Like reload, but only touches rows for `ids` (comma-separated):
+        /// inserts, removes or refreshes them in place instead of resetting.
         #[qinvokable]
-        fn reload(self: Pin<&mut MediaModel>, page: &QString, status: &QString, search: &QString, sort_field: &QString, sort_dir: &QString);
+        #[cxx_name = "applyChanges"]
+        fn apply_changes(self: Pin<&mut MediaModel>, ids: &QString, query: &QString);
+
+        /// Replace the rows with the `limit` newest items across all media
+        /// types, by creation or (with `by_updated`) last update
+        #[qinvokable]
+        #[cxx_name = "loadRecent"]
+        fn load_recent(self: Pin<&mut MediaModel>, limit: i32, by_updated: bool);
 
         #[qinvokable]
         #[cxx_name = "getItemId"]
@@ -52,10 +70,38 @@ pub mod qobject {
         #[cxx_name = "getItemTitle"]
         fn get_item_title(self: &MediaModel, row: i32) -> QString;
 
+        /// The full row as a JSON object ("{}" if the row doesn't exist)
+        #[qinvokable]
+        #[cxx_name = "getItemJson"]
+        fn get_item_json(self: &MediaModel, row: i32) -> QString;
+
+        /// Row currently showing the item with this id, or -1
+        #[qinvokable]
+        #[cxx_name = "rowForId"]
+        fn row_for_id(self: &MediaModel, id: i32) -> i32;
+
         #[qinvokable]
         #[cxx_name = "getItemNativeTitle"]
         fn get_item_native_title(self: &MediaModel, row: i32) -> QString;
 
+        #[qinvokable]
+        #[cxx_name = "toggleSelected"]
+        fn toggle_selected(self: Pin<&mut MediaModel>, row: i32);
+
+        /// Select exactly the rows between from_row and to_row (inclusive, either order)
+        #[qinvokable]
+        #[cxx_name = "selectRange"]
+        fn select_range(self: Pin<&mut MediaModel>, from_row: i32, to_row: i32);
+
+        #[qinvokable]
+        #[cxx_name = "clearSelection"]
+        fn clear_selection(self: Pin<&mut MediaModel>);
+
+        /// Comma-separated ids in row order, as deleteItems/moveItems take them
+        #[qinvokable]
+        #[cxx_name = "getSelectedIds"]
+        fn get_selected_ids(self: &MediaModel) -> QString;
+
         #[qinvokable]
         #[cxx_name = "getItemRomajiTitle"]
         fn get_item_romaji_title(self: &MediaModel, row: i32) -> QString;
@@ -68,6 +114,33 @@ pub mod qobject {
         #[inherit]
         #[cxx_name = "endResetModel"]
         unsafe fn end_reset_model_media(self: Pin<&mut MediaModel>);
+        #[inherit]
+        #[cxx_name = "beginInsertRows"]
+        unsafe fn begin_insert_rows_media(self: Pin<&mut MediaModel>, parent: &QModelIndex, first: i32, last: i32);
+        #[inherit]
+        #[cxx_name = "endInsertRows"]
+        unsafe fn end_insert_rows_media(self: Pin<&mut MediaModel>);
+        #[inherit]
+        #[cxx_name = "beginRemoveRows"]
+        unsafe fn begin_remove_rows_media(self: Pin<&mut MediaModel>, parent: &QModelIndex, first: i32, last: i32);
+        #[inherit]
+        #[cxx_name = "endRemoveRows"]
+        unsafe fn end_remove_rows_media(self: Pin<&mut MediaModel>);
+        #[inherit]
+        #[cxx_name = "index"]
+        fn index_media(self: &MediaModel, row: i32, column: i32, parent: &QModelIndex) -> QModelIndex;
+    }
+
+    unsafe extern "RustQt" {
+        #[qsignal]
+        #[inherit]
+        #[cxx_name = "dataChanged"]
+        fn data_changed_media(
+            self: Pin<&mut MediaModel>,
+            top_left: &QModelIndex,
+            bottom_right: &QModelIndex,
+            roles: &QVector_i32,
+        );
     }
 
     // ── SearchModel ─────────────────────────────────────────────────────
@@ -100,12 +173,52 @@ pub mod qobject {
         #[cxx_name = "toggleSelection"]
         fn toggle_selection(self: Pin<&mut SearchModel>, row: i32);
 
+        /// Narrow the loaded results to titles containing `text` (any of
+        /// title/romaji/native, case-insensitive). Empty clears it.
+        #[qinvokable]
+        #[cxx_name = "setResultFilter"]
+        fn set_result_filter(self: Pin<&mut SearchModel>, text: &QString);
+
+        /// Keep results released within [min_year, max_year]; 0 leaves that end open
+        #[qinvokable]
+        #[cxx_name = "setYearFilter"]
+        fn set_year_filter(self: Pin<&mut SearchModel>, min_year: i32, max_year: i32);
+
+        /// Order rows by "relevance" (provider order), "rating", "popularity",
+        /// "year" (newest first) or "title"
+        #[qinvokable]
+        #[cxx_name = "setResultSort"]
+        fn set_result_sort(self: Pin<&mut SearchModel>, mode: &QString);
+
+        /// Let results already in the library be selected, for adding a
+        /// second copy. Turning it off deselects them.
+        #[qinvokable]
+        #[cxx_name = "setAllowOwned"]
+        fn set_allow_owned(self: Pin<&mut SearchModel>, allow: bool);
+
+        #[qinvokable]
+        #[cxx_name = "selectAll"]
+        fn select_all(self: Pin<&mut SearchModel>);
+
+        #[qinvokable]
+        #[cxx_name = "selectNone"]
+        fn select_none(self: Pin<&mut SearchModel>);
+
+        #[qinvokable]
+        #[cxx_name = "invertSelection"]
+        fn invert_selection(self: Pin<&mut SearchModel>);
+
         #[qinvokable]
         #[cxx_name = "getSelectedIndices"]
         fn get_selected_indices(self: &SearchModel) -> QString;
 
         #[qinvokable]
         fn clear(self: Pin<&mut SearchModel>);
+
+        /// Pick up a prefetched poster for one result from app state
+        #[qinvokable]
+        #[cxx_name = "refreshPoster"]
+        fn refresh_poster(self: Pin<&mut SearchModel>, result_index: i32);
     }
 
     extern "RustQt" {
@@ -115,15 +228,117 @@ pub mod qobject {
         #[inherit]
         #[cxx_name = "endResetModel"]
         unsafe fn end_reset_model_search(self: Pin<&mut SearchModel>);
+        #[inherit]
+        fn index(self: &SearchModel, row: i32, column: i32, parent: &QModelIndex) -> QModelIndex;
+    }
+
+    unsafe extern "RustQt" {
+        #[qsignal]
+        #[inherit]
+        #[cxx_name = "dataChanged"]
+        fn data_changed(
+            self: Pin<&mut SearchModel>,
+            top_left: &QModelIndex,
+            bottom_right: &QModelIndex,
+            roles: &QVector_i32,
+        );
+    }
+
+    // ── StatsModel ──────────────────────────────────────────────────────
+    extern "RustQt" {
+        #[qobject]
+        #[base = QAbstractListModel]
+        #[qml_element]
+        type StatsModel = super::StatsModelRust;
+
+        #[qinvokable]
+        #[cxx_override]
+        fn data(self: &StatsModel, index: &QModelIndex, role: i32) -> QVariant;
+
+        #[qinvokable]
+        #[cxx_override]
+        #[cxx_name = "roleNames"]
+        fn role_names(self: &StatsModel) -> QHash_i32_QByteArray;
+
+        #[qinvokable]
+        #[cxx_override]
+        #[cxx_name = "rowCount"]
+        fn row_count(self: &StatsModel, parent: &QModelIndex) -> i32;
+
+        /// Load counts for one category ("quality_type", "source", "year",
+        /// "status", "media_type", "watched"), or every category when empty. An empty
+        /// `page` counts across all media types.
+        #[qinvokable]
+        fn reload(self: Pin<&mut StatsModel>, category: &QString, page: &QString);
+    }
+
+    extern "RustQt" {
+        #[inherit]
+        #[cxx_name = "beginResetModel"]
+        unsafe fn begin_reset_model_stats(self: Pin<&mut StatsModel>);
+        #[inherit]
+        #[cxx_name = "endResetModel"]
+        unsafe fn end_reset_model_stats(self: Pin<&mut StatsModel>);
+    }
+
+    // ── NotificationModel ───────────────────────────────────────────────
+    extern "RustQt" {
+        #[qobject]
+        #[base = QAbstractListModel]
+        #[qml_element]
+        #[qproperty(i32, unread_count)]
+        type NotificationModel = super::NotificationModelRust;
+
+        #[qinvokable]
+        #[cxx_override]
+        fn data(self: &NotificationModel, index: &QModelIndex, role: i32) -> QVariant;
+
+        #[qinvokable]
+        #[cxx_override]
+        #[cxx_name = "roleNames"]
+        fn role_names(self: &NotificationModel) -> QHash_i32_QByteArray;
+
+        #[qinvokable]
+        #[cxx_override]
+        #[cxx_name = "rowCount"]
+        fn row_count(self: &NotificationModel, parent: &QModelIndex) -> i32;
+
+        /// Copy the notification history from the app state, newest first
+        #[qinvokable]
+        fn reload(self: Pin<&mut NotificationModel>);
+
+        /// Forget every notification
+        #[qinvokable]
+        fn clear(self: Pin<&mut NotificationModel>);
+
+        #[qinvokable]
+        #[cxx_name = "markRead"]
+        fn mark_read(self: Pin<&mut NotificationModel>);
+    }
+
+    extern "RustQt" {
+        #[inherit]
+        #[cxx_name = "beginResetModel"]
+        unsafe fn begin_reset_model_notifications(self: Pin<&mut NotificationModel>);
+        #[inherit]
+        #[cxx_name = "endResetModel"]
+        unsafe fn end_reset_model_notifications(self: Pin<&mut NotificationModel>);
     }
+
+    // Grid thumbnails are generated off the GUI thread and handed back
+    impl cxx_qt::Threading for MediaModel {}
 }
 
 use core::pin::Pin;
-use cxx_qt::CxxQtType;
-use cxx_qt_lib::{QByteArray, QHash, QHashPair_i32_QByteArray, QModelIndex, QString, QVariant};
+use cxx_qt::{CxxQtType, Threading};
+use cxx_qt_lib::{QByteArray, QHash, QHashPair_i32_QByteArray, QModelIndex, QString, QVariant, QVector};
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashSet;
+use std::path::PathBuf;
 
-use crate::bridge::get_app_state;
+use crate::bridge::{get_app_state, Notification, ViewQuery};
 use crate::db;
+use crate::models::MediaItem;
 
 // ═══════════════════════════════════════════════════════════════════════
 // MediaModel roles & types
@@ -142,6 +357,32 @@ const MEDIA_ROLE_SOURCE: i32 = 264;
 const MEDIA_ROLE_NOTES: i32 = 265;
 const MEDIA_ROLE_POSTER_PATH: i32 = 266;
 const MEDIA_ROLE_HAS_POSTER: i32 = 267;
+const MEDIA_ROLE_NUMBER_OF_SEASONS: i32 = 268;
+const MEDIA_ROLE_NUMBER_OF_EPISODES: i32 = 269;
+const MEDIA_ROLE_PROGRESS: i32 = 270;
+const MEDIA_ROLE_TOTAL_EPISODES: i32 = 271;
+const MEDIA_ROLE_THUMBNAIL_PATH: i32 = 272;
+const MEDIA_ROLE_FAVORITE: i32 = 273;
+const MEDIA_ROLE_ACQUIRED_DATE: i32 = 274;
+const MEDIA_ROLE_WATCHED_DATE: i32 = 275;
+const MEDIA_ROLE_POSTER_WIDTH: i32 = 276;
+const MEDIA_ROLE_POSTER_HEIGHT: i32 = 277;
+const MEDIA_ROLE_TMDB_ID: i32 = 278;
+const MEDIA_ROLE_ANILIST_ID: i32 = 279;
+const MEDIA_ROLE_CREATED_AT: i32 = 280;
+const MEDIA_ROLE_UPDATED_AT: i32 = 281;
+const MEDIA_ROLE_SELECTED: i32 = 282;
+const MEDIA_ROLE_MISSING_FIELDS: i32 = 283;
+const MEDIA_ROLE_MATCHED_FIELD: i32 = 284;
+const MEDIA_ROLE_MATCH_SNIPPET: i32 = 285;
+const MEDIA_ROLE_IMDB_ID: i32 = 286;
+const MEDIA_ROLE_ANIME_FORMAT: i32 = 287;
+const MEDIA_ROLE_SEASON: i32 = 288;
+const MEDIA_ROLE_WATCHED: i32 = 289;
+const MEDIA_ROLE_POSTER_SOURCE: i32 = 290;
+
+/// Characters of context `matchSnippet` shows around a search hit
+const MATCH_SNIPPET_CHARS: usize = 60;
 
 struct DisplayItem {
     id: i32,
@@ -156,11 +397,48 @@ struct DisplayItem {
     notes: String,
     poster_path: String,
     has_poster: bool,
+    /// Local cached original, used to derive the grid thumbnail on demand
+    cached_file: Option<PathBuf>,
+    /// Show the 2:3 framed copy in the grid instead of the plain thumbnail
+    uniform_poster: bool,
+    thumbnail_path: OnceCell<String>,
+    number_of_seasons: i32,
+    number_of_episodes: i32,
+    progress: i32, // -1 when not tracked
+    total_episodes: i32,
+    favorite: bool,
+    watched: bool,
+    acquired_date: String,
+    watched_date: String,
+    poster_width: i32, // 0 when unknown
+    poster_height: i32,
+    /// "manual" (set from a URL), "provider", or empty without a poster
+    poster_source: &'static str,
+    tmdb_id: i64, // 0 when not from TMDB
+    anilist_id: i64, // 0 when not from AniList
+    imdb_id: String, // "tt..." or empty
+    anime_format: String, // AniList format ("TV", "Movie", ...), empty if unknown
+    season: String,
+    created_at: String, // ISO 8601, empty if unknown
+    updated_at: String,
+    /// Comma-separated empty fields checked by the incomplete filter
+    /// ("year", "quality_type", "source")
+    missing_fields: String,
+    /// Field the search term was found in ("title", "romaji", "native",
+    /// "notes"); empty when not searching
+    matched_field: String,
+    /// Text around the hit in matched_field
+    match_snippet: String,
 }
 
 #[derive(Default)]
 pub struct MediaModelRust {
     items: Vec<DisplayItem>,
+    /// Ids rather than rows so a selection survives rows being patched
+    selected: HashSet<i32>,
+    selected_count: i32,
+    /// Items whose grid thumbnail is being generated on a worker thread
+    pending_thumbnails: RefCell<HashSet<i32>>,
 }
 
 impl qobject::MediaModel {
@@ -180,6 +458,29 @@ impl qobject::MediaModel {
                 MEDIA_ROLE_NOTES => QVariant::from(&QString::from(&item.notes)),
                 MEDIA_ROLE_POSTER_PATH => QVariant::from(&QString::from(&item.poster_path)),
                 MEDIA_ROLE_HAS_POSTER => QVariant::from(&item.has_poster),
+                MEDIA_ROLE_THUMBNAIL_PATH => QVariant::from(&QString::from(self.thumbnail_for(item))),
+                MEDIA_ROLE_NUMBER_OF_SEASONS => QVariant::from(&item.number_of_seasons),
+                MEDIA_ROLE_NUMBER_OF_EPISODES => QVariant::from(&item.number_of_episodes),
+                MEDIA_ROLE_PROGRESS => QVariant::from(&item.progress),
+                MEDIA_ROLE_TOTAL_EPISODES => QVariant::from(&item.total_episodes),
+                MEDIA_ROLE_FAVORITE => QVariant::from(&item.favorite),
+                MEDIA_ROLE_ACQUIRED_DATE => QVariant::from(&QString::from(&item.acquired_date)),
+                MEDIA_ROLE_WATCHED_DATE => QVariant::from(&QString::from(&item.watched_date)),
+                MEDIA_ROLE_POSTER_WIDTH => QVariant::from(&item.poster_width),
+                MEDIA_ROLE_POSTER_HEIGHT => QVariant::from(&item.poster_height),
+                MEDIA_ROLE_TMDB_ID => QVariant::from(&item.tmdb_id),
+                MEDIA_ROLE_ANILIST_ID => QVariant::from(&item.anilist_id),
+                MEDIA_ROLE_CREATED_AT => QVariant::from(&QString::from(&item.created_at)),
+                MEDIA_ROLE_UPDATED_AT => QVariant::from(&QString::from(&item.updated_at)),
+                MEDIA_ROLE_SELECTED => QVariant::from(&self.selected.contains(&item.id)),
+                MEDIA_ROLE_MISSING_FIELDS => QVariant::from(&QString::from(&item.missing_fields)),
+                MEDIA_ROLE_MATCHED_FIELD => QVariant::from(&QString::from(&item.matched_field)),
+                MEDIA_ROLE_MATCH_SNIPPET => QVariant::from(&QString::from(&item.match_snippet)),
+                MEDIA_ROLE_IMDB_ID => QVariant::from(&QString::from(&item.imdb_id)),
+                MEDIA_ROLE_ANIME_FORMAT => QVariant::from(&QString::from(&item.anime_format)),
+                MEDIA_ROLE_SEASON => QVariant::from(&QString::from(&item.season)),
+                MEDIA_ROLE_WATCHED => QVariant::from(&item.watched),
+                MEDIA_ROLE_POSTER_SOURCE => QVariant::from(&QString::from(item.poster_source)),
                 _ => QVariant::default(),
             };
         }
@@ -200,6 +501,29 @@ impl qobject::MediaModel {
         roles.insert(MEDIA_ROLE_NOTES, QByteArray::from("notes"));
         roles.insert(MEDIA_ROLE_POSTER_PATH, QByteArray::from("posterPath"));
         roles.insert(MEDIA_ROLE_HAS_POSTER, QByteArray::from("hasPoster"));
+        roles.insert(MEDIA_ROLE_THUMBNAIL_PATH, QByteArray::from("thumbnailPath"));
+        roles.insert(MEDIA_ROLE_NUMBER_OF_SEASONS, QByteArray::from("numberOfSeasons"));
+        roles.insert(MEDIA_ROLE_NUMBER_OF_EPISODES, QByteArray::from("numberOfEpisodes"));
+        roles.insert(MEDIA_ROLE_PROGRESS, QByteArray::from("progress"));
+        roles.insert(MEDIA_ROLE_TOTAL_EPISODES, QByteArray::from("totalEpisodes"));
+        roles.insert(MEDIA_ROLE_FAVORITE, QByteArray::from("favorite"));
+        roles.insert(MEDIA_ROLE_ACQUIRED_DATE, QByteArray::from("acquiredDate"));
+        roles.insert(MEDIA_ROLE_WATCHED_DATE, QByteArray::from("watchedDate"));
+        roles.insert(MEDIA_ROLE_POSTER_WIDTH, QByteArray::from("posterWidth"));
+        roles.insert(MEDIA_ROLE_POSTER_HEIGHT, QByteArray::from("posterHeight"));
+        roles.insert(MEDIA_ROLE_TMDB_ID, QByteArray::from("tmdbId"));
+        roles.insert(MEDIA_ROLE_ANILIST_ID, QByteArray::from("anilistId"));
+        roles.insert(MEDIA_ROLE_CREATED_AT, QByteArray::from("createdAt"));
+        roles.insert(MEDIA_ROLE_UPDATED_AT, QByteArray::from("updatedAt"));
+        roles.insert(MEDIA_ROLE_SELECTED, QByteArray::from("selected"));
+        roles.insert(MEDIA_ROLE_MISSING_FIELDS, QByteArray::from("missingFields"));
+        roles.insert(MEDIA_ROLE_MATCHED_FIELD, QByteArray::from("matchedField"));
+        roles.insert(MEDIA_ROLE_MATCH_SNIPPET, QByteArray::from("matchSnippet"));
+        roles.insert(MEDIA_ROLE_IMDB_ID, QByteArray::from("imdbId"));
+        roles.insert(MEDIA_ROLE_ANIME_FORMAT, QByteArray::from("animeFormat"));
+        roles.insert(MEDIA_ROLE_SEASON, QByteArray::from("season"));
+        roles.insert(MEDIA_ROLE_WATCHED, QByteArray::from("watched"));
+        roles.insert(MEDIA_ROLE_POSTER_SOURCE, QByteArray::from("posterSource"));
         roles
     }
 
@@ -207,56 +531,152 @@ impl qobject::MediaModel {
         self.items.len() as i32
     }
 
-    pub fn reload(mut self: Pin<&mut Self>, page: &QString, status: &QString, search: &QString, sort_field: &QString, sort_dir: &QString) {
-        let page_str = page.to_string();
-        let status_str = status.to_string();
-        let search_str = search.to_string();
-        let sort_f = sort_field.to_string();
-        let sort_d = sort_dir.to_string();
-
+    pub fn reload(mut self: Pin<&mut Self>, query: &QString) {
+        let view = parse_view(query);
+        let db_items = view.items().unwrap_or_default();
         let state = get_app_state();
-        let conn = state.db.lock().unwrap();
+        let data_dir = &state.data_dir;
+        let uniform = state.config.lock().unwrap().uniform_posters;
+        let display_items: Vec<DisplayItem> = db_items
+            .iter()
+            .map(|item| DisplayItem::from_media_item(item, data_dir, uniform).with_search_match(&view.search))
+            .collect();
 
-        let db_items = if search_str.is_empty() {
-            db::queries::get_items_sorted(&conn, Some(&page_str), Some(&status_str), &sort_f, &sort_d).unwrap_or_default()
-        } else {
-            db::queries::search_items(&conn, &search_str, Some(&page_str), Some(&status_str)).unwrap_or_default()
-        };
-        drop(conn);
+        unsafe {
+            self.as_mut().begin_reset_model_media();
+            self.as_mut().rust_mut().items = display_items;
+            self.as_mut().rust_mut().selected.clear();
+            self.as_mut().end_reset_model_media();
+        }
+        self.as_mut().set_selected_count(0);
+    }
 
-        let data_dir = &state.data_dir;
+    pub fn load_recent(mut self: Pin<&mut Self>, limit: i32, by_updated: bool) {
+        let state = get_app_state();
+        let db_items = {
+            let conn = state.db.lock().unwrap();
+            db::queries::get_recent_items(&conn, limit.max(0) as usize, by_updated).unwrap_or_default()
+        };
+        let uniform = state.config.lock().unwrap().uniform_posters;
         let display_items: Vec<DisplayItem> = db_items
             .iter()
-            .map(|item| {
-                let (poster_path, has_poster) = resolve_poster(item.poster_url.as_deref(), data_dir);
-                DisplayItem {
-                    id: item.id.unwrap_or(-1) as i32,
-                    title: item.title.clone(),
-                    native_title: item.native_title.clone().unwrap_or_default(),
-                    romaji_title: item.romaji_title.clone().unwrap_or_default(),
-                    year: item.year.unwrap_or(0),
-                    media_type: item.media_type.clone(),
-                    status: item.status.clone(),
-                    quality_type: item.quality_type.clone().unwrap_or_default(),
-                    source: item.source.clone().unwrap_or_default(),
-                    notes: item.notes.clone().unwrap_or_default(),
-                    poster_path,
-                    has_poster,
-                }
-            })
+            .map(|item| DisplayItem::from_media_item(item, &state.data_dir, uniform))
             .collect();
 
         unsafe {
             self.as_mut().begin_reset_model_media();
             self.as_mut().rust_mut().items = display_items;
+            self.as_mut().rust_mut().selected.clear();
             self.as_mut().end_reset_model_media();
         }
+        self.as_mut().set_selected_count(0);
+    }
+
+    pub fn apply_changes(mut self: Pin<&mut Self>, ids: &QString, query: &QString) {
+        let changed: HashSet<i32> = ids
+            .to_string()
+            .split(',')
+            .filter_map(|s| s.trim().parse().ok())
+            .collect();
+        let view = parse_view(query);
+        let db_items = view.items().unwrap_or_default();
+        let new_ids: Vec<i32> = db_items.iter().map(|item| item.id.unwrap_or(-1) as i32).collect();
+        let new_set: HashSet<i32> = new_ids.iter().copied().collect();
+
+        // Rows that left the view: deleted, or no longer matching the filter
+        self.as_mut().remove_rows_where(|item| !new_set.contains(&item.id));
+
+        // An edit can move its row (e.g. a rename under title sort). Take the
+        // edited rows out and let the insert pass put them back in place.
+        if !self.rows_in_order(&new_ids) {
+            self.as_mut().remove_rows_where(|item| changed.contains(&item.id));
+        }
+        if !self.rows_in_order(&new_ids) {
+            // Something else moved as well; fall back to a full reset
+            self.reload(query);
+            return;
+        }
+
+        // Every remaining row is now in new_ids order, so walking new_ids
+        // finds each missing row's sorted position
+        let state = get_app_state();
+        let data_dir = &state.data_dir;
+        let uniform = state.config.lock().unwrap().uniform_posters;
+        let mut inserted = HashSet::new();
+        for (row, item) in db_items.iter().enumerate() {
+            let id = new_ids[row];
+            if self.items.get(row).map(|i| i.id) == Some(id) {
+                continue;
+            }
+            let display = DisplayItem::from_media_item(item, data_dir, uniform).with_search_match(&view.search);
+            unsafe {
+                self.as_mut().begin_insert_rows_media(&QModelIndex::default(), row as i32, row as i32);
+                self.as_mut().rust_mut().items.insert(row, display);
+                self.as_mut().end_insert_rows_media();
+            }
+            inserted.insert(id);
+        }
+
+        for (row, item) in db_items.iter().enumerate() {
+            let id = new_ids[row];
+            if !changed.contains(&id) || inserted.contains(&id) {
+                continue;
+            }
+            self.as_mut().rust_mut().items[row] =
+                DisplayItem::from_media_item(item, data_dir, uniform).with_search_match(&view.search);
+            let idx = self.index_media(row as i32, 0, &QModelIndex::default());
+            self.as_mut().data_changed_media(&idx, &idx, &QVector::<i32>::default());
+        }
+
+        // Rows that left the view can't stay selected
+        self.as_mut().rust_mut().selected.retain(|id| new_set.contains(id));
+        let count = self.selected.len() as i32;
+        self.as_mut().set_selected_count(count);
+    }
+
+    fn remove_rows_where(mut self: Pin<&mut Self>, remove: impl Fn(&DisplayItem) -> bool) {
+        for row in (0..self.items.len()).rev() {
+            if !remove(&self.items[row]) {
+                continue;
+            }
+            unsafe {
+                self.as_mut().begin_remove_rows_media(&QModelIndex::default(), row as i32, row as i32);
+                self.as_mut().rust_mut().items.remove(row);
+                self.as_mut().end_remove_rows_media();
+            }
+        }
+    }
+
+    /// Whether the current rows appear in `ids` in the same relative order
+    fn rows_in_order(&self, ids: &[i32]) -> bool {
+        let mut remaining = ids.iter();
+        self.items.iter().all(|item| remaining.any(|id| *id == item.id))
     }
 
     pub fn get_item_id(&self, row: i32) -> i32 {
         self.items.get(row as usize).map(|i| i.id).unwrap_or(-1)
     }
 
+    pub fn get_item_json(&self, row: i32) -> QString {
+        let Some(item) = self.items.get(row as usize) else {
+            return QString::from("{}");
+        };
+        let state = get_app_state();
+        let conn = state.db.lock().unwrap();
+        match db::queries::get_item_by_id(&conn, item.id as i64) {
+            Ok(Some(full)) => QString::from(&serde_json::to_string(&full).unwrap_or_else(|_| "{}".into())),
+            _ => QString::from("{}"),
+        }
+    }
+
+    pub fn row_for_id(&self, id: i32) -> i32 {
+        self.items
+            .iter()
+            .position(|i| i.id == id)
+            .map(|row| row as i32)
+            .unwrap_or(-1)
+    }
+
     pub fn get_item_title(&self, row: i32) -> QString {
         self.items
             .get(row as usize)
@@ -271,6 +691,111 @@ impl qobject::MediaModel {
             .unwrap_or_default()
     }
 
+    pub fn toggle_selected(mut self: Pin<&mut Self>, row: i32) {
+        let Some(id) = self.items.get(row as usize).map(|i| i.id) else {
+            return;
+        };
+        let mut rust = self.as_mut().rust_mut();
+        if !rust.selected.remove(&id) {
+            rust.selected.insert(id);
+        }
+        self.as_mut().selection_changed(row, row);
+    }
+
+    pub fn select_range(mut self: Pin<&mut Self>, from_row: i32, to_row: i32) {
+        let rows = self.items.len() as i32;
+        if rows == 0 {
+            return;
+        }
+        let first = from_row.min(to_row).clamp(0, rows - 1);
+        let last = from_row.max(to_row).clamp(0, rows - 1);
+        let range: HashSet<i32> = self.items[first as usize..=last as usize].iter().map(|i| i.id).collect();
+        self.as_mut().rust_mut().selected = range;
+        self.as_mut().selection_changed(0, rows - 1);
+    }
+
+    pub fn clear_selection(mut self: Pin<&mut Self>) {
+        if self.selected.is_empty() {
+            return;
+        }
+        self.as_mut().rust_mut().selected.clear();
+        let rows = self.items.len() as i32;
+        self.as_mut().selection_changed(0, rows - 1);
+    }
+
+    pub fn get_selected_ids(&self) -> QString {
+        let ids: Vec<String> = self
+            .items
+            .iter()
+            .filter(|i| self.selected.contains(&i.id))
+            .map(|i| i.id.to_string())
+            .collect();
+        QString::from(&ids.join(","))
+    }
+
+    /// Thumbnail for a row's delegate. One that doesn't exist yet is generated
+    /// on a worker thread; the original poster shows until it's ready.
+    fn thumbnail_for<'a>(&self, item: &'a DisplayItem) -> &'a str {
+        if let Some(path) = item.ready_thumbnail() {
+            return path;
+        }
+        let Some(original) = item.cached_file.clone() else {
+            return &item.poster_path;
+        };
+        if self.pending_thumbnails.borrow_mut().insert(item.id) {
+            let (id, uniform) = (item.id, item.uniform_poster);
+            let qt_thread = self.qt_thread();
+            get_app_state().runtime.spawn_blocking(move || {
+                let thumb = if uniform {
+                    crate::images::cache::ensure_framed(&original)
+                } else {
+                    crate::images::cache::ensure_thumbnail(&original)
+                };
+                // Undecodable files come back as the original; either way
+                // the row stops asking
+                let url = crate::images::cache::file_url(&thumb);
+                let _ = qt_thread.queue(move |model: Pin<&mut qobject::MediaModel>| {
+                    model.thumbnail_ready(id, &original, uniform, url);
+                });
+            });
+        }
+        &item.poster_path
+    }
+
+    /// Show a thumbnail generated by `thumbnail_for`, unless the row has
+    /// since been replaced with a different poster
+    fn thumbnail_ready(mut self: Pin<&mut Self>, id: i32, original: &std::path::Path, uniform: bool, url: String) {
+        self.pending_thumbnails.borrow_mut().remove(&id);
+        let Some(row) = self.items.iter().position(|item| item.id == id) else {
+            return;
+        };
+        let item = &self.items[row];
+        if item.cached_file.as_deref() != Some(original) || item.uniform_poster != uniform {
+            return;
+        }
+        if item.thumbnail_path.set(url).is_err() {
+            return;
+        }
+        let idx = self.index_media(row as i32, 0, &QModelIndex::default());
+        let mut roles = QVector::<i32>::default();
+        roles.append(MEDIA_ROLE_THUMBNAIL_PATH);
+        self.as_mut().data_changed_media(&idx, &idx, &roles);
+    }
+
+    /// Refresh `selected_count` and repaint the selection role for rows first..=last
+    fn selection_changed(mut self: Pin<&mut Self>, first: i32, last: i32) {
+        let count = self.selected.len() as i32;
+        self.as_mut().set_selected_count(count);
+        if first > last {
+            return;
+        }
+        let top = self.index_media(first, 0, &QModelIndex::default());
+        let bottom = self.index_media(last, 0, &QModelIndex::default());
+        let mut roles = QVector::<i32>::default();
+        roles.append(MEDIA_ROLE_SELECTED);
+        self.as_mut().data_changed_media(&top, &bottom, &roles);
+    }
+
     pub fn get_item_romaji_title(&self, row: i32) -> QString {
         self.items
             .get(row as usize)
@@ -279,28 +804,165 @@ impl qobject::MediaModel {
     }
 }
 
-fn resolve_poster(poster_url: Option<&str>, data_dir: &std::path::Path) -> (String, bool) {
+/// Parse a `viewQuery()` JSON; a malformed one shows an empty page.
+fn parse_view(query: &QString) -> ViewQuery {
+    serde_json::from_str(&query.to_string()).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring malformed view query: {}", e);
+        ViewQuery::default()
+    })
+}
+
+impl DisplayItem {
+    fn from_media_item(item: &MediaItem, data_dir: &std::path::Path, uniform_poster: bool) -> Self {
+        let (poster_path, has_poster, cached_file) = resolve_poster(item.poster_url.as_deref(), data_dir);
+        DisplayItem {
+            id: item.id.unwrap_or(-1) as i32,
+            title: item.title.clone(),
+            native_title: item.native_title.clone().unwrap_or_default(),
+            romaji_title: item.romaji_title.clone().unwrap_or_default(),
+            year: item.year.unwrap_or(0),
+            media_type: item.media_type.clone(),
+            status: item.status.clone(),
+            quality_type: item.quality_type.clone().unwrap_or_default(),
+            source: item.source.clone().unwrap_or_default(),
+            notes: item.notes.clone().unwrap_or_default(),
+            poster_path,
+            has_poster,
+            cached_file,
+            uniform_poster,
+            thumbnail_path: OnceCell::new(),
+            number_of_seasons: item.number_of_seasons.unwrap_or(0),
+            number_of_episodes: item.number_of_episodes.unwrap_or(0),
+            progress: item.progress.unwrap_or(-1),
+            total_episodes: item.total_episodes.unwrap_or(0),
+            favorite: item.favorite,
+            watched: item.watched,
+            acquired_date: item.acquired_date.clone().unwrap_or_default(),
+            watched_date: item.watched_date.clone().unwrap_or_default(),
+            poster_width: item.poster_width,
+            poster_height: item.poster_height,
+            poster_source: match (has_poster, item.poster_manual) {
+                (false, _) => "",
+                (true, true) => "manual",
+                (true, false) => "provider",
+            },
+            tmdb_id: item.tmdb_id.unwrap_or(0),
+            anilist_id: item.anilist_id.unwrap_or(0),
+            imdb_id: item.imdb_id.clone().unwrap_or_default(),
+            anime_format: item.anime_format.clone().unwrap_or_default(),
+            season: item.season.clone().unwrap_or_default(),
+            created_at: item.created_at.as_deref().map(to_iso_timestamp).unwrap_or_default(),
+            updated_at: item.updated_at.as_deref().map(to_iso_timestamp).unwrap_or_default(),
+            missing_fields: missing_fields(item),
+            matched_field: String::new(),
+            match_snippet: String::new(),
+        }
+    }
+
+    /// Record which field `search` matched (same folding as the SQL search)
+    /// and the text around the hit, so a row whose title doesn't contain the
+    /// term can say why it's there.
+    fn with_search_match(mut self, search: &str) -> Self {
+        let term = db::fold::fold(search.trim());
+        let fields = [
+            ("title", &self.title),
+            ("romaji", &self.romaji_title),
+            ("native", &self.native_title),
+            ("notes", &self.notes),
+        ];
+        let hit = fields.into_iter().find_map(|(name, text)| {
+            db::fold::find_folded(text, &term).map(|range| (name, match_snippet(text, range)))
+        });
+        if let Some((name, snippet)) = hit {
+            self.matched_field = name.to_string();
+            self.match_snippet = snippet;
+        }
+        self
+    }
+
+    /// Grid-sized poster (framed to 2:3 with `uniform_poster`) if it's on
+    /// disk already; None while it still has to be generated. Remote posters
+    /// use `poster_path` as is.
+    fn ready_thumbnail(&self) -> Option<&str> {
+        if self.thumbnail_path.get().is_none() {
+            let path = match &self.cached_file {
+                Some(original) => {
+                    let variant = if self.uniform_poster {
+                        crate::images::cache::framed_path(original)
+                    } else {
+                        crate::images::cache::thumbnail_path(original)
+                    };
+                    crate::images::cache::file_url(&variant.filter(|p| p.exists())?)
+                }
+                None => self.poster_path.clone(),
+            };
+            let _ = self.thumbnail_path.set(path);
+        }
+        self.thumbnail_path.get().map(String::as_str)
+    }
+}
+
+fn resolve_poster(poster_url: Option<&str>, data_dir: &std::path::Path) -> (String, bool, Option<PathBuf>) {
     if let Some(raw_url) = poster_url {
         let url = raw_url.trim();
         if !url.is_empty() {
             if url.starts_with("http://") || url.starts_with("https://") {
-                return (url.to_string(), true);
-            }
-
-            let direct = crate::images::cache::resolve_cached_poster_path(url, data_dir);
-            if direct.exists() {
-                return (format!("file://{}", direct.to_string_lossy()), true);
+                return (url.to_string(), true, None);
             }
 
-            if let Some(name) = std::path::Path::new(url).file_name() {
-                let fallback = data_dir.join("image_cache").join(name);
-                if fallback.exists() {
-                    return (format!("file://{}", fallback.to_string_lossy()), true);
-                }
+            if let Some(path) = crate::images::cache::find_cached_poster(url, data_dir) {
+                return (crate::images::cache::file_url(&path), true, Some(path));
             }
         }
     }
-    (String::new(), false)
+    (String::new(), false, None)
+}
+
+/// About MATCH_SNIPPET_CHARS of `text` centred on the byte range `hit`, on
+/// one line, with "…" where it was cut.
+fn match_snippet(text: &str, hit: std::ops::Range<usize>) -> String {
+    let hit_chars = text[hit.clone()].chars().count();
+    let context = MATCH_SNIPPET_CHARS.saturating_sub(hit_chars) / 2;
+    let before: Vec<char> = text[..hit.start].chars().collect();
+    let after: Vec<char> = text[hit.end..].chars().collect();
+    let from = before.len().saturating_sub(context);
+    let to = after.len().min(context);
+
+    let mut snippet = String::new();
+    if from > 0 {
+        snippet.push('…');
+    }
+    snippet.extend(&before[from..]);
+    snippet.push_str(&text[hit]);
+    snippet.extend(&after[..to]);
+    if to < after.len() {
+        snippet.push('…');
+    }
+    snippet.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Fields `ItemFilter::incomplete` checks that are empty on this item
+fn missing_fields(item: &MediaItem) -> String {
+    let blank = |v: &Option<String>| v.as_deref().unwrap_or("").trim().is_empty();
+    let mut missing = Vec::new();
+    if item.year.is_none() {
+        missing.push("year");
+    }
+    if blank(&item.quality_type) {
+        missing.push("quality_type");
+    }
+    if blank(&item.source) {
+        missing.push("source");
+    }
+    missing.join(",")
+}
+
+/// SQLite's CURRENT_TIMESTAMP ("YYYY-MM-DD HH:MM:SS", UTC) as ISO 8601
+fn to_iso_timestamp(ts: &str) -> String {
+    match ts.split_once(' ') {
+        Some((date, time)) if !ts.contains('T') => format!("{}T{}Z", date, time),
+        _ => ts.to_string(),
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -316,6 +978,13 @@ const SEARCH_ROLE_POSTER_PATH: i32 = 261;
 const SEARCH_ROLE_HAS_POSTER: i32 = 262;
 const SEARCH_ROLE_SELECTED: i32 = 263;
 const SEARCH_ROLE_INDEX: i32 = 264;
+const SEARCH_ROLE_POSTER_URL: i32 = 265;
+const SEARCH_ROLE_ALREADY_OWNED: i32 = 266;
+const SEARCH_ROLE_OWNED_STATUS: i32 = 267;
+const SEARCH_ROLE_VOTE_AVERAGE: i32 = 268;
+const SEARCH_ROLE_POPULARITY: i32 = 269;
+const SEARCH_ROLE_ANIME_FORMAT: i32 = 270;
+const SEARCH_ROLE_SEASON: i32 = 271;
 
 struct SearchItem {
     title: String,
@@ -323,22 +992,131 @@ struct SearchItem {
     romaji_title: String,
     year: i32,
     overview: String,
-    poster_path: String,
+    poster_path: String, // prefetched local thumbnail once ready, else the remote URL
+    poster_url: String,  // always the provider URL
     has_poster: bool,
     selected: bool,
     index: i32,
+    /// Status of the matching library item, if this result is already owned
+    owned_status: Option<String>,
+    vote_average: f64, // 0 when the provider has no score
+    popularity: f64,
+    vote_count: i64,
+    anime_format: String, // empty outside AniList anime
+    season: String,
+}
+
+impl SearchItem {
+    fn matches(&self, text: &str, min_year: i32, max_year: i32) -> bool {
+        if min_year > 0 && self.year < min_year {
+            return false;
+        }
+        if max_year > 0 && (self.year == 0 || self.year > max_year) {
+            return false;
+        }
+        text.is_empty()
+            || [&self.title, &self.romaji_title, &self.native_title]
+                .iter()
+                .any(|t| t.to_lowercase().contains(text))
+    }
 }
 
 #[derive(Default)]
 pub struct SearchModelRust {
+    /// Every loaded result, in result order (`items[i].index == i`)
     items: Vec<SearchItem>,
+    /// Positions in `items` that pass the current filter, one per model row
+    rows: Vec<usize>,
+    filter_text: String, // lowercased
+    min_year: i32,
+    max_year: i32,
+    sort_mode: String, // see set_result_sort; empty is provider order
+    /// Owned results can be selected too (adding duplicates is allowed)
+    allow_owned: bool,
     selected_count: i32,
 }
 
+impl SearchModelRust {
+    fn item_at(&self, row: i32) -> Option<&SearchItem> {
+        let pos = *self.rows.get(usize::try_from(row).ok()?)?;
+        self.items.get(pos)
+    }
+
+    fn visible_items(&self) -> impl Iterator<Item = &SearchItem> {
+        self.rows.iter().filter_map(|&pos| self.items.get(pos))
+    }
+
+    /// Selected rows that are currently visible; hidden selections don't count
+    fn visible_selected_count(&self) -> i32 {
+        self.visible_items().filter(|i| i.selected).count() as i32
+    }
+
+    /// Flip the selection of the result shown at `row`. Unless `allow_owned`
+    /// is on, owned results can't be selected (they'd be skipped as
+    /// duplicates on add), only unselected. Returns false when nothing changed.
+    fn toggle_row(&mut self, row: i32) -> bool {
+        let Some(pos) = usize::try_from(row).ok().and_then(|r| self.rows.get(r).copied()) else {
+            return false;
+        };
+        let allow_owned = self.allow_owned;
+        match self.items.get_mut(pos) {
+            Some(item) if allow_owned || item.owned_status.is_none() || item.selected => {
+                item.selected = !item.selected;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Set every visible row's `selected` flag from its current value. Owned
+    /// rows end up unselected unless `allow_owned` is on.
+    fn update_visible_selected(&mut self, f: impl Fn(bool) -> bool) {
+        for &pos in &self.rows {
+            if let Some(item) = self.items.get_mut(pos) {
+                item.selected = (self.allow_owned || item.owned_status.is_none()) && f(item.selected);
+            }
+        }
+    }
+
+    fn apply_filter(&mut self) {
+        let (text, min_year, max_year) = (&self.filter_text, self.min_year, self.max_year);
+        self.rows = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.matches(text, min_year, max_year))
+            .map(|(pos, _)| pos)
+            .collect();
+
+        // Rows stay positions into `items`, so sorting never changes which
+        // result a row adds. Ties keep provider order.
+        let items = &self.items;
+        match self.sort_mode.as_str() {
+            "rating" => self.rows.sort_by(|&a, &b| {
+                items[b]
+                    .vote_average
+                    .total_cmp(&items[a].vote_average)
+                    .then(items[b].popularity.total_cmp(&items[a].popularity))
+                    .then(a.cmp(&b))
+            }),
+            "popularity" => self.rows.sort_by(|&a, &b| {
+                items[b]
+                    .popularity
+                    .total_cmp(&items[a].popularity)
+                    .then(items[b].vote_count.cmp(&items[a].vote_count))
+                    .then(a.cmp(&b))
+            }),
+            // Undated results last
+            "year" => self.rows.sort_by(|&a, &b| items[b].year.cmp(&items[a].year).then(a.cmp(&b))),
+            "title" => self.rows.sort_by_cached_key(|&pos| (items[pos].title.to_lowercase(), pos)),
+            _ => {}
+        }
+    }
+}
+
 impl qobject::SearchModel {
     pub fn data(&self, index: &QModelIndex, role: i32) -> QVariant {
-        let row = index.row() as usize;
-        if let Some(item) = self.items.get(row) {
+        if let Some(item) = self.item_at(index.row()) {
             return match role {
                 SEARCH_ROLE_TITLE => QVariant::from(&QString::from(&item.title)),
                 SEARCH_ROLE_NATIVE_TITLE => QVariant::from(&QString::from(&item.native_title)),
@@ -349,6 +1127,15 @@ impl qobject::SearchModel {
                 SEARCH_ROLE_HAS_POSTER => QVariant::from(&item.has_poster),
                 SEARCH_ROLE_SELECTED => QVariant::from(&item.selected),
                 SEARCH_ROLE_INDEX => QVariant::from(&item.index),
+                SEARCH_ROLE_POSTER_URL => QVariant::from(&QString::from(&item.poster_url)),
+                SEARCH_ROLE_ALREADY_OWNED => QVariant::from(&item.owned_status.is_some()),
+                SEARCH_ROLE_OWNED_STATUS => {
+                    QVariant::from(&QString::from(item.owned_status.as_deref().unwrap_or("")))
+                }
+                SEARCH_ROLE_VOTE_AVERAGE => QVariant::from(&item.vote_average),
+                SEARCH_ROLE_POPULARITY => QVariant::from(&item.popularity),
+                SEARCH_ROLE_ANIME_FORMAT => QVariant::from(&QString::from(&item.anime_format)),
+                SEARCH_ROLE_SEASON => QVariant::from(&QString::from(&item.season)),
                 _ => QVariant::default(),
             };
         }
@@ -366,23 +1153,57 @@ impl qobject::SearchModel {
         roles.insert(SEARCH_ROLE_HAS_POSTER, QByteArray::from("hasPoster"));
         roles.insert(SEARCH_ROLE_SELECTED, QByteArray::from("selected"));
         roles.insert(SEARCH_ROLE_INDEX, QByteArray::from("resultIndex"));
+        roles.insert(SEARCH_ROLE_POSTER_URL, QByteArray::from("posterUrl"));
+        roles.insert(SEARCH_ROLE_ALREADY_OWNED, QByteArray::from("alreadyOwned"));
+        roles.insert(SEARCH_ROLE_OWNED_STATUS, QByteArray::from("ownedStatus"));
+        roles.insert(SEARCH_ROLE_VOTE_AVERAGE, QByteArray::from("voteAverage"));
+        roles.insert(SEARCH_ROLE_POPULARITY, QByteArray::from("popularity"));
+        roles.insert(SEARCH_ROLE_ANIME_FORMAT, QByteArray::from("animeFormat"));
+        roles.insert(SEARCH_ROLE_SEASON, QByteArray::from("season"));
         roles
     }
 
     pub fn row_count(&self, _parent: &QModelIndex) -> i32 {
-        self.items.len() as i32
+        self.rows.len() as i32
     }
 
     pub fn load_from_state(mut self: Pin<&mut Self>) {
         let state = get_app_state();
         let results = state.search_results.lock().unwrap();
+        let cached = state.cached_poster_paths.lock().unwrap();
+
+        // One lookup for the whole result set; a failed query just leaves nothing flagged
+        let media_type = state.search_media_type.lock().unwrap().clone();
+        let candidates: Vec<(Option<i64>, Vec<&str>, Option<i32>)> = results
+            .iter()
+            .map(|r| {
+                let titles = [Some(r.title.as_str()), r.native_title.as_deref(), r.romaji_title.as_deref()]
+                    .into_iter()
+                    .flatten()
+                    .filter(|t| !t.trim().is_empty())
+                    .collect();
+                (Some(r.api_id), titles, r.year)
+            })
+            .collect();
+        let owned = {
+            let conn = state.db.lock().unwrap();
+            db::queries::find_existing_by_api_ids(&conn, &media_type, &candidates)
+                .unwrap_or_else(|_| vec![None; results.len()])
+        };
 
         let items: Vec<SearchItem> = results
             .iter()
+            .zip(owned)
             .enumerate()
-            .map(|(i, r)| {
-                // Use the original remote URL directly — QML Image can load HTTP URLs
-                let poster_path = r.poster_url.clone().unwrap_or_default();
+            .map(|(i, (r, owned_status))| {
+                // Prefer the prefetched copy; QML Image can load the HTTP URL meanwhile
+                let poster_url = r.poster_url.clone().unwrap_or_default();
+                let poster_path = cached
+                    .get(i)
+                    .cloned()
+                    .flatten()
+                    .map(|path| crate::images::cache::stored_poster_url(&path, &state.data_dir))
+                    .unwrap_or_else(|| poster_url.clone());
                 let has_poster = !poster_path.is_empty();
 
                 SearchItem {
@@ -392,53 +1213,431 @@ impl qobject::SearchModel {
                     year: r.year.unwrap_or(0),
                     overview: r.overview.clone().unwrap_or_default(),
                     poster_path,
+                    poster_url,
                     has_poster,
                     selected: false,
                     index: i as i32,
+                    owned_status,
+                    vote_average: r.vote_average.unwrap_or(0.0),
+                    popularity: r.popularity.unwrap_or(0.0),
+                    vote_count: r.vote_count.unwrap_or(0),
+                    anime_format: r.anime_format.clone().unwrap_or_default(),
+                    season: r.season.clone().unwrap_or_default(),
                 }
             })
             .collect();
 
+        drop(cached);
         drop(results);
 
+        // A new result set starts unfiltered, but keeps the rating sort
         unsafe {
             self.as_mut().begin_reset_model_search();
-            self.as_mut().rust_mut().items = items;
+            let mut rust = self.as_mut().rust_mut();
+            rust.items = items;
+            rust.filter_text.clear();
+            rust.min_year = 0;
+            rust.max_year = 0;
+            rust.apply_filter();
             self.as_mut().set_selected_count(0);
             self.as_mut().end_reset_model_search();
         }
     }
 
-    pub fn toggle_selection(mut self: Pin<&mut Self>, row: i32) {
-        if let Some(item) = self.as_mut().rust_mut().items.get_mut(row as usize) {
-            item.selected = !item.selected;
+    pub fn set_result_filter(mut self: Pin<&mut Self>, text: &QString) {
+        self.as_mut().rust_mut().filter_text = text.to_string().trim().to_lowercase();
+        self.refilter();
+    }
+
+    pub fn set_year_filter(mut self: Pin<&mut Self>, min_year: i32, max_year: i32) {
+        {
+            let mut rust = self.as_mut().rust_mut();
+            rust.min_year = min_year.max(0);
+            rust.max_year = max_year.max(0);
         }
-        let count = self.items.iter().filter(|i| i.selected).count() as i32;
-        self.as_mut().set_selected_count(count);
+        self.refilter();
+    }
 
-        // Notify QML the data changed - trigger full reset for simplicity
+    pub fn set_result_sort(mut self: Pin<&mut Self>, mode: &QString) {
+        let mode = match mode.to_string().as_str() {
+            mode @ ("rating" | "popularity" | "year" | "title") => mode.to_string(),
+            _ => String::new(),
+        };
+        if self.sort_mode == mode {
+            return;
+        }
+        self.as_mut().rust_mut().sort_mode = mode;
+        self.refilter();
+    }
+
+    /// Rebuild the visible rows. Selection flags on hidden results are kept
+    /// but only visible ones count toward `selected_count`.
+    fn refilter(mut self: Pin<&mut Self>) {
         unsafe {
             self.as_mut().begin_reset_model_search();
+            self.as_mut().rust_mut().apply_filter();
             self.as_mut().end_reset_model_search();
         }
+        let count = self.visible_selected_count();
+        self.as_mut().set_selected_count(count);
     }
 
+    pub fn toggle_selection(mut self: Pin<&mut Self>, row: i32) {
+        if !self.as_mut().rust_mut().toggle_row(row) {
+            return;
+        }
+        let count = self.visible_selected_count();
+        self.as_mut().set_selected_count(count);
+
+        // Only this row changed; a reset would scroll the list back to the top
+        let idx = self.index(row, 0, &QModelIndex::default());
+        let mut roles = QVector::<i32>::default();
+        roles.append(SEARCH_ROLE_SELECTED);
+        self.as_mut().data_changed(&idx, &idx, &roles);
+    }
+
+    pub fn set_allow_owned(mut self: Pin<&mut Self>, allow: bool) {
+        self.as_mut().rust_mut().allow_owned = allow;
+        if !allow {
+            // Drops owned rows from the selection, leaves the rest as is
+            self.update_all_selected(|selected| selected);
+        }
+    }
+
+    pub fn select_all(self: Pin<&mut Self>) {
+        self.update_all_selected(|_| true);
+    }
+
+    pub fn select_none(self: Pin<&mut Self>) {
+        self.update_all_selected(|_| false);
+    }
+
+    pub fn invert_selection(self: Pin<&mut Self>) {
+        self.update_all_selected(|selected| !selected);
+    }
+
+    /// Update every visible row's selection, then notify with one ranged
+    /// dataChanged. Each row keeps its result `index`, so
+    /// `get_selected_indices` still maps back to `search_results`.
+    fn update_all_selected(mut self: Pin<&mut Self>, f: impl Fn(bool) -> bool) {
+        self.as_mut().rust_mut().update_visible_selected(f);
+        let count = self.visible_selected_count();
+        self.as_mut().set_selected_count(count);
+
+        let rows = self.rows.len() as i32;
+        if rows == 0 {
+            return;
+        }
+        let top = self.index(0, 0, &QModelIndex::default());
+        let bottom = self.index(rows - 1, 0, &QModelIndex::default());
+        let mut roles = QVector::<i32>::default();
+        roles.append(SEARCH_ROLE_SELECTED);
+        self.as_mut().data_changed(&top, &bottom, &roles);
+    }
+
+    /// Result indices (into `search_results`) of the visible selected rows
     pub fn get_selected_indices(&self) -> QString {
         let indices: Vec<String> = self
-            .items
-            .iter()
+            .visible_items()
             .filter(|i| i.selected)
             .map(|i| i.index.to_string())
             .collect();
         QString::from(&indices.join(","))
     }
 
+    pub fn refresh_poster(mut self: Pin<&mut Self>, result_index: i32) {
+        let state = get_app_state();
+        let path = state
+            .cached_poster_paths
+            .lock()
+            .unwrap()
+            .get(result_index as usize)
+            .cloned()
+            .flatten();
+        let Some(path) = path.map(|p| crate::images::cache::stored_poster_url(&p, &state.data_dir)) else {
+            return;
+        };
+
+        // `items` is in result order; the row may differ while a filter is active
+        match self.as_mut().rust_mut().items.get_mut(result_index as usize) {
+            Some(item) => {
+                item.poster_path = path;
+                item.has_poster = true;
+            }
+            None => return,
+        }
+        let Some(row) = self.rows.iter().position(|&pos| pos == result_index as usize) else {
+            return;
+        };
+        let row = row as i32;
+
+        let idx = self.index(row, 0, &QModelIndex::default());
+        let mut roles = QVector::<i32>::default();
+        roles.append(SEARCH_ROLE_POSTER_PATH);
+        roles.append(SEARCH_ROLE_HAS_POSTER);
+        self.as_mut().data_changed(&idx, &idx, &roles);
+    }
+
     pub fn clear(mut self: Pin<&mut Self>) {
         unsafe {
             self.as_mut().begin_reset_model_search();
             self.as_mut().rust_mut().items.clear();
+            self.as_mut().rust_mut().rows.clear();
             self.as_mut().set_selected_count(0);
             self.as_mut().end_reset_model_search();
         }
     }
 }
+
+// ═══════════════════════════════════════════════════════════════════════
+// StatsModel
+// ═══════════════════════════════════════════════════════════════════════
+
+const STATS_ROLE_CATEGORY: i32 = 256;
+const STATS_ROLE_LABEL: i32 = 257;
+const STATS_ROLE_COUNT: i32 = 258;
+const STATS_ROLE_SHARE: i32 = 259;
+
+/// Categories `reload` loads when none is given, in display order
+const STATS_CATEGORIES: [&str; 6] = ["media_type", "status", "quality_type", "source", "year", "watched"];
+
+struct StatsRow {
+    category: String,
+    label: String, // empty for items with no value
+    count: i64,
+    /// count relative to the largest row in its category (0..=1), for bar widths
+    share: f64,
+}
+
+#[derive(Default)]
+pub struct StatsModelRust {
+    rows: Vec<StatsRow>,
+}
+
+impl qobject::StatsModel {
+    pub fn data(&self, index: &QModelIndex, role: i32) -> QVariant {
+        if let Some(row) = self.rows.get(index.row() as usize) {
+            return match role {
+                STATS_ROLE_CATEGORY => QVariant::from(&QString::from(&row.category)),
+                STATS_ROLE_LABEL => QVariant::from(&QString::from(&row.label)),
+                STATS_ROLE_COUNT => QVariant::from(&(row.count as i32)),
+                STATS_ROLE_SHARE => QVariant::from(&row.share),
+                _ => QVariant::default(),
+            };
+        }
+        QVariant::default()
+    }
+
+    pub fn role_names(&self) -> QHash<QHashPair_i32_QByteArray> {
+        let mut roles = QHash::<QHashPair_i32_QByteArray>::default();
+        roles.insert(STATS_ROLE_CATEGORY, QByteArray::from("category"));
+        roles.insert(STATS_ROLE_LABEL, QByteArray::from("label"));
+        roles.insert(STATS_ROLE_COUNT, QByteArray::from("count"));
+        roles.insert(STATS_ROLE_SHARE, QByteArray::from("share"));
+        roles
+    }
+
+    pub fn row_count(&self, _parent: &QModelIndex) -> i32 {
+        self.rows.len() as i32
+    }
+
+    pub fn reload(mut self: Pin<&mut Self>, category: &QString, page: &QString) {
+        let category = category.to_string();
+        let page = page.to_string();
+        let categories: Vec<&str> = if category.is_empty() {
+            STATS_CATEGORIES.to_vec()
+        } else {
+            vec![category.as_str()]
+        };
+        let media_type = (!page.is_empty()).then_some(page.as_str());
+
+        let mut rows = Vec::new();
+        {
+            let state = get_app_state();
+            let conn = state.db.lock().unwrap();
+            for category in categories {
+                // A failed query just leaves that category out
+                let counts = db::queries::count_by_column(&conn, category, media_type).unwrap_or_default();
+                let max = counts.iter().map(|(_, count)| *count).max().unwrap_or(0).max(1);
+                rows.extend(counts.into_iter().map(|(label, count)| StatsRow {
+                    category: category.to_string(),
+                    label,
+                    count,
+                    share: count as f64 / max as f64,
+                }));
+            }
+        }
+
+        unsafe {
+            self.as_mut().begin_reset_model_stats();
+            self.as_mut().rust_mut().rows = rows;
+            self.as_mut().end_reset_model_stats();
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// NotificationModel
+// ═══════════════════════════════════════════════════════════════════════
+
+const NOTIFICATION_ROLE_TIMESTAMP: i32 = 256;
+const NOTIFICATION_ROLE_TYPE: i32 = 257;
+const NOTIFICATION_ROLE_MESSAGE: i32 = 258;
+const NOTIFICATION_ROLE_DETAILS: i32 = 259;
+const NOTIFICATION_ROLE_READ: i32 = 260;
+
+#[derive(Default)]
+pub struct NotificationModelRust {
+    unread_count: i32,
+    rows: Vec<Notification>,
+}
+
+impl qobject::NotificationModel {
+    pub fn data(&self, index: &QModelIndex, role: i32) -> QVariant {
+        if let Some(row) = self.rows.get(index.row() as usize) {
+            return match role {
+                // Milliseconds, so QML can pass it straight to `new Date()`
+                NOTIFICATION_ROLE_TIMESTAMP => QVariant::from(&(row.timestamp as f64)),
+                NOTIFICATION_ROLE_TYPE => QVariant::from(&QString::from(&row.toast_type)),
+                NOTIFICATION_ROLE_MESSAGE => QVariant::from(&QString::from(&row.message)),
+                NOTIFICATION_ROLE_DETAILS => QVariant::from(&QString::from(&row.details)),
+                NOTIFICATION_ROLE_READ => QVariant::from(&row.read),
+                _ => QVariant::default(),
+            };
+        }
+        QVariant::default()
+    }
+
+    pub fn role_names(&self) -> QHash<QHashPair_i32_QByteArray> {
+        let mut roles = QHash::<QHashPair_i32_QByteArray>::default();
+        roles.insert(NOTIFICATION_ROLE_TIMESTAMP, QByteArray::from("timestamp"));
+        roles.insert(NOTIFICATION_ROLE_TYPE, QByteArray::from("toastType"));
+        roles.insert(NOTIFICATION_ROLE_MESSAGE, QByteArray::from("message"));
+        roles.insert(NOTIFICATION_ROLE_DETAILS, QByteArray::from("details"));
+        roles.insert(NOTIFICATION_ROLE_READ, QByteArray::from("read"));
+        roles
+    }
+
+    pub fn row_count(&self, _parent: &QModelIndex) -> i32 {
+        self.rows.len() as i32
+    }
+
+    pub fn reload(mut self: Pin<&mut Self>) {
+        let rows: Vec<Notification> = {
+            let state = get_app_state();
+            let notifications = state.notifications.lock().unwrap();
+            notifications.iter().cloned().collect()
+        };
+        let unread = rows.iter().filter(|n| !n.read).count() as i32;
+
+        unsafe {
+            self.as_mut().begin_reset_model_notifications();
+            self.as_mut().rust_mut().rows = rows;
+            self.as_mut().end_reset_model_notifications();
+        }
+        self.as_mut().set_unread_count(unread);
+    }
+
+    pub fn clear(self: Pin<&mut Self>) {
+        get_app_state().notifications.lock().unwrap().clear();
+        self.reload();
+    }
+
+    pub fn mark_read(self: Pin<&mut Self>) {
+        {
+            let state = get_app_state();
+            let mut notifications = state.notifications.lock().unwrap();
+            for notification in notifications.iter_mut() {
+                notification.read = true;
+            }
+        }
+        self.reload();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(index: i32, title: &str, owned_status: Option<&str>) -> SearchItem {
+        SearchItem {
+            title: title.to_string(),
+            native_title: String::new(),
+            romaji_title: String::new(),
+            year: 2000,
+            overview: String::new(),
+            poster_path: String::new(),
+            poster_url: String::new(),
+            has_poster: false,
+            selected: false,
+            index,
+            owned_status: owned_status.map(str::to_string),
+            vote_average: 0.0,
+            popularity: 0.0,
+            vote_count: 0,
+            anime_format: String::new(),
+            season: String::new(),
+        }
+    }
+
+    fn search_model(titles: &[(&str, Option<&str>)]) -> SearchModelRust {
+        let mut model = SearchModelRust {
+            items: titles
+                .iter()
+                .enumerate()
+                .map(|(i, (title, owned))| result(i as i32, title, *owned))
+                .collect(),
+            ..Default::default()
+        };
+        model.apply_filter();
+        model
+    }
+
+    #[test]
+    fn toggling_a_row_updates_selected_count() {
+        let mut model = search_model(&[("Alien", None), ("Aliens", None), ("Alien 3", None)]);
+        assert!(model.toggle_row(0));
+        assert!(model.toggle_row(2));
+        assert_eq!(model.visible_selected_count(), 2);
+
+        assert!(model.toggle_row(0));
+        assert_eq!(model.visible_selected_count(), 1);
+
+        assert!(!model.toggle_row(3));
+        assert!(!model.toggle_row(-1));
+        assert_eq!(model.visible_selected_count(), 1);
+    }
+
+    #[test]
+    fn owned_rows_are_not_selectable() {
+        let mut model = search_model(&[("Alien", Some("Watched")), ("Aliens", None)]);
+        assert!(!model.toggle_row(0));
+        model.update_visible_selected(|_| true);
+        assert_eq!(model.visible_selected_count(), 1);
+    }
+
+    #[test]
+    fn owned_rows_are_selectable_when_duplicates_are_allowed() {
+        let mut model = search_model(&[("Alien", Some("Watched")), ("Aliens", None)]);
+        model.allow_owned = true;
+        assert!(model.toggle_row(0));
+        assert_eq!(model.visible_selected_count(), 1);
+        model.update_visible_selected(|_| true);
+        assert_eq!(model.visible_selected_count(), 2);
+
+        // Turning it back off drops only the owned row
+        model.allow_owned = false;
+        model.update_visible_selected(|selected| selected);
+        assert_eq!(model.visible_selected_count(), 1);
+        assert!(!model.items[0].selected);
+    }
+
+    #[test]
+    fn hidden_selections_do_not_count() {
+        let mut model = search_model(&[("Alien", None), ("Predator", None)]);
+        model.update_visible_selected(|_| true);
+        model.filter_text = "pred".to_string();
+        model.apply_filter();
+        assert_eq!(model.visible_selected_count(), 1);
+    }
+}
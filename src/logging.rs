@@ -0,0 +1,53 @@
+//! Log file under the data dir, so problems on someone else's machine can
+//! be looked into afterwards.
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::prelude::*;
+
+/// Daily files kept; older ones are deleted when a new one starts
+const MAX_LOG_FILES: usize = 5;
+const LOG_PREFIX: &str = "media-tracker";
+const LOG_SUFFIX: &str = "log";
+
+fn log_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("logs")
+}
+
+/// Write this app's events at `level` ("error", "warn", "info", "debug" or
+/// "trace"; anything else means "info") to data/logs. Without a usable log
+/// directory the app just runs unlogged.
+pub fn init(data_dir: &Path, level: &str) {
+    let level = LevelFilter::from_str(level).unwrap_or(LevelFilter::INFO);
+    let appender = match RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_PREFIX)
+        .filename_suffix(LOG_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(log_dir(data_dir))
+    {
+        Ok(appender) => appender,
+        Err(e) => {
+            eprintln!("Logging disabled: {}", e);
+            return;
+        }
+    };
+    // Only our own events; reqwest/hyper internals would drown them out
+    let _ = tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_writer(appender).with_ansi(false))
+        .with(Targets::new().with_target(env!("CARGO_CRATE_NAME"), level))
+        .try_init();
+}
+
+/// The newest log file, if one has been written
+pub fn current_log_file(data_dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(log_dir(data_dir))
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == LOG_SUFFIX))
+        .max_by_key(|path| path.metadata().and_then(|m| m.modified()).ok())
+}
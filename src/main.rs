@@ -1,7 +1,11 @@
 mod api;
+mod cli;
 mod config;
 mod db;
+mod export;
 mod images;
+mod logging;
+mod maintenance;
 mod models;
 
 pub mod bridge;
@@ -10,8 +14,14 @@ pub mod list_models;
 use cxx_qt_lib::{QGuiApplication, QQmlApplicationEngine, QUrl};
 
 fn main() {
+    // `--exec '<json>'` runs one scripted command against the DB and exits
+    if let Some(code) = cli::run_from_args() {
+        std::process::exit(code);
+    }
+
     // Initialize app state (DB, config, etc.) before Qt starts
     bridge::init_app_state();
+    maintenance::scheduler::start_idle_worker();
 
     // Force "Basic" Qt Quick Controls style so all controls (Menu, ComboBox,
     // Dialog buttons, etc.) fully respect the dark palette set in QML.
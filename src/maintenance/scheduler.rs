@@ -0,0 +1,359 @@
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::bridge::{get_app_state, AppState};
+use crate::db;
+use crate::images;
+
+/// How often the worker wakes up to check for idleness.
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Low-priority jobs run by the idle scheduler. Each call to `step` does one
+/// small unit of work so the worker can back off between units.
+pub enum MaintenanceTask {
+    WalCheckpoint,
+    OrphanCleanup { pending: Option<Vec<PathBuf>> },
+    /// Trim prefetched search posters to `SEARCH_CACHE_MAX_FILES`.
+    CacheRetention,
+    /// Pre-generate the grid copy of each cached poster so the grid doesn't
+    /// have to decode it when first shown.
+    ThumbnailGeneration { pending: Option<Vec<PathBuf>> },
+}
+
+/// The tasks queued at startup and again for each later idle period.
+fn default_tasks() -> Vec<MaintenanceTask> {
+    vec![
+        MaintenanceTask::OrphanCleanup { pending: None },
+        MaintenanceTask::CacheRetention,
+        MaintenanceTask::ThumbnailGeneration { pending: None },
+        MaintenanceTask::WalCheckpoint,
+    ]
+}
+
+impl MaintenanceTask {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MaintenanceTask::WalCheckpoint => "wal_checkpoint",
+            MaintenanceTask::OrphanCleanup { .. } => "orphan_cleanup",
+            MaintenanceTask::CacheRetention => "cache_retention",
+            MaintenanceTask::ThumbnailGeneration { .. } => "thumbnail_generation",
+        }
+    }
+
+    /// Run one unit of work. Returns true once the task is finished.
+    fn step(&mut self, state: &AppState) -> bool {
+        let cache_dir = state.data_dir.join("image_cache");
+        match self {
+            MaintenanceTask::WalCheckpoint => {
+                let conn = state.db.lock().unwrap();
+                let _ = conn.execute_batch("PRAGMA wal_checkpoint(PASSIVE);");
+                true
+            }
+            MaintenanceTask::OrphanCleanup { pending } => match pending {
+                None => {
+                    let referenced = {
+                        let conn = state.db.lock().unwrap();
+                        db::queries::get_all_poster_urls(&conn).unwrap_or_default()
+                    };
+                    let orphans = images::cache::find_orphaned_files(&cache_dir, &referenced, &state.data_dir);
+                    let done = orphans.is_empty();
+                    *pending = Some(orphans);
+                    done
+                }
+                Some(files) => {
+                    if let Some(path) = files.pop() {
+                        // An item may have picked the file up since the scan;
+                        // hold the db lock so none can until it's gone.
+                        let conn = state.db.lock().unwrap();
+                        let referenced = db::queries::get_all_poster_urls(&conn).unwrap_or_default();
+                        if images::cache::is_orphaned(&path, &cache_dir, &referenced, &state.data_dir) {
+                            let _ = std::fs::remove_file(path);
+                        }
+                    }
+                    files.is_empty()
+                }
+            },
+            MaintenanceTask::CacheRetention => {
+                images::cache::evict_search_cache(&cache_dir, images::cache::SEARCH_CACHE_MAX_FILES);
+                true
+            }
+            MaintenanceTask::ThumbnailGeneration { pending } => match pending {
+                None => {
+                    let referenced = {
+                        let conn = state.db.lock().unwrap();
+                        db::queries::get_all_poster_urls(&conn).unwrap_or_default()
+                    };
+                    let originals: Vec<PathBuf> = referenced
+                        .iter()
+                        .filter(|p| !p.starts_with("http://") && !p.starts_with("https://"))
+                        .map(|p| images::cache::resolve_cached_poster_path(p, &state.data_dir))
+                        .filter(|p| p.is_file())
+                        .collect();
+                    let done = originals.is_empty();
+                    *pending = Some(originals);
+                    done
+                }
+                Some(files) => {
+                    if let Some(original) = files.pop() {
+                        let uniform = state.config.lock().unwrap().uniform_posters;
+                        if uniform {
+                            images::cache::ensure_framed(&original);
+                        } else {
+                            images::cache::ensure_thumbnail(&original);
+                        }
+                    }
+                    files.is_empty()
+                }
+            },
+        }
+    }
+}
+
+/// Source of the current time, so tests can drive idleness by hand.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Tracks user activity and holds queued maintenance tasks.
+pub struct IdleScheduler {
+    clock: Box<dyn Clock>,
+    last_activity: Mutex<Instant>,
+    /// Activity timestamp as of the last time the default tasks were queued
+    filled_at: Mutex<Option<Instant>>,
+    queue: Mutex<VecDeque<MaintenanceTask>>,
+}
+
+impl IdleScheduler {
+    pub fn new() -> Self {
+        Self::with_clock(Box::new(SystemClock))
+    }
+
+    pub fn with_clock(clock: Box<dyn Clock>) -> Self {
+        Self {
+            last_activity: Mutex::new(clock.now()),
+            clock,
+            filled_at: Mutex::new(None),
+            queue: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Record user activity; any running task pauses after its current unit.
+    pub fn touch(&self) {
+        *self.last_activity.lock().unwrap() = self.clock.now();
+    }
+
+    fn is_idle(&self, threshold: Duration) -> bool {
+        let last = *self.last_activity.lock().unwrap();
+        self.clock.now().saturating_duration_since(last) >= threshold
+    }
+
+    pub fn enqueue(&self, task: MaintenanceTask) {
+        self.queue.lock().unwrap().push_back(task);
+    }
+
+    pub fn queued_names(&self) -> Vec<&'static str> {
+        self.queue.lock().unwrap().iter().map(|t| t.name()).collect()
+    }
+
+    /// Queue `tasks()` once the queue has drained, but only if the user has
+    /// been active since the last fill, so each idle period gets one pass.
+    fn refill(&self, tasks: impl FnOnce() -> Vec<MaintenanceTask>) {
+        let last = *self.last_activity.lock().unwrap();
+        let mut filled_at = self.filled_at.lock().unwrap();
+        let mut queue = self.queue.lock().unwrap();
+        if queue.is_empty() && *filled_at != Some(last) {
+            queue.extend(tasks());
+            *filled_at = Some(last);
+        }
+    }
+
+    /// If the user has been idle for `threshold`, take the next task and run
+    /// units of it with `step` until it finishes or the user comes back, in
+    /// which case the partly-done task goes back to the front of the queue.
+    fn run_next(&self, threshold: Duration, mut step: impl FnMut(&mut MaintenanceTask) -> bool) {
+        if !self.is_idle(threshold) {
+            return;
+        }
+        let Some(mut task) = self.queue.lock().unwrap().pop_front() else {
+            return;
+        };
+        loop {
+            if step(&mut task) {
+                break;
+            }
+            if !self.is_idle(threshold) {
+                self.queue.lock().unwrap().push_front(task);
+                break;
+            }
+        }
+    }
+}
+
+impl Default for IdleScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Queue the default tasks and start the worker thread.
+pub fn start_idle_worker() {
+    let state = get_app_state();
+    state.maintenance.refill(default_tasks);
+
+    std::thread::spawn(move || loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let (enabled, minutes) = {
+            let cfg = state.config.lock().unwrap();
+            (cfg.idle_maintenance, cfg.idle_maintenance_minutes)
+        };
+        let threshold = Duration::from_secs(u64::from(minutes) * 60);
+        if !enabled {
+            continue;
+        }
+        state.maintenance.refill(default_tasks);
+        state.maintenance.run_next(threshold, |task| task.step(&state));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    const THRESHOLD: Duration = Duration::from_secs(300);
+
+    #[derive(Clone)]
+    struct MockClock(Arc<Mutex<Instant>>);
+
+    impl MockClock {
+        fn advance(&self, by: Duration) {
+            *self.0.lock().unwrap() += by;
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> Instant {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    fn scheduler() -> (IdleScheduler, MockClock) {
+        let clock = MockClock(Arc::new(Mutex::new(Instant::now())));
+        (IdleScheduler::with_clock(Box::new(clock.clone())), clock)
+    }
+
+    fn cleanup(files: usize) -> MaintenanceTask {
+        MaintenanceTask::OrphanCleanup {
+            pending: Some((0..files).map(|i| PathBuf::from(i.to_string())).collect()),
+        }
+    }
+
+    /// Pop one pending file per unit, like the real cleanup step.
+    fn pop_one(task: &mut MaintenanceTask) -> bool {
+        match task {
+            MaintenanceTask::OrphanCleanup { pending: Some(files) } => {
+                files.pop();
+                files.is_empty()
+            }
+            _ => true,
+        }
+    }
+
+    fn remaining(sched: &IdleScheduler) -> Option<usize> {
+        match sched.queue.lock().unwrap().front() {
+            Some(MaintenanceTask::OrphanCleanup { pending: Some(files) }) => Some(files.len()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn waits_for_idle_threshold() {
+        let (sched, clock) = scheduler();
+        sched.enqueue(cleanup(3));
+
+        clock.advance(THRESHOLD - Duration::from_secs(1));
+        let mut units = 0;
+        sched.run_next(THRESHOLD, |t| {
+            units += 1;
+            pop_one(t)
+        });
+        assert_eq!(units, 0);
+
+        clock.advance(Duration::from_secs(1));
+        sched.run_next(THRESHOLD, |t| {
+            units += 1;
+            pop_one(t)
+        });
+        assert_eq!(units, 3);
+        assert!(sched.queued_names().is_empty());
+    }
+
+    #[test]
+    fn activity_pauses_and_idle_resumes() {
+        let (sched, clock) = scheduler();
+        sched.enqueue(cleanup(5));
+        sched.enqueue(MaintenanceTask::WalCheckpoint);
+        clock.advance(THRESHOLD);
+
+        // The user comes back during the second unit
+        let mut units = 0;
+        sched.run_next(THRESHOLD, |t| {
+            units += 1;
+            if units == 2 {
+                sched.touch();
+            }
+            pop_one(t)
+        });
+        assert_eq!(units, 2);
+        assert_eq!(sched.queued_names(), vec!["orphan_cleanup", "wal_checkpoint"]);
+        assert_eq!(remaining(&sched), Some(3));
+
+        // Still active: nothing runs
+        sched.run_next(THRESHOLD, pop_one);
+        assert_eq!(remaining(&sched), Some(3));
+
+        // Idle again: picks up where it left off
+        clock.advance(THRESHOLD);
+        let mut units = 0;
+        sched.run_next(THRESHOLD, |t| {
+            units += 1;
+            pop_one(t)
+        });
+        assert_eq!(units, 3);
+        assert_eq!(sched.queued_names(), vec!["wal_checkpoint"]);
+    }
+
+    #[test]
+    fn refills_once_per_idle_period() {
+        let (sched, clock) = scheduler();
+        sched.refill(default_tasks);
+        let defaults = sched.queued_names();
+        assert_eq!(defaults.len(), default_tasks().len());
+
+        clock.advance(THRESHOLD);
+        while !sched.queued_names().is_empty() {
+            sched.run_next(THRESHOLD, |_| true);
+        }
+
+        // Same idle period: the drained queue stays empty
+        sched.refill(default_tasks);
+        assert!(sched.queued_names().is_empty());
+
+        // After fresh activity the tasks come back
+        clock.advance(Duration::from_secs(1));
+        sched.touch();
+        sched.refill(default_tasks);
+        assert_eq!(sched.queued_names(), defaults);
+    }
+}
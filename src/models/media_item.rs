@@ -1,6 +1,8 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MediaItem {
     pub id: Option<i64>,
     pub title: String,
@@ -14,9 +16,35 @@ pub struct MediaItem {
     pub notes: Option<String>,
     pub tmdb_id: Option<i64>,
     pub anilist_id: Option<i64>,
+    /// IMDb title id ("tt0133093"), looked up from TMDB for movies and TV
+    #[serde(default)]
+    pub imdb_id: Option<String>,
+    /// AniList release format ("TV", "Movie", "OVA", ...) and season ("Winter", ...)
+    #[serde(default)]
+    pub anime_format: Option<String>,
+    #[serde(default)]
+    pub season: Option<String>,
     pub poster_url: Option<String>,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
+    pub number_of_seasons: Option<i32>,
+    pub number_of_episodes: Option<i32>,
+    pub progress: Option<i32>,
+    pub total_episodes: Option<i32>,
+    #[serde(default)]
+    pub favorite: bool,
+    pub acquired_date: Option<String>, // YYYY-MM-DD
+    pub watched_date: Option<String>,  // YYYY-MM-DD
+    #[serde(default)]
+    pub poster_width: i32, // 0 when unknown
+    #[serde(default)]
+    pub poster_height: i32,
+    /// Seen it, independent of status (which tracks acquisition)
+    #[serde(default)]
+    pub watched: bool,
+    /// Poster was set by hand from a URL rather than picked from the provider
+    #[serde(default)]
+    pub poster_manual: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +56,24 @@ pub struct SearchResult {
     pub year: Option<i32>,
     pub overview: Option<String>,
     pub poster_url: Option<String>,
+    /// Episode (anime) or chapter (manga) count when the provider reports it
+    #[serde(default)]
+    pub total_episodes: Option<i32>,
+    /// Average user score out of 10
+    #[serde(default)]
+    pub vote_average: Option<f64>,
+    /// Provider popularity (TMDB's popularity score, AniList's member count)
+    #[serde(default)]
+    pub popularity: Option<f64>,
+    /// Number of user ratings behind vote_average; TMDB only
+    #[serde(default)]
+    pub vote_count: Option<i64>,
+    /// AniList release format ("TV", "Movie", "OVA", ...); anime only
+    #[serde(default)]
+    pub anime_format: Option<String>,
+    /// AniList release season ("Winter", "Spring", ...); anime only
+    #[serde(default)]
+    pub season: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,7 +82,16 @@ pub struct BatchAddResult {
     pub skipped: i32,
     pub errors: i32,
     pub added_items: Vec<String>,
+    /// "New Title → why it was skipped", one per skipped item
     pub skipped_items: Vec<String>,
+    /// Library id each skipped item duplicates, in `skipped_items` order
+    /// (None for repeats within the batch)
+    #[serde(default)]
+    pub skipped_matches: Vec<Option<i64>>,
+    /// Duplicates added anyway because skipping was off, labelled like
+    /// `skipped_items`; these are also in `added_items`
+    #[serde(default)]
+    pub forced_items: Vec<String>,
     pub error_items: Vec<String>,
 }
 
@@ -44,17 +99,126 @@ pub struct BatchAddResult {
 pub struct AppConfig {
     pub tmdb_api_key: String,
     pub quality_types: Vec<String>,
+    #[serde(default = "default_statuses")]
+    pub statuses: Vec<String>,
     pub view_mode: String,
     #[serde(default)]
     pub include_adult: bool,
     #[serde(default = "default_row_height")]
     pub row_height: i32,
+    #[serde(default = "default_true")]
+    pub idle_maintenance: bool,
+    #[serde(default = "default_idle_maintenance_minutes")]
+    pub idle_maintenance_minutes: u32,
+    /// TMDB poster size for new downloads ("w342", "w500", "original", ...)
+    #[serde(default = "default_poster_size")]
+    pub poster_size: String,
+    /// TMDB search language ("en-US", "de-DE", ...)
+    #[serde(default = "default_tmdb_language")]
+    pub tmdb_language: String,
+    /// TMDB release region (ISO 3166-1, e.g. "DE"); empty for none
+    #[serde(default)]
+    pub tmdb_region: String,
+    /// TMDB search result pages fetched per query (1-5, 20 results each)
+    #[serde(default = "default_tmdb_result_pages")]
+    pub tmdb_result_pages: u32,
+    /// Table sort ("title", "year", ...) and direction ("ASC"/"DESC")
+    #[serde(default = "default_sort_field")]
+    pub sort_field: String,
+    #[serde(default = "default_sort_dir")]
+    pub sort_dir: String,
+    /// Tie-breaker applied after sort_field
+    #[serde(default = "default_sort_field")]
+    pub sort_field2: String,
+    #[serde(default = "default_sort_dir")]
+    pub sort_dir2: String,
+    /// Sort "The Matrix" under M and "2 Fast" before "22 Jump"
+    #[serde(default = "default_true")]
+    pub ignore_articles: bool,
+    /// Download posters for online search results before anything is added
+    #[serde(default)]
+    pub prefetch_search_posters: bool,
+    /// Show grid posters cropped or letterboxed to a uniform 2:3
+    #[serde(default = "default_true")]
+    pub uniform_posters: bool,
+    /// Status new items of a media type start in, keyed by media type.
+    /// Types without an entry use the first status.
+    #[serde(default)]
+    pub default_status_per_type: HashMap<String, String>,
+    /// Recent online search queries per media type, newest first
+    #[serde(default)]
+    pub search_history: HashMap<String, Vec<String>>,
+    /// Keep tmdb_api_key in the system keyring rather than in config.json
+    #[serde(default)]
+    pub use_keyring: bool,
+    /// Log file verbosity ("error", "warn", "info", "debug", "trace");
+    /// read at startup
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    /// config.json gets a placeholder instead of the key (set while loading
+    /// and saving; see `config::secrets`)
+    #[serde(skip)]
+    pub api_key_in_keyring: bool,
+}
+
+impl AppConfig {
+    /// The status a media type's page opens on and new items get. Falls back
+    /// to the first status when unset or no longer configured.
+    pub fn default_status_for(&self, media_type: &str) -> String {
+        self.default_status_per_type
+            .get(media_type)
+            .filter(|status| self.statuses.contains(status))
+            .or_else(|| self.statuses.first())
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Media types whose metadata comes from AniList rather than TMDB.
+pub fn is_anilist_type(media_type: &str) -> bool {
+    matches!(media_type, "Anime" | "Manga")
+}
+
+pub fn default_statuses() -> Vec<String> {
+    vec!["On Drive".into(), "To Download".into(), "To Work On".into()]
 }
 
 fn default_row_height() -> i32 {
     44
 }
 
+fn default_true() -> bool {
+    true
+}
+
+fn default_idle_maintenance_minutes() -> u32 {
+    5
+}
+
+fn default_poster_size() -> String {
+    "w500".into()
+}
+
+fn default_tmdb_language() -> String {
+    "en-US".into()
+}
+
+fn default_tmdb_result_pages() -> u32 {
+    2
+}
+
+fn default_log_level() -> String {
+    "info".into()
+}
+
+fn default_sort_field() -> String {
+    "title".into()
+}
+
+fn default_sort_dir() -> String {
+    "ASC".into()
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -70,9 +234,28 @@ impl Default for AppConfig {
                 "WEB-DL 2160p".into(),
                 "WebDL".into(),
             ],
+            statuses: default_statuses(),
             view_mode: "grid".into(),
             include_adult: false,
             row_height: 44,
+            idle_maintenance: true,
+            idle_maintenance_minutes: 5,
+            poster_size: default_poster_size(),
+            tmdb_language: default_tmdb_language(),
+            tmdb_region: String::new(),
+            tmdb_result_pages: default_tmdb_result_pages(),
+            sort_field: default_sort_field(),
+            sort_dir: default_sort_dir(),
+            sort_field2: default_sort_field(),
+            sort_dir2: default_sort_dir(),
+            ignore_articles: true,
+            prefetch_search_posters: false,
+            uniform_posters: true,
+            default_status_per_type: HashMap::new(),
+            search_history: HashMap::new(),
+            use_keyring: false,
+            log_level: default_log_level(),
+            api_key_in_keyring: false,
         }
     }
 }
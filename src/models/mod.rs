@@ -1,2 +1,2 @@
 pub mod media_item;
-pub use media_item::{AppConfig, BatchAddResult, MediaItem, SearchResult};
+pub use media_item::{is_anilist_type, AppConfig, BatchAddResult, MediaItem, SearchResult};